@@ -11,6 +11,69 @@ pub const C_FG_B_BLACK: &str = "\x1b[90;1m";
 pub const C_FG_B_WHITE: &str = "\x1b[97;1m";
 pub const COLOR_RESET: &str = "\x1b[m";
 
+/// Human-readable names for each color constant, used for legends and user input.
+pub const COLOR_NAMES: &[(&str, &str)] = &[
+    ("black", C_FG_BLACK),
+    ("red", C_FG_RED),
+    ("green", C_FG_GREEN),
+    ("yellow", C_FG_YELLOW),
+    ("blue", C_FG_BLUE),
+    ("magenta", C_FG_MAGENTA),
+    ("cyan", C_FG_CYAN),
+    ("white", C_FG_WHITE),
+    ("bright-black", C_FG_B_BLACK),
+    ("bright-white", C_FG_B_WHITE),
+];
+
+/// CSS colors for each color constant, used by the HTML output.
+///
+/// Values follow the common xterm palette for bold/bright colors so the HTML output
+/// looks like a typical dark terminal.
+pub const COLOR_CSS: &[(&str, &str)] = &[
+    (C_FG_BLACK, "#7f7f7f"),
+    (C_FG_RED, "#ff5555"),
+    (C_FG_GREEN, "#55ff55"),
+    (C_FG_YELLOW, "#ffff55"),
+    (C_FG_BLUE, "#5c5cff"),
+    (C_FG_MAGENTA, "#ff55ff"),
+    (C_FG_CYAN, "#55ffff"),
+    (C_FG_WHITE, "#e5e5e5"),
+    (C_FG_B_BLACK, "#7f7f7f"),
+    (C_FG_B_WHITE, "#ffffff"),
+];
+
+/// Brightness of the terminal background, detected for `--auto-colors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+/// Swap a logo color that would vanish against the terminal background.
+///
+/// White logo parts become black on light backgrounds, and black parts become
+/// white on dark backgrounds; every other color is kept.
+///
+/// # Arguments
+///
+/// * `color` - One of the `C_FG_*` escape sequences
+/// * `background` - Detected terminal background
+fn contrast_color(color: &'static str, background: Background) -> &'static str {
+    match (background, color) {
+        (Background::Light, C_FG_WHITE | C_FG_B_WHITE) => C_FG_BLACK,
+        (Background::Dark, C_FG_BLACK) => C_FG_WHITE,
+        _ => color,
+    }
+}
+
+/// A vendor logo ready for display.
+pub struct Logo {
+    /// Logo lines with color placeholders substituted
+    pub lines: Vec<String>,
+    /// Colors substituted for `$C1`..`$CN`, in slot order
+    pub colors: Vec<&'static str>,
+}
+
 // ASCII art for vendors (short logos only for now)
 const ASCII_AMD: &str = "\
 $C2          '###############             \n\
@@ -93,8 +156,8 @@ $C1    kMMMMMMMMMMMMMMMMMMMMMMd                \n\
 $C2     ;KMMMMMMMWXXWMMMMMMMk.                 \n\
 $C3       .cooc,.    .,coo:.                   \n";
 
-fn logo_lines_for_vendor(vendor_id: &str) -> Option<Vec<String>> {
-    let (raw_logo, colors): (&str, &[&str]) = match vendor_id {
+fn logo_for_vendor(vendor_id: &str, use_color: bool, palette: Option<&[&'static str]>, background: Option<Background>) -> Option<Logo> {
+    let (raw_logo, default_colors): (&str, &[&'static str]) = match vendor_id {
         "AuthenticAMD" | "amd" => (ASCII_AMD, &[C_FG_WHITE, C_FG_RED]),
         "GenuineIntel" | "intel" => (ASCII_INTEL_NEW, &[C_FG_CYAN]),
         "ARM" | "arm" => (ASCII_ARM, &[C_FG_CYAN]),
@@ -103,16 +166,70 @@ fn logo_lines_for_vendor(vendor_id: &str) -> Option<Vec<String>> {
         "Apple" | "apple" => (ASCII_APPLE, &[C_FG_RED, C_FG_YELLOW, C_FG_GREEN, C_FG_CYAN, C_FG_BLUE, C_FG_MAGENTA, C_FG_WHITE]),
         _ => return None,
     };
+    // A user palette fills the slots in order, repeating if it is shorter
+    let colors: Vec<&'static str> = match palette {
+        Some(palette) if !palette.is_empty() => {
+            (0..default_colors.len()).map(|i| palette[i % palette.len()]).collect()
+        }
+        _ => default_colors.iter()
+            .map(|&color| background.map_or(color, |background| contrast_color(color, background)))
+            .collect(),
+    };
     let mut processed_logo = raw_logo.to_string();
     for (i, color) in colors.iter().enumerate() {
         let placeholder = format!("$C{}", i + 1);
-        processed_logo = processed_logo.replace(&placeholder, color);
+        processed_logo = processed_logo.replace(&placeholder, if use_color { color } else { "" });
     }
-    processed_logo = processed_logo.replace("$CR", COLOR_RESET);
+    processed_logo = processed_logo.replace("$CR", if use_color { COLOR_RESET } else { "" });
     let lines: Vec<String> = processed_logo.lines().map(|l| l.to_string()).collect();
-    Some(lines)
+    Some(Logo { lines, colors })
+}
+
+/// Get the logo for a vendor.
+///
+/// # Arguments
+///
+/// * `vendor_id` - Vendor ID (e.g. "AuthenticAMD") or lowercase vendor name
+/// * `use_color` - Substitute ANSI colors; when `false` the placeholders are removed
+/// * `palette` - Colors for the `$C1`..`$CN` slots (`--logo-colors`), repeated if
+///   shorter than the logo's palette; `None` keeps the vendor's colors
+/// * `background` - Terminal background (`--auto-colors`); the vendor's colors are
+///   adjusted for contrast against it, while a user palette is used as given
+///
+/// # Returns
+///
+/// Returns `Some(Logo)` with the processed lines and the colors used, or `None`
+/// if there is no logo for the vendor.
+pub fn get_logo_for_vendor(vendor_id: &str, use_color: bool, palette: Option<&[&'static str]>, background: Option<Background>) -> Option<Logo> {
+    logo_for_vendor(vendor_id, use_color, palette, background)
+}
+
+/// Get the human-readable name of a color constant.
+///
+/// # Arguments
+///
+/// * `color` - One of the `C_FG_*` escape sequences
+///
+/// # Returns
+///
+/// Returns the color name (e.g. "red"), or "unknown" for unrecognized sequences.
+pub fn color_name(color: &str) -> &'static str {
+    COLOR_NAMES.iter()
+        .find(|(_, code)| *code == color)
+        .map_or("unknown", |(name, _)| name)
 }
 
-pub fn get_logo_lines_for_vendor(vendor_id: &str) -> Option<Vec<String>> {
-    logo_lines_for_vendor(vendor_id)
+/// Get the CSS color for a color constant.
+///
+/// # Arguments
+///
+/// * `color` - One of the `C_FG_*` escape sequences
+///
+/// # Returns
+///
+/// Returns a CSS hex color (e.g. "#ff5555"), or `None` for unrecognized sequences.
+pub fn css_color(color: &str) -> Option<&'static str> {
+    COLOR_CSS.iter()
+        .find(|(code, _)| *code == color)
+        .map(|(_, css)| *css)
 }
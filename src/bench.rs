@@ -0,0 +1,225 @@
+//! Cache latency and memory bandwidth micro-benchmarks for rcpufetch
+//! (`--bench-cache`, `--bench-bandwidth`).
+//!
+//! The cache benchmark measures load-to-use latency with a pointer chase: a buffer is linked into a single
+//! random cycle of cache lines and the benchmark follows it, so every load depends on
+//! the previous one. The random order defeats hardware prefetchers, and the data
+//! dependency prevents the CPU and compiler from overlapping or removing loads. As the
+//! working set outgrows each cache level the latency steps up, which reveals the
+//! cache boundaries.
+//!
+//! The bandwidth benchmark runs STREAM-style copy and triad kernels over arrays much
+//! larger than any cache, split across all logical CPUs so every memory channel is
+//! kept busy, and reports the best rate seen.
+//!
+//! Results are measured on the running system and vary between runs with clock
+//! speed, background load, and memory placement.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Cache line size assumed when spacing the chain's nodes.
+const LINE_BYTES: usize = 64;
+
+/// Smallest and largest working set sizes, in KB.
+const MIN_SIZE_KB: usize = 4;
+const MAX_SIZE_KB: usize = 256 * 1024;
+
+/// Number of dependent loads timed per working set size.
+const LOADS: usize = 1 << 22;
+
+/// Latency increase over the previous size that marks a likely cache boundary.
+const BOUNDARY_RATIO: f64 = 1.4;
+
+/// Size of each of the three bandwidth arrays, in bytes.
+const BANDWIDTH_ARRAY_BYTES: usize = 128 * 1024 * 1024;
+
+/// Number of times each bandwidth kernel is run; the best run is reported.
+const BANDWIDTH_REPS: usize = 5;
+
+/// Scalar used by the triad kernel (`a = b + SCALAR * c`).
+const TRIAD_SCALAR: f64 = 3.0;
+
+/// Run the benchmark and print a table of working set size and latency.
+pub fn print_cache_benchmark() {
+    println!("Cache latency benchmark (measured; results vary between runs)");
+    println!();
+    println!("{:>12}  {:>10}", "Working set", "Latency");
+
+    let mut previous_ns: Option<f64> = None;
+    let mut size_kb = MIN_SIZE_KB;
+    while size_kb <= MAX_SIZE_KB {
+        let ns = measure_latency_ns(size_kb * 1024);
+        let marker = match previous_ns {
+            Some(previous) if ns >= previous * BOUNDARY_RATIO => "  <- likely cache boundary",
+            _ => "",
+        };
+        println!("{:>12}  {:>7.1} ns{}", format_size_kb(size_kb), ns, marker);
+        previous_ns = Some(ns);
+        size_kb *= 2;
+    }
+
+    println!();
+    println!("A boundary at N means a cache level holds at least N/2 but less than N.");
+}
+
+/// Run the bandwidth benchmark and print the peak memory bandwidth.
+///
+/// Bytes are counted the way STREAM counts them: copy reads and writes one array
+/// (2 × size), triad reads two arrays and writes one (3 × size).
+pub fn print_bandwidth_benchmark() {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let len = BANDWIDTH_ARRAY_BYTES / std::mem::size_of::<f64>();
+    let mut a = vec![0.0; len];
+    let mut b = vec![0.0; len];
+    let mut c = vec![0.0; len];
+
+    // Initialize from the worker threads so pages are placed near the CPUs using them
+    run_parallel(threads, &mut a, &mut b, &mut c, |a, b, c| {
+        a.fill(1.0);
+        b.fill(2.0);
+        c.fill(0.0);
+    });
+
+    let mut copy_gbs: f64 = 0.0;
+    let mut triad_gbs: f64 = 0.0;
+    for _ in 0..BANDWIDTH_REPS {
+        let seconds = run_parallel(threads, &mut a, &mut b, &mut c, |a, _, c| c.copy_from_slice(a));
+        copy_gbs = copy_gbs.max(2.0 * BANDWIDTH_ARRAY_BYTES as f64 / seconds / 1e9);
+
+        let seconds = run_parallel(threads, &mut a, &mut b, &mut c, |a, b, c| {
+            for ((a, b), c) in a.iter_mut().zip(b.iter()).zip(c.iter()) {
+                *a = b + TRIAD_SCALAR * c;
+            }
+        });
+        triad_gbs = triad_gbs.max(3.0 * BANDWIDTH_ARRAY_BYTES as f64 / seconds / 1e9);
+    }
+    black_box((&a, &b, &c));
+
+    println!("Memory Bandwidth: ~{:.0} GB/s (measured)", copy_gbs.max(triad_gbs));
+    println!(
+        "Copy: {:.1} GB/s, Triad: {:.1} GB/s, {} {} (approximate; depends on system load)",
+        copy_gbs, triad_gbs, threads, if threads == 1 { "thread" } else { "threads" },
+    );
+}
+
+/// Run a kernel over the three arrays, split into one chunk per thread.
+///
+/// # Arguments
+///
+/// * `threads` - Number of threads to use
+/// * `a`, `b`, `c` - Arrays of equal length
+/// * `kernel` - Function applied to each thread's chunk of `a`, `b`, and `c`
+///
+/// # Returns
+///
+/// Returns the wall-clock time in seconds until every thread finished.
+fn run_parallel<F>(threads: usize, a: &mut [f64], b: &mut [f64], c: &mut [f64], kernel: F) -> f64
+where
+    F: Fn(&mut [f64], &mut [f64], &mut [f64]) + Sync,
+{
+    let chunk = a.len().div_ceil(threads).max(1);
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for ((a, b), c) in a.chunks_mut(chunk).zip(b.chunks_mut(chunk)).zip(c.chunks_mut(chunk)) {
+            let kernel = &kernel;
+            scope.spawn(move || kernel(a, b, c));
+        }
+    });
+    start.elapsed().as_secs_f64()
+}
+
+/// Measure the average latency of a dependent load for a working set.
+///
+/// # Arguments
+///
+/// * `size_bytes` - Working set size in bytes
+///
+/// # Returns
+///
+/// Returns the average time per load in nanoseconds.
+fn measure_latency_ns(size_bytes: usize) -> f64 {
+    let chain = build_chain(size_bytes);
+
+    // Warm up: touch every node once so the timed pass doesn't include page faults
+    let mut index = chase(&chain, 0, size_bytes / LINE_BYTES);
+
+    let start = Instant::now();
+    index = chase(&chain, index, LOADS);
+    let elapsed = start.elapsed();
+    black_box(index);
+
+    elapsed.as_nanos() as f64 / LOADS as f64
+}
+
+/// Follow the chain for a number of loads.
+///
+/// # Arguments
+///
+/// * `chain` - Buffer where each node holds the index of the next node
+/// * `start` - Index to start from
+/// * `loads` - Number of loads to perform
+///
+/// # Returns
+///
+/// Returns the index reached, so the loads can't be optimized away.
+fn chase(chain: &[usize], start: usize, loads: usize) -> usize {
+    let mut index = start;
+    for _ in 0..loads {
+        index = black_box(chain[index]);
+    }
+    index
+}
+
+/// Build a buffer linking one node per cache line into a single random cycle.
+///
+/// Uses Sattolo's algorithm, which produces a permutation with exactly one cycle, so
+/// the chase visits every line before repeating.
+///
+/// # Arguments
+///
+/// * `size_bytes` - Working set size in bytes
+fn build_chain(size_bytes: usize) -> Vec<usize> {
+    let stride = LINE_BYTES / std::mem::size_of::<usize>();
+    let lines = (size_bytes / LINE_BYTES).max(2);
+
+    let mut order: Vec<usize> = (0..lines).collect();
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    for i in (1..lines).rev() {
+        let j = rng.next() as usize % i;
+        order.swap(i, j);
+    }
+
+    // order is a cyclic permutation: line i links to line order[i]
+    let mut chain = vec![0; lines * stride];
+    for (line, &next) in order.iter().enumerate() {
+        chain[line * stride] = next * stride;
+    }
+    chain
+}
+
+/// Format a working set size, using MB for sizes of 1 MB and above.
+///
+/// # Arguments
+///
+/// * `size_kb` - Size in KB
+fn format_size_kb(size_kb: usize) -> String {
+    if size_kb >= 1024 {
+        format!("{} MB", size_kb / 1024)
+    } else {
+        format!("{} KB", size_kb)
+    }
+}
+
+/// Minimal xorshift64 generator; the shuffle only needs to look random to the
+/// prefetchers, not be cryptographically strong.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -17,8 +17,67 @@
 //! }
 //! ```
 
+use crate::art::logos::COLOR_NAMES;
+use crate::cpu::FIELD_KEYS;
 use std::env;
 
+/// Output format selected with `--format`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Terminal output with ANSI colors (default)
+    #[default]
+    Text,
+    /// Self-contained HTML with inline CSS colors
+    Html,
+    /// JSON object with the detected fields
+    Json,
+    /// Shell variable assignments for `eval`
+    Shell,
+}
+
+/// Output width selected with `--width`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    /// Wrap to this many columns (`--width <N>`)
+    Columns(usize),
+    /// Measure the terminal itself, ignoring `COLUMNS` (`--width=auto`)
+    Auto,
+}
+
+/// Dimension the per-core and per-cache details are grouped by, selected with
+/// `--group-by`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Physical package
+    Socket,
+    /// Core type on hybrid chips (P-cores, E-cores)
+    CoreType,
+    /// NUMA node
+    Numa,
+}
+
+impl GroupBy {
+    /// Name of the dimension as given on the command line, e.g. "socket".
+    pub fn name(self) -> &'static str {
+        match self {
+            GroupBy::Socket => "socket",
+            GroupBy::CoreType => "coretype",
+            GroupBy::Numa => "numa",
+        }
+    }
+}
+
+/// Frequency shown on the frequency line, selected with `--freq-mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreqMode {
+    /// Nominal (base) frequency
+    Base,
+    /// Currently sampled frequency
+    Current,
+    /// Maximum (boost) frequency
+    Max,
+}
+
 /// Command line arguments structure
 ///
 /// Holds all supported CLI options for rcpufetch, including flags for help, version,
@@ -37,22 +96,118 @@ pub struct Args {
     pub version: bool,
     /// Generate shell completions (`--completions <SHELL>`)
     pub completions: Option<String>,
+    /// Number of spaces between the logo and info columns (`--gap <N>`)
+    pub gap: Option<usize>,
+    /// Disable ANSI colors (`--no-color`)
+    pub no_color: bool,
+    /// Print a legend naming the logo colors (`--color-legend`)
+    pub color_legend: bool,
+    /// Show additional fields (`-v`/`--verbose`)
+    pub verbose: bool,
+    /// Output format (`--format <FORMAT>`)
+    pub format: OutputFormat,
+    /// Group CPU flags by category (`--flags-grouped`)
+    pub flags_grouped: bool,
+    /// Show cache sizes on a single line (`--compact-cache`)
+    pub compact_cache: bool,
+    /// Show per-CPU current frequencies (`--freq`)
+    pub freq: bool,
+    /// Which frequency the frequency line shows (`--freq-mode <MODE>`)
+    pub freq_mode: Option<FreqMode>,
+    /// Output width (`--width <N>` or `--width=auto`)
+    pub width: Option<Width>,
+    /// Ignore the terminal width (`--fixed-width`)
+    pub fixed_width: bool,
+    /// Print a fingerprint of the CPU configuration (`--fingerprint`)
+    pub fingerprint: bool,
+    /// Fail when a critical field could not be detected (`--strict`)
+    pub strict: bool,
+    /// Colors replacing the logo's palette (`--logo-colors <LIST>`)
+    pub logo_colors: Option<Vec<&'static str>>,
+    /// Show per-cache details and latency estimates (`--cache-details`)
+    pub cache_details: bool,
+    /// Run the cache latency micro-benchmark (`--bench-cache`)
+    pub bench_cache: bool,
+    /// Run the memory bandwidth micro-benchmark (`--bench-bandwidth`)
+    pub bench_bandwidth: bool,
+    /// Show power details such as idle states (`--power`)
+    pub power: bool,
+    /// Print the logo to the right of the information (`--logo-right`)
+    pub logo_right: bool,
+    /// Show the CPU temperature (`--temp`)
+    pub temp: bool,
+    /// Pretty-print JSON output (`--pretty`)
+    pub pretty: bool,
+    /// List flags missing from the classification table (`--unknown-flags`)
+    pub unknown_flags: bool,
+    /// Wrap the output for pasting into forums and chats (`--share`)
+    pub share: bool,
+    /// Override the detected architecture, for testing (`--force-arch <ARCH>`)
+    pub force_arch: Option<String>,
+    /// Show memory-related CPU capabilities (`--memory`)
+    pub memory: bool,
+    /// Print only one field's value (`--get <FIELD>`)
+    pub get: Option<String>,
+    /// Annotate values with their source (`--show-source`)
+    pub show_source: bool,
+    /// Display a JSON snapshot instead of the running CPU (`--from-json <FILE>`)
+    pub from_json: Option<String>,
+    /// Show at most this many flags (`--max-flags <N>`)
+    pub max_flags: Option<usize>,
+    /// Adjust logo colors to the terminal background (`--auto-colors`)
+    pub auto_colors: bool,
+    /// Print the size of the rendered output to stderr (`--dimensions`)
+    pub dimensions: bool,
+    /// Blank lines added above the logo (`--logo-padding <N>`)
+    pub logo_padding: Option<usize>,
+    /// Display label overrides as `(field, label)` (`--label <FIELD=LABEL>`, repeatable)
+    pub labels: Vec<(&'static str, String)>,
+    /// Show control-flow protection features (`--security`)
+    pub security: bool,
+    /// Show a grid of the logical CPUs by core type (`--core-map`)
+    pub core_map: bool,
+    /// Measure the effective frequency with the APERF/MPERF MSRs (`--msr`)
+    pub msr: bool,
+    /// Dimension to group per-core details by (`--group-by <DIM>`)
+    pub group_by: Option<GroupBy>,
 }
 
 impl Args {
     /// Parse command line arguments manually.
     ///
-    /// Iterates over `std::env::args()` and matches each argument, setting the appropriate
-    /// fields in the struct. Handles both short and long options, as well as value-taking
-    /// options (e.g., `--logo <VENDOR>`). Returns descriptive error messages for unknown
-    /// or malformed arguments.
+    /// Iterates over the options in `RCPUFETCH_OPTS` followed by `std::env::args()` and
+    /// matches each argument, setting the appropriate fields in the struct. Because the
+    /// command line comes last, its values override the ones from the environment, and
+    /// switches set there can be turned off again with `--no-<SWITCH>`, `--color`, or
+    /// `--show-logo`. Handles both short and long options, as well as value-taking
+    /// options (e.g., `--logo <VENDOR>`). Returns descriptive error messages for
+    /// unknown or malformed arguments.
     ///
     /// # Returns
     ///
     /// * `Ok(Args)` if parsing succeeds
     /// * `Err(String)` if an unknown or malformed argument is encountered
     pub fn parse() -> Result<Self, String> {
-        let args: Vec<String> = env::args().collect();
+        let mut args: Vec<String> = env::args().take(1).collect();
+        if let Some(opts) = env::var_os("RCPUFETCH_OPTS") {
+            args.extend(split_options(&opts.to_string_lossy())?);
+        }
+        args.extend(env::args().skip(1));
+        Self::parse_from(args)
+    }
+
+    /// Parse an argument list, starting with the program name.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The program name followed by the `RCPUFETCH_OPTS` options and the
+    ///   command line arguments
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Args)` if parsing succeeds
+    /// * `Err(String)` if an unknown or malformed argument is encountered
+    fn parse_from(args: Vec<String>) -> Result<Self, String> {
         let mut parsed_args = Args::default();
         let mut i = 1; // Skip program name
 
@@ -70,6 +225,21 @@ impl Args {
                 "-n" | "--no-logo" => {
                     parsed_args.no_logo = true;
                 }
+                "--show-logo" => {
+                    parsed_args.no_logo = false;
+                }
+                "-v" | "--verbose" => {
+                    parsed_args.verbose = true;
+                }
+                "--no-color" => {
+                    parsed_args.no_color = true;
+                }
+                "--color" => {
+                    parsed_args.no_color = false;
+                }
+                "--color-legend" => {
+                    parsed_args.color_legend = true;
+                }
                 "-l" | "--logo" => {
                     i += 1;
                     if i >= args.len() {
@@ -84,6 +254,203 @@ impl Args {
                     }
                     parsed_args.logo = Some(value.to_string());
                 }
+                "--gap" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --gap requires a value".to_string());
+                    }
+                    parsed_args.gap = Some(parse_gap(&args[i])?);
+                }
+                arg if arg.starts_with("--gap=") => {
+                    let value = arg.strip_prefix("--gap=").unwrap();
+                    parsed_args.gap = Some(parse_gap(value)?);
+                }
+                "--format" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --format requires a value (text, html, json, shell)".to_string());
+                    }
+                    parsed_args.format = parse_format(&args[i])?;
+                }
+                arg if arg.starts_with("--format=") => {
+                    let value = arg.strip_prefix("--format=").unwrap();
+                    parsed_args.format = parse_format(value)?;
+                }
+                "--flags-grouped" => {
+                    parsed_args.flags_grouped = true;
+                }
+                "--compact-cache" => {
+                    parsed_args.compact_cache = true;
+                }
+                "--freq" => {
+                    parsed_args.freq = true;
+                }
+                "--freq-mode" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --freq-mode requires a value (base, current, max)".to_string());
+                    }
+                    parsed_args.freq_mode = Some(parse_freq_mode(&args[i])?);
+                }
+                arg if arg.starts_with("--freq-mode=") => {
+                    let value = arg.strip_prefix("--freq-mode=").unwrap();
+                    parsed_args.freq_mode = Some(parse_freq_mode(value)?);
+                }
+                "--width" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --width requires a value".to_string());
+                    }
+                    parsed_args.width = Some(parse_width(&args[i])?);
+                }
+                arg if arg.starts_with("--width=") => {
+                    let value = arg.strip_prefix("--width=").unwrap();
+                    parsed_args.width = Some(parse_width(value)?);
+                }
+                "--fixed-width" => {
+                    parsed_args.fixed_width = true;
+                }
+                "--fingerprint" => {
+                    parsed_args.fingerprint = true;
+                }
+                "--strict" => {
+                    parsed_args.strict = true;
+                }
+                "--logo-colors" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --logo-colors requires a comma-separated list of colors".to_string());
+                    }
+                    parsed_args.logo_colors = Some(parse_logo_colors(&args[i])?);
+                }
+                arg if arg.starts_with("--logo-colors=") => {
+                    let value = arg.strip_prefix("--logo-colors=").unwrap();
+                    parsed_args.logo_colors = Some(parse_logo_colors(value)?);
+                }
+                "--cache-details" => {
+                    parsed_args.cache_details = true;
+                }
+                "--bench-cache" => {
+                    parsed_args.bench_cache = true;
+                }
+                "--bench-bandwidth" => {
+                    parsed_args.bench_bandwidth = true;
+                }
+                "--power" => {
+                    parsed_args.power = true;
+                }
+                "--logo-right" => {
+                    parsed_args.logo_right = true;
+                }
+                "--temp" => {
+                    parsed_args.temp = true;
+                }
+                "--pretty" => {
+                    parsed_args.pretty = true;
+                }
+                "--unknown-flags" => {
+                    parsed_args.unknown_flags = true;
+                }
+                "--share" => {
+                    parsed_args.share = true;
+                }
+                "--force-arch" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --force-arch requires an architecture (e.g. x86_64, aarch64)".to_string());
+                    }
+                    parsed_args.force_arch = Some(args[i].clone());
+                }
+                arg if arg.starts_with("--force-arch=") => {
+                    parsed_args.force_arch = Some(arg.strip_prefix("--force-arch=").unwrap().to_string());
+                }
+                "--memory" => {
+                    parsed_args.memory = true;
+                }
+                "--get" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --get requires a field name (e.g. model, max_freq_ghz, l3_kb)".to_string());
+                    }
+                    parsed_args.get = Some(args[i].clone());
+                }
+                arg if arg.starts_with("--get=") => {
+                    parsed_args.get = Some(arg.strip_prefix("--get=").unwrap().to_string());
+                }
+                "--show-source" => {
+                    parsed_args.show_source = true;
+                }
+                "--from-json" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --from-json requires a file path".to_string());
+                    }
+                    parsed_args.from_json = Some(args[i].clone());
+                }
+                arg if arg.starts_with("--from-json=") => {
+                    parsed_args.from_json = Some(arg.strip_prefix("--from-json=").unwrap().to_string());
+                }
+                "--max-flags" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --max-flags requires a value".to_string());
+                    }
+                    parsed_args.max_flags = Some(parse_max_flags(&args[i])?);
+                }
+                arg if arg.starts_with("--max-flags=") => {
+                    let value = arg.strip_prefix("--max-flags=").unwrap();
+                    parsed_args.max_flags = Some(parse_max_flags(value)?);
+                }
+                "--auto-colors" => {
+                    parsed_args.auto_colors = true;
+                }
+                "--dimensions" => {
+                    parsed_args.dimensions = true;
+                }
+                "--json" => {
+                    parsed_args.format = OutputFormat::Json;
+                }
+                "--logo-padding" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --logo-padding requires a value".to_string());
+                    }
+                    parsed_args.logo_padding = Some(parse_logo_padding(&args[i])?);
+                }
+                arg if arg.starts_with("--logo-padding=") => {
+                    let value = arg.strip_prefix("--logo-padding=").unwrap();
+                    parsed_args.logo_padding = Some(parse_logo_padding(value)?);
+                }
+                "--label" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --label requires a value (e.g. cores=CPUs)".to_string());
+                    }
+                    add_label(&mut parsed_args.labels, &args[i])?;
+                }
+                arg if arg.starts_with("--label=") => {
+                    add_label(&mut parsed_args.labels, arg.strip_prefix("--label=").unwrap())?;
+                }
+                "--security" => {
+                    parsed_args.security = true;
+                }
+                "--core-map" => {
+                    parsed_args.core_map = true;
+                }
+                "--msr" => {
+                    parsed_args.msr = true;
+                }
+                "--group-by" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err("Error: --group-by requires a value (socket, coretype, numa)".to_string());
+                    }
+                    parsed_args.group_by = Some(parse_group_by(&args[i])?);
+                }
+                arg if arg.starts_with("--group-by=") => {
+                    let value = arg.strip_prefix("--group-by=").unwrap();
+                    parsed_args.group_by = Some(parse_group_by(value)?);
+                }
                 "--completions" => {
                     i += 1;
                     if i >= args.len() {
@@ -91,6 +458,10 @@ impl Args {
                     }
                     parsed_args.completions = Some(args[i].clone());
                 }
+                arg if arg.strip_prefix("--no-").is_some_and(|name| parsed_args.switch(name).is_some()) => {
+                    // Turn off a switch set earlier, e.g. in RCPUFETCH_OPTS
+                    *parsed_args.switch(&arg["--no-".len()..]).unwrap() = false;
+                }
                 arg => {
                     return Err(format!("Error: Unknown argument '{}'", arg));
                 }
@@ -100,6 +471,290 @@ impl Args {
 
         Ok(parsed_args)
     }
+
+    /// Look up a display switch that `--no-<SWITCH>` can turn off.
+    ///
+    /// Covers the switches that make sense as persistent defaults in
+    /// `RCPUFETCH_OPTS`; `--no-logo` and `--no-color` are undone with `--show-logo`
+    /// and `--color` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Long option name without the leading dashes, e.g. "verbose"
+    ///
+    /// # Returns
+    ///
+    /// Returns the field behind the switch, or `None` if `name` isn't one.
+    fn switch(&mut self, name: &str) -> Option<&mut bool> {
+        Some(match name {
+            "verbose" => &mut self.verbose,
+            "color-legend" => &mut self.color_legend,
+            "flags-grouped" => &mut self.flags_grouped,
+            "compact-cache" => &mut self.compact_cache,
+            "freq" => &mut self.freq,
+            "fixed-width" => &mut self.fixed_width,
+            "strict" => &mut self.strict,
+            "cache-details" => &mut self.cache_details,
+            "power" => &mut self.power,
+            "logo-right" => &mut self.logo_right,
+            "temp" => &mut self.temp,
+            "pretty" => &mut self.pretty,
+            "share" => &mut self.share,
+            "memory" => &mut self.memory,
+            "show-source" => &mut self.show_source,
+            "auto-colors" => &mut self.auto_colors,
+            "dimensions" => &mut self.dimensions,
+            "security" => &mut self.security,
+            "core-map" => &mut self.core_map,
+            "msr" => &mut self.msr,
+            _ => return None,
+        })
+    }
+}
+
+/// Parse the value of the `--gap` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value
+///
+/// # Returns
+///
+/// * `Ok(usize)` with the number of spaces
+/// * `Err(String)` if the value is not a non-negative integer
+fn parse_gap(value: &str) -> Result<usize, String> {
+    value.parse::<usize>()
+        .map_err(|_| format!("Error: --gap expects a non-negative number, got '{}'", value))
+}
+
+/// Parse the value of the `--logo-padding` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value
+///
+/// # Returns
+///
+/// * `Ok(usize)` with the number of blank lines
+/// * `Err(String)` if the value is not a non-negative integer
+fn parse_logo_padding(value: &str) -> Result<usize, String> {
+    value.parse::<usize>()
+        .map_err(|_| format!("Error: --logo-padding expects a non-negative number, got '{}'", value))
+}
+
+/// Parse a `--label` value and record the override.
+///
+/// A later override for the same field replaces an earlier one, so `--label` on the
+/// command line wins over one from `RCPUFETCH_OPTS`.
+///
+/// # Arguments
+///
+/// * `labels` - Overrides collected so far
+/// * `value` - The raw option value, e.g. "cores=CPUs"
+///
+/// # Returns
+///
+/// * `Ok(())` if the override was recorded
+/// * `Err(String)` if the value isn't `FIELD=LABEL`, the label is empty, or the
+///   field is unknown
+fn add_label(labels: &mut Vec<(&'static str, String)>, value: &str) -> Result<(), String> {
+    let Some((field, label)) = value.split_once('=') else {
+        return Err(format!("Error: --label expects FIELD=LABEL, got '{}'", value));
+    };
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(format!("Error: --label needs a non-empty label for '{}'", field));
+    }
+
+    let labelled = || FIELD_KEYS.iter().filter(|(_, labels, _)| !labels.is_empty());
+    let Some(&(field, _, _)) = labelled().find(|(key, _, _)| key.eq_ignore_ascii_case(field.trim())) else {
+        let valid: Vec<&str> = labelled().map(|(key, _, _)| *key).collect();
+        return Err(format!("Error: Unknown field '{}' for --label. Valid fields: {}", field, valid.join(", ")));
+    };
+    labels.retain(|(key, _)| *key != field);
+    labels.push((field, label.to_string()));
+    Ok(())
+}
+
+/// Parse the value of the `--width` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value, a number of columns or `auto`
+///
+/// # Returns
+///
+/// * `Ok(Width)` with the selected width
+/// * `Err(String)` if the value is neither `auto` nor a positive integer
+fn parse_width(value: &str) -> Result<Width, String> {
+    if value.eq_ignore_ascii_case("auto") {
+        return Ok(Width::Auto);
+    }
+    value.parse::<usize>()
+        .ok()
+        .filter(|&width| width > 0)
+        .map(Width::Columns)
+        .ok_or_else(|| format!("Error: --width expects a positive number or 'auto', got '{}'", value))
+}
+
+/// Parse the value of the `--max-flags` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value
+///
+/// # Returns
+///
+/// * `Ok(usize)` with the number of flags to show
+/// * `Err(String)` if the value is not a positive integer
+fn parse_max_flags(value: &str) -> Result<usize, String> {
+    value.parse::<usize>()
+        .ok()
+        .filter(|&count| count > 0)
+        .ok_or_else(|| format!("Error: --max-flags expects a positive number, got '{}'", value))
+}
+
+/// Split the `RCPUFETCH_OPTS` environment variable into arguments.
+///
+/// Arguments are separated by whitespace. Single quotes keep their contents literally;
+/// inside double quotes and outside of quotes a backslash escapes the next character,
+/// like in a POSIX shell.
+///
+/// # Arguments
+///
+/// * `value` - The value of `RCPUFETCH_OPTS`, e.g. `--width 80 --logo-colors 'red,blue'`
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` with the arguments in order
+/// * `Err(String)` if a quote is left unterminated
+fn split_options(value: &str) -> Result<Vec<String>, String> {
+    let mut options = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                options.extend(current.take());
+            }
+            '\\' => {
+                current.get_or_insert_default().extend(chars.next());
+            }
+            '\'' => {
+                let arg = current.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("Error: RCPUFETCH_OPTS has an unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => arg.extend(chars.next()),
+                        Some(c) => arg.push(c),
+                        None => return Err("Error: RCPUFETCH_OPTS has an unterminated double quote".to_string()),
+                    }
+                }
+            }
+            c => current.get_or_insert_default().push(c),
+        }
+    }
+
+    options.extend(current);
+    Ok(options)
+}
+
+/// Parse the value of the `--format` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value (case-insensitive)
+///
+/// # Returns
+///
+/// * `Ok(OutputFormat)` for a supported format
+/// * `Err(String)` if the format is not recognized
+fn parse_format(value: &str) -> Result<OutputFormat, String> {
+    match value.to_lowercase().as_str() {
+        "text" => Ok(OutputFormat::Text),
+        "html" => Ok(OutputFormat::Html),
+        "json" => Ok(OutputFormat::Json),
+        "shell" => Ok(OutputFormat::Shell),
+        _ => Err(format!("Error: Unknown format '{}'. Valid formats: text, html, json, shell", value)),
+    }
+}
+
+/// Parse the value of the `--freq-mode` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value (case-insensitive)
+///
+/// # Returns
+///
+/// * `Ok(FreqMode)` for a supported mode
+/// * `Err(String)` if the mode is not recognized
+fn parse_freq_mode(value: &str) -> Result<FreqMode, String> {
+    match value.to_lowercase().as_str() {
+        "base" => Ok(FreqMode::Base),
+        "current" => Ok(FreqMode::Current),
+        "max" => Ok(FreqMode::Max),
+        _ => Err(format!("Error: Unknown frequency mode '{}'. Valid modes: base, current, max", value)),
+    }
+}
+
+/// Parse the value of the `--group-by` option.
+///
+/// # Arguments
+///
+/// * `value` - The raw option value (case-insensitive)
+///
+/// # Returns
+///
+/// * `Ok(GroupBy)` for a supported dimension
+/// * `Err(String)` if the dimension is not recognized
+fn parse_group_by(value: &str) -> Result<GroupBy, String> {
+    match value.to_lowercase().as_str() {
+        "socket" => Ok(GroupBy::Socket),
+        "coretype" => Ok(GroupBy::CoreType),
+        "numa" => Ok(GroupBy::Numa),
+        _ => Err(format!("Error: Unknown grouping '{}'. Valid groupings: socket, coretype, numa", value)),
+    }
+}
+
+/// Parse the value of the `--logo-colors` option.
+///
+/// # Arguments
+///
+/// * `value` - Comma-separated color names (case-insensitive), e.g. "red,yellow,green"
+///
+/// # Returns
+///
+/// * `Ok(Vec<&str>)` with the ANSI color constant for each name, in order
+/// * `Err(String)` if the list is empty or contains an unknown color name
+fn parse_logo_colors(value: &str) -> Result<Vec<&'static str>, String> {
+    let names: Vec<&str> = value.split(',').map(str::trim).collect();
+    if names.iter().any(|name| name.is_empty()) {
+        return Err(format!("Error: Invalid color list '{}'. Expected comma-separated color names", value));
+    }
+
+    names.iter()
+        .map(|name| {
+            COLOR_NAMES.iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name))
+                .map(|(_, code)| *code)
+                .ok_or_else(|| {
+                    let valid: Vec<&str> = COLOR_NAMES.iter().map(|(known, _)| *known).collect();
+                    format!("Error: Unknown color '{}'. Valid colors: {}", name, valid.join(", "))
+                })
+        })
+        .collect()
 }
 
 /// Print help information to stdout.
@@ -118,13 +773,66 @@ pub fn print_help() {
     println!("        --license                Display license information");
     println!("        --completions <SHELL>    Generate shell completions (fish, bash, zsh)");
     println!("    -n, --no-logo                Disable logo display");
+    println!("        --show-logo              Show the logo again after --no-logo (e.g. from RCPUFETCH_OPTS)");
+    println!("    -v, --verbose                Show additional CPU details");
     println!("    -l, --logo <VENDOR>          Override logo display with specific vendor");
     println!("                                 Valid vendors: nvidia, powerpc, arm, amd, intel, apple");
+    println!("        --gap <N>                Number of spaces between logo and info (default: 3)");
+    println!("        --logo-padding <N>       Add N blank lines above the logo");
+    println!("        --no-color               Disable colored output");
+    println!("        --color                  Enable colored output again after --no-color");
+    println!("        --color-legend           Print a legend naming the logo colors");
+    println!("        --format <FORMAT>        Output format: text, html, json, shell (default: text)");
+    println!("        --json                   Shorthand for --format json");
+    println!("        --flags-grouped          Group CPU flags by category (SIMD, Crypto, ...)");
+    println!("        --max-flags <N>          Show only the first N flags, followed by how many were left out");
+    println!("        --compact-cache          Show cache sizes on a single line");
+    println!("        --freq                   Show the current frequency of each CPU");
+    println!("        --freq-mode <MODE>       Frequency to show: base, current, max (default: max)");
+    println!("        --width <N|auto>         Wrap output to N columns; 'auto' caps the default width to");
+    println!("                                 the terminal measured directly, ignoring COLUMNS. The width is");
+    println!("                                 resolved in this order: --width N, --width auto (terminal),");
+    println!("                                 COLUMNS, the terminal, the default (100, or 80 without a logo)");
+    println!("        --fixed-width            Ignore the terminal width and always use --width or the default");
+    println!("        --fingerprint            Print a stable hash of the CPU configuration and exit");
+    println!("        --strict                 Exit with an error if the model, vendor, or core count is unknown");
+    println!("        --logo-colors <LIST>     Recolor the logo with comma-separated colors (e.g. red,yellow,green)");
+    println!("        --auto-colors            Adjust logo colors to the terminal's light or dark background (Linux, macOS)");
+    println!("        --cache-details          Show per-cache details and typical cache latencies");
+    println!("        --bench-cache            Measure cache latencies with a micro-benchmark and exit");
+    println!("        --bench-bandwidth        Measure memory bandwidth with a micro-benchmark and exit");
+    println!("        --power                  Show power details such as supported CPU idle states (Linux)");
+    println!("        --logo-right             Print the information first and the logo on the right");
+    println!("        --temp                   Show the CPU temperature and Tjmax (Linux)");
+    println!("        --pretty                 Indent --format json output over multiple lines");
+    println!("        --unknown-flags          List CPU flags missing from rcpufetch's classification table and exit");
+    println!("        --share                  Print color-free output in a fenced block with a header, for pasting into forums");
+    println!("        --force-arch <ARCH>      [testing] Pretend the architecture is ARCH (e.g. aarch64) to test rendering");
+    println!("        --memory                 Show memory-related CPU capabilities such as huge page sizes (Linux)");
+    println!("        --get <FIELD>            Print only the value of FIELD (e.g. model, max_freq_ghz, l3_kb) and exit");
+    println!("        --show-source            Annotate each value with where it was read from (Linux)");
+    println!("        --from-json <FILE>       Load a snapshot saved with --format json and display it instead of this CPU");
+    println!("        --dimensions             Also print the output's width and height in terminal cells to stderr as JSON");
+    println!("        --label <FIELD=LABEL>    Rename the label of FIELD (e.g. cores=CPUs); repeatable");
+    println!("        --security               Show control-flow protection features such as CET, SMEP, SMAP (Linux)");
+    println!("        --core-map               Show a grid of the logical CPUs colored by core type on hybrid CPUs (Linux)");
+    println!("        --msr                    Show the effective frequency measured with the APERF/MPERF MSRs (Linux, root)");
+    println!("        --group-by <DIM>         Group cores, caches, and --freq by socket, coretype, or numa (Linux)");
+    println!();
+    println!("ENVIRONMENT:");
+    println!("    RCPUFETCH_OPTS               Default options, read before the command line (e.g. \"--width 80 --no-color\")");
+    println!("                                 Turn switches set there off with --no-<SWITCH> (e.g. --no-verbose)");
     println!();
     println!("EXAMPLES:");
     println!("    rcpufetch                    Display CPU info with auto-detected logo");
     println!("    rcpufetch --no-logo          Display CPU info without logo");
     println!("    rcpufetch --logo intel       Display CPU info with Intel logo");
+    println!("    rcpufetch --gap 6            Put six spaces between the logo and info");
+    println!("    rcpufetch --format html      Write CPU info as a self-contained HTML snippet");
+    println!("    rcpufetch --format json --pretty  Write CPU info as indented JSON");
+    println!("    eval \"$(rcpufetch --format shell)\"  Set RCPUFETCH_* shell variables");
+    println!("    rcpufetch --get max_freq_ghz Print only the maximum frequency, for scripts");
+    println!("    rcpufetch --from-json cpu.json Display a snapshot saved with --format json");
     println!("    rcpufetch --license          Show license information");
 }
 
@@ -177,7 +885,47 @@ fn print_fish_completions() {
     println!("complete -c rcpufetch -s V -l version -d 'Print version information'");
     println!("complete -c rcpufetch -l license -d 'Display license information'");
     println!("complete -c rcpufetch -s n -l no-logo -d 'Disable logo display'");
+    println!("complete -c rcpufetch -l show-logo -d 'Show the logo again after --no-logo'");
+    println!("complete -c rcpufetch -s v -l verbose -d 'Show additional CPU details'");
     println!("complete -c rcpufetch -s l -l logo -x -a 'nvidia powerpc arm amd intel apple' -d 'Override logo display with specific vendor'");
+    println!("complete -c rcpufetch -l gap -x -d 'Number of spaces between logo and info'");
+    println!("complete -c rcpufetch -l no-color -d 'Disable colored output'");
+    println!("complete -c rcpufetch -l color -d 'Enable colored output again after --no-color'");
+    println!("complete -c rcpufetch -l color-legend -d 'Print a legend naming the logo colors'");
+    println!("complete -c rcpufetch -l format -x -a 'text html json shell' -d 'Output format'");
+    println!("complete -c rcpufetch -l flags-grouped -d 'Group CPU flags by category'");
+    println!("complete -c rcpufetch -l compact-cache -d 'Show cache sizes on a single line'");
+    println!("complete -c rcpufetch -l freq -d 'Show the current frequency of each CPU'");
+    println!("complete -c rcpufetch -l freq-mode -x -a 'base current max' -d 'Frequency to show'");
+    println!("complete -c rcpufetch -l width -x -a 'auto' -d 'Wrap output to N columns'");
+    println!("complete -c rcpufetch -l fixed-width -d 'Ignore the terminal width'");
+    println!("complete -c rcpufetch -l fingerprint -d 'Print a stable hash of the CPU configuration'");
+    println!("complete -c rcpufetch -l strict -d 'Exit with an error if critical fields are unknown'");
+    println!("complete -c rcpufetch -l logo-colors -x -d 'Recolor the logo with comma-separated colors'");
+    println!("complete -c rcpufetch -l cache-details -d 'Show per-cache details and typical latencies'");
+    println!("complete -c rcpufetch -l bench-cache -d 'Measure cache latencies with a micro-benchmark'");
+    println!("complete -c rcpufetch -l bench-bandwidth -d 'Measure memory bandwidth with a micro-benchmark'");
+    println!("complete -c rcpufetch -l power -d 'Show power details such as CPU idle states'");
+    println!("complete -c rcpufetch -l logo-right -d 'Print the logo on the right'");
+    println!("complete -c rcpufetch -l temp -d 'Show the CPU temperature and Tjmax'");
+    println!("complete -c rcpufetch -l pretty -d 'Indent JSON output'");
+    println!("complete -c rcpufetch -l unknown-flags -d 'List CPU flags rcpufetch does not recognize'");
+    println!("complete -c rcpufetch -l share -d 'Print output ready to paste into forums and chats'");
+    println!("complete -c rcpufetch -l force-arch -x -d '[testing] Override the detected architecture'");
+    println!("complete -c rcpufetch -l memory -d 'Show memory-related CPU capabilities such as huge page sizes'");
+    println!("complete -c rcpufetch -l get -x -a 'model vendor architecture physical_cores logical_cores max_freq_ghz l1d_kb l1i_kb l1_kb l2_kb l3_kb flags' -d 'Print only the value of one field'");
+    println!("complete -c rcpufetch -l show-source -d 'Annotate each value with where it was read from'");
+    println!("complete -c rcpufetch -l from-json -r -F -d 'Display a snapshot saved with --format json'");
+    println!("complete -c rcpufetch -l max-flags -x -d 'Show only the first N flags'");
+    println!("complete -c rcpufetch -l auto-colors -d 'Adjust logo colors to the terminal background'");
+    println!("complete -c rcpufetch -l dimensions -d 'Print the output size to stderr'");
+    println!("complete -c rcpufetch -l json -d 'Shorthand for --format json'");
+    println!("complete -c rcpufetch -l logo-padding -x -d 'Add N blank lines above the logo'");
+    println!("complete -c rcpufetch -l label -x -d 'Rename the label of a field (FIELD=LABEL)'");
+    println!("complete -c rcpufetch -l security -d 'Show control-flow protection features such as CET'");
+    println!("complete -c rcpufetch -l core-map -d 'Show a grid of the logical CPUs colored by core type'");
+    println!("complete -c rcpufetch -l msr -d 'Show the effective frequency from the APERF/MPERF MSRs'");
+    println!("complete -c rcpufetch -l group-by -x -a 'socket coretype numa' -d 'Group per-core details'");
     println!("complete -c rcpufetch -l completions -x -a 'fish bash zsh' -d 'Generate shell completions'");
 }
 
@@ -189,13 +937,33 @@ fn print_bash_completions() {
     println!("    COMPREPLY=()");
     println!("    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"");
     println!("    prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"");
-    println!("    opts=\"-h --help -V --version --license -n --no-logo -l --logo --completions\"");
+    println!("    opts=\"-h --help -V --version --license -n --no-logo --show-logo -v --verbose -l --logo --gap --no-color --color --color-legend --format --flags-grouped --compact-cache --freq --freq-mode --width --fixed-width --fingerprint --strict --logo-colors --cache-details --bench-cache --bench-bandwidth --power --logo-right --temp --pretty --unknown-flags --share --force-arch --memory --get --show-source --from-json --max-flags --auto-colors --dimensions --json --logo-padding --label --security --core-map --msr --group-by --completions\"");
     println!();
     println!("    case \"${{prev}}\" in");
     println!("        --logo|-l)");
     println!("            COMPREPLY=($(compgen -W \"nvidia powerpc arm amd intel apple\" -- \"${{cur}}\"))");
     println!("            return 0");
     println!("            ;;");
+    println!("        --format)");
+    println!("            COMPREPLY=($(compgen -W \"text html json shell\" -- \"${{cur}}\"))");
+    println!("            return 0");
+    println!("            ;;");
+    println!("        --freq-mode)");
+    println!("            COMPREPLY=($(compgen -W \"base current max\" -- \"${{cur}}\"))");
+    println!("            return 0");
+    println!("            ;;");
+    println!("        --get)");
+    println!("            COMPREPLY=($(compgen -W \"model vendor architecture physical_cores logical_cores max_freq_ghz l1d_kb l1i_kb l1_kb l2_kb l3_kb flags\" -- \"${{cur}}\"))");
+    println!("            return 0");
+    println!("            ;;");
+    println!("        --from-json)");
+    println!("            COMPREPLY=($(compgen -f -- \"${{cur}}\"))");
+    println!("            return 0");
+    println!("            ;;");
+    println!("        --group-by)");
+    println!("            COMPREPLY=($(compgen -W \"socket coretype numa\" -- \"${{cur}}\"))");
+    println!("            return 0");
+    println!("            ;;");
     println!("        --completions)");
     println!("            COMPREPLY=($(compgen -W \"fish bash zsh\" -- \"${{cur}}\"))");
     println!("            return 0");
@@ -218,9 +986,85 @@ fn print_zsh_completions() {
     println!("        '(-V --version){{-V,--version}}[Print version information]' \\");
     println!("        '--license[Display license information]' \\");
     println!("        '(-n --no-logo){{-n,--no-logo}}[Disable logo display]' \\");
+    println!("        '--show-logo[Show the logo again after --no-logo]' \\");
+    println!("        '(-v --verbose){{-v,--verbose}}[Show additional CPU details]' \\");
     println!("        '(-l --logo){{-l,--logo}}[Override logo display with specific vendor]:vendor:(nvidia powerpc arm amd intel apple)' \\");
+    println!("        '--gap[Number of spaces between logo and info]:spaces:' \\");
+    println!("        '--no-color[Disable colored output]' \\");
+    println!("        '--color[Enable colored output again after --no-color]' \\");
+    println!("        '--color-legend[Print a legend naming the logo colors]' \\");
+    println!("        '--format[Output format]:format:(text html json shell)' \\");
+    println!("        '--flags-grouped[Group CPU flags by category]' \\");
+    println!("        '--compact-cache[Show cache sizes on a single line]' \\");
+    println!("        '--freq[Show the current frequency of each CPU]' \\");
+    println!("        '--freq-mode[Frequency to show]:mode:(base current max)' \\");
+    println!("        '--width[Wrap output to N columns]:columns:(auto)' \\");
+    println!("        '--fixed-width[Ignore the terminal width]' \\");
+    println!("        '--fingerprint[Print a stable hash of the CPU configuration]' \\");
+    println!("        '--strict[Exit with an error if critical fields are unknown]' \\");
+    println!("        '--logo-colors[Recolor the logo with comma-separated colors]:colors:' \\");
+    println!("        '--cache-details[Show per-cache details and typical latencies]' \\");
+    println!("        '--bench-cache[Measure cache latencies with a micro-benchmark]' \\");
+    println!("        '--bench-bandwidth[Measure memory bandwidth with a micro-benchmark]' \\");
+    println!("        '--power[Show power details such as CPU idle states]' \\");
+    println!("        '--logo-right[Print the logo on the right]' \\");
+    println!("        '--temp[Show the CPU temperature and Tjmax]' \\");
+    println!("        '--pretty[Indent JSON output]' \\");
+    println!("        '--unknown-flags[List CPU flags rcpufetch does not recognize]' \\");
+    println!("        '--share[Print output ready to paste into forums and chats]' \\");
+    println!("        '--force-arch[Override the detected architecture (testing)]:architecture:' \\");
+    println!("        '--memory[Show memory-related CPU capabilities such as huge page sizes]' \\");
+    println!("        '--get[Print only the value of one field]:field:(model vendor architecture physical_cores logical_cores max_freq_ghz l1d_kb l1i_kb l1_kb l2_kb l3_kb flags)' \\");
+    println!("        '--show-source[Annotate each value with where it was read from]' \\");
+    println!("        '--from-json[Display a snapshot saved with --format json]:file:_files' \\");
+    println!("        '--max-flags[Show only the first N flags]:count:' \\");
+    println!("        '--auto-colors[Adjust logo colors to the terminal background]' \\");
+    println!("        '--dimensions[Print the output size to stderr]' \\");
+    println!("        '--json[Shorthand for --format json]' \\");
+    println!("        '--logo-padding[Add N blank lines above the logo]:lines:' \\");
+    println!("        '*--label[Rename the label of a field]:field=label:' \\");
+    println!("        '--security[Show control-flow protection features such as CET]' \\");
+    println!("        '--core-map[Show a grid of the logical CPUs colored by core type]' \\");
+    println!("        '--msr[Show the effective frequency from the APERF/MPERF MSRs]' \\");
+    println!("        '--group-by[Group per-core details]:dimension:(socket coretype numa)' \\");
     println!("        '--completions[Generate shell completions]:shell:(fish bash zsh)'");
     println!("}}");
     println!();
     println!("_rcpufetch \"$@\"");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_options_keeps_quoted_spaces() {
+        assert_eq!(
+            split_options("--width 80  --logo-colors 'red, yellow' --label \"cores=Kerne und Threads\"").unwrap(),
+            ["--width", "80", "--logo-colors", "red, yellow", "--label", "cores=Kerne und Threads"],
+        );
+    }
+
+    #[test]
+    fn split_options_handles_escapes() {
+        assert_eq!(split_options(r#"--label model=It\'s "a \"b\"" 'c\d'"#).unwrap(), ["--label", "model=It's", "a \"b\"", "c\\d"]);
+        assert_eq!(split_options(r"a\ b").unwrap(), ["a b"]);
+    }
+
+    #[test]
+    fn split_options_rejects_unterminated_quotes() {
+        assert!(split_options("--logo-colors 'red").is_err());
+        assert!(split_options("--label \"cores=CPUs").is_err());
+    }
+
+    #[test]
+    fn command_line_turns_off_switches_from_environment() {
+        let mut args = vec!["rcpufetch".to_string()];
+        args.extend(split_options("--verbose --no-logo --no-color").unwrap());
+        args.extend(["--no-verbose", "--show-logo", "--color"].map(String::from));
+        let parsed = Args::parse_from(args).unwrap();
+        assert!(!parsed.verbose);
+        assert!(!parsed.no_logo);
+        assert!(!parsed.no_color);
+    }
+}
@@ -0,0 +1,439 @@
+//! Platform-independent CPU types shared by every OS module.
+//!
+//! The OS modules each gather information in their own way; this module defines the
+//! common shapes they report it in, so callers can use the same code on every
+//! platform.
+
+use crate::display::DisplayOptions;
+use crate::json::JsonValue;
+use std::fmt;
+
+/// Kind of data a cache holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheType {
+    /// Data cache (e.g. L1d)
+    Data,
+    /// Instruction cache (e.g. L1i)
+    Instruction,
+    /// Unified data and instruction cache (e.g. L2, L3)
+    Unified,
+}
+
+/// One level of the cache hierarchy, as seen from a single core.
+///
+/// Attributes the platform doesn't report are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    /// Cache level (1, 2, 3, ...)
+    pub level: u32,
+    /// Data, instruction, or unified cache
+    pub cache_type: CacheType,
+    /// Size of one cache instance in KB
+    pub size_kb: u32,
+    /// Ways of associativity
+    pub associativity: Option<u32>,
+    /// Cache line size in bytes
+    pub line_size: Option<u32>,
+    /// Number of logical CPUs sharing one instance of this cache
+    pub shared_cpus: Option<u32>,
+    /// Number of instances of this cache in the system
+    pub instances: Option<u32>,
+    /// Whether the cache is inclusive of the lower cache levels
+    pub inclusive: Option<bool>,
+}
+
+impl Cache {
+    /// Short name of the cache, e.g. "L1d", "L1i", or "L2".
+    pub fn name(&self) -> String {
+        match self.cache_type {
+            CacheType::Data => format!("L{}d", self.level),
+            CacheType::Instruction => format!("L{}i", self.level),
+            CacheType::Unified => format!("L{}", self.level),
+        }
+    }
+
+    /// Total size of all instances in KB, counting one instance when the number
+    /// isn't known.
+    pub fn total_kb(&self) -> u64 {
+        self.size_kb as u64 * self.instances.unwrap_or(1) as u64
+    }
+}
+
+/// Core type of a logical CPU on hybrid designs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CoreKind {
+    /// Performance core (Intel P-core, ARM big core)
+    Performance,
+    /// Efficiency core (Intel E-core, ARM little core)
+    Efficiency,
+    /// Low-power efficiency core (Intel LP E-core, the smallest ARM cores)
+    LowPower,
+}
+
+impl CoreKind {
+    /// Letter used for the core type when colors are off, e.g. 'P'.
+    pub fn letter(self) -> char {
+        match self {
+            CoreKind::Performance => 'P',
+            CoreKind::Efficiency => 'E',
+            CoreKind::LowPower => 'L',
+        }
+    }
+
+    /// Display name of the core type, e.g. "P-core".
+    pub fn name(self) -> &'static str {
+        match self {
+            CoreKind::Performance => "P-core",
+            CoreKind::Efficiency => "E-core",
+            CoreKind::LowPower => "LP E-core",
+        }
+    }
+}
+
+/// Field that must be detected for `--strict` validation to pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriticalField {
+    Model,
+    Vendor,
+    CoreCount,
+}
+
+impl CriticalField {
+    /// All critical fields, in reporting order.
+    pub const ALL: [CriticalField; 3] = [
+        CriticalField::Model,
+        CriticalField::Vendor,
+        CriticalField::CoreCount,
+    ];
+
+    /// Human-readable field name.
+    pub fn label(self) -> &'static str {
+        match self {
+            CriticalField::Model => "model",
+            CriticalField::Vendor => "vendor",
+            CriticalField::CoreCount => "core count",
+        }
+    }
+}
+
+/// Error produced by `CpuInfo::validate` when critical fields are unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Critical fields that could not be detected
+    pub missing: Vec<CriticalField>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<&str> = self.missing.iter().map(|field| field.label()).collect();
+        write!(f, "could not detect CPU {}", fields.join(", "))
+    }
+}
+
+/// Keys of the detected fields, shared by `--get`, `--format shell`, `--fingerprint`,
+/// and `--label`.
+///
+/// Each entry is `(key, labels, canonical)`. `labels` are the display labels the key
+/// renames with `--label`; some fields are labelled differently by the OS modules
+/// (e.g. "L2 Size" on Linux, "L2 Cache Size" on macOS and Windows), so one key can
+/// cover several. `canonical` marks the keys of `CpuInfo::canonical_fields`, which
+/// `--get` and `--format shell` print; the others only have a label.
+pub const FIELD_KEYS: &[(&str, &[&str], bool)] = &[
+    ("model", &["Name"], true),
+    ("vendor", &["Vendor"], true),
+    ("architecture", &["Architecture"], true),
+    ("byte_order", &["Byte Order"], false),
+    ("cores", &["Cores"], false),
+    ("physical_cores", &[], true),
+    ("logical_cores", &[], true),
+    ("threads_per_core", &["Threads per core"], false),
+    ("core_types", &["Core Types"], false),
+    ("max_freq_ghz", &["Max Frequency"], true),
+    ("base_freq", &["Base Frequency"], false),
+    ("current_freq", &["Current Frequency"], false),
+    ("energy_perf_bias", &["Energy/Performance Bias"], false),
+    ("effective_freq", &["Effective Frequency"], false),
+    ("base_clock", &["Base Clock"], false),
+    ("xsave", &["XSAVE"], false),
+    ("cache", &["Cache"], false),
+    ("l1_kb", &["L1 Size", "L1 Cache Size"], true),
+    ("l1d_kb", &["L1d Size"], true),
+    ("l1i_kb", &["L1i Size"], true),
+    ("l2_kb", &["L2 Size", "L2 Cache Size"], true),
+    ("l3_kb", &["L3 Size", "L3 Cache Size"], true),
+    ("clflush", &["CLFLUSH Size"], false),
+    ("numa", &["NUMA"], false),
+    ("microarchitecture", &["Microarchitecture"], false),
+    ("resource_control", &["Resource Control"], false),
+    ("hypervisor", &["Hypervisor"], false),
+    ("environment", &["Environment"], false),
+    ("cpu_quota", &["CPU Quota"], false),
+    ("temperature", &["Temperature"], false),
+    ("memory", &["Memory"], false),
+    ("control_flow", &["Control-flow protection"], false),
+    ("smt_risk", &["SMT Risk"], false),
+    ("flags", &["Flags"], true),
+];
+
+/// Interface implemented by each OS-specific CPU information type.
+pub trait CpuInfo {
+    /// Get the detailed cache hierarchy, ordered by level.
+    ///
+    /// # Returns
+    ///
+    /// Returns one entry per cache level and type, or an empty list if the platform
+    /// doesn't expose cache details.
+    fn caches(&self) -> Vec<Cache>;
+
+    /// Get the CPU model name (e.g. "AMD Ryzen 5 9600X 6-Core Processor").
+    fn model(&self) -> &str;
+
+    /// Replace the detected architecture (`--force-arch`, a testing aid).
+    ///
+    /// Everything that depends on the architecture, such as the ARM logo fallback,
+    /// then behaves as it would on that architecture.
+    ///
+    /// # Arguments
+    ///
+    /// * `architecture` - Architecture string, e.g. "aarch64"
+    fn force_architecture(&mut self, architecture: &str);
+
+    /// Get the raw CPU flag string in the platform's format.
+    ///
+    /// # Returns
+    ///
+    /// Returns the flags, or an empty string if the platform doesn't report any.
+    fn flags(&self) -> &str;
+
+    /// Get the fields that identify this CPU configuration for `fingerprint`.
+    ///
+    /// The names are canonical keys from `FIELD_KEYS`.
+    ///
+    /// Only static properties belong here (vendor, model, architecture, core counts,
+    /// cache sizes, flags); transient values such as the current frequency must be
+    /// left out so identical hardware always produces the same fingerprint.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(name, value)` pairs in a fixed order.
+    fn fingerprint_fields(&self) -> Vec<(&'static str, String)>;
+
+    /// Get the maximum CPU frequency in GHz, if known.
+    ///
+    /// On hybrid chips this is the frequency of the fastest cores.
+    fn max_frequency_ghz(&self) -> Option<f32>;
+
+    /// Get the detected fields as a JSON object (`--format json`).
+    ///
+    /// Unknown values are `null`; the serial number is never included.
+    fn to_json(&self) -> JsonValue;
+
+    /// Print the information next to the vendor logo, or the logo of
+    /// `logo_override` if given (text output).
+    fn display_info_with_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions);
+
+    /// Print the information without a logo (`--no-logo`).
+    fn display_info_no_logo(&self, opts: &DisplayOptions);
+
+    /// Print the information as a self-contained HTML snippet (`--format html`).
+    fn display_info_html(&self, logo_override: Option<&str>, opts: &DisplayOptions);
+
+    /// Check that every critical field was detected (`--strict`).
+    ///
+    /// Runs after detection over the same fields as `fingerprint_fields`: the model
+    /// and vendor must not be empty or "Unknown", and the core count must be nonzero.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if all critical fields are known, or a `ValidationError`
+    /// listing the missing ones.
+    fn validate(&self) -> Result<(), ValidationError> {
+        let fields = self.fingerprint_fields();
+        let value = |name: &str| {
+            fields.iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap_or("")
+        };
+
+        let missing: Vec<CriticalField> = CriticalField::ALL.iter()
+            .copied()
+            .filter(|field| match field {
+                CriticalField::Model => is_unknown(value("model")),
+                CriticalField::Vendor => is_unknown(value("vendor")),
+                CriticalField::CoreCount => matches!(value("logical_cores"), "" | "0"),
+            })
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { missing })
+        }
+    }
+
+    /// Get the canonical fields shared by `--get` and `--format shell`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the fields of `fingerprint_fields` followed by "max_freq_ghz", as
+    /// `(key, value)` pairs; values that couldn't be detected may be empty or
+    /// "unknown".
+    fn canonical_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = self.fingerprint_fields();
+        fields.push(("max_freq_ghz", self.max_frequency_ghz().map_or_else(String::new, |ghz| ghz.to_string())));
+        fields
+    }
+
+    /// Format the canonical fields as shell variable assignments (`--format shell`).
+    ///
+    /// Each field becomes one `RCPUFETCH_<KEY>=value` line, e.g.
+    /// `RCPUFETCH_MODEL='Intel(R) Core(TM) i7-8700K CPU @ 3.70GHz'`, so the output
+    /// can be passed to `eval`. Values are single-quoted unless they consist only of
+    /// characters that are safe unquoted; unknown values are empty strings.
+    fn shell_assignments(&self) -> String {
+        self.canonical_fields()
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_unknown(value) { "" } else { value };
+                format!("RCPUFETCH_{}={}\n", key.to_uppercase(), shell_quote(value))
+            })
+            .collect()
+    }
+
+    /// Look up a single field by its canonical key (`--get <FIELD>`).
+    ///
+    /// The keys are the canonical ones from `FIELD_KEYS` (e.g. "model", "l3_kb",
+    /// "max_freq_ghz"). Keys the platform doesn't report, such as "l1_kb" on Linux,
+    /// are unknown rather than invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Canonical field key
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(value))`, `Ok(None)` when the field is unknown on this
+    /// system, or `Err` listing the valid keys when `key` isn't one of them.
+    fn field(&self, key: &str) -> Result<Option<String>, String> {
+        if !FIELD_KEYS.iter().any(|&(name, _, canonical)| canonical && name == key) {
+            let names: Vec<&str> = FIELD_KEYS.iter()
+                .filter(|(_, _, canonical)| *canonical)
+                .map(|(name, _, _)| *name)
+                .collect();
+            return Err(format!("unknown field '{}'. Valid fields: {}", key, names.join(", ")));
+        }
+        Ok(self.canonical_fields()
+            .into_iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value)
+            .filter(|value| !is_unknown(value)))
+    }
+
+    /// Compute a stable fingerprint of the CPU configuration (`--fingerprint`).
+    ///
+    /// The fields from `fingerprint_fields` are canonicalized as `name=value` lines
+    /// and hashed with 64-bit FNV-1a.
+    ///
+    /// # Returns
+    ///
+    /// Returns the hash as 16 lowercase hex digits.
+    fn fingerprint(&self) -> String {
+        let canonical: String = self.fingerprint_fields()
+            .iter()
+            .map(|(name, value)| format!("{}={}\n", name, value))
+            .collect();
+        format!("{:016x}", fnv1a_64(canonical.as_bytes()))
+    }
+}
+
+/// Whether a detected string value is missing.
+///
+/// # Arguments
+///
+/// * `value` - Detected value
+fn is_unknown(value: &str) -> bool {
+    value.is_empty() || value.eq_ignore_ascii_case("unknown")
+}
+
+/// Quote a value for POSIX shells.
+///
+/// Values made only of letters, digits, `.`, `_`, and `-` are returned as is;
+/// anything else (including the empty string) is wrapped in single quotes, with
+/// embedded single quotes written as `'\''`.
+///
+/// # Arguments
+///
+/// * `value` - Value to quote
+fn shell_quote(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-');
+    if !value.is_empty() && value.chars().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Hash bytes with 64-bit FNV-1a.
+///
+/// # Arguments
+///
+/// * `bytes` - Data to hash
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// Canonicalize a flag list for fingerprinting: normalized, sorted, and deduplicated.
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux or macOS format
+pub fn canonical_flags(flags: &str) -> String {
+    let mut normalized: Vec<String> = crate::flags::split_flags(flags)
+        .into_iter()
+        .map(crate::flags::normalize)
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized.join(" ")
+}
+
+/// Format an optional cache size for fingerprinting.
+///
+/// # Arguments
+///
+/// * `size_kb` - Cache size in KB, if known
+pub fn canonical_size(size_kb: Option<impl Into<u64>>) -> String {
+    size_kb.map_or_else(|| "unknown".to_string(), |kb| kb.into().to_string())
+}
+
+/// Get the byte order rcpufetch was built for.
+///
+/// # Returns
+///
+/// Returns "Little Endian" or "Big Endian".
+pub fn byte_order() -> &'static str {
+    if cfg!(target_endian = "little") {
+        "Little Endian"
+    } else {
+        "Big Endian"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_keys_are_unique_and_usable() {
+        let mut keys: Vec<&str> = FIELD_KEYS.iter().map(|(key, _, _)| *key).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), FIELD_KEYS.len());
+        // Every key can be printed with --get or renamed with --label
+        assert!(FIELD_KEYS.iter().all(|(_, labels, canonical)| *canonical || !labels.is_empty()));
+    }
+}
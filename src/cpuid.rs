@@ -0,0 +1,423 @@
+//! Thin CPUID helpers for rcpufetch.
+//!
+//! Wraps the `__cpuid_count` intrinsic so OS modules can query CPUID leaves without
+//! sprinkling architecture `cfg` attributes around. On non-x86 targets every query
+//! returns `None`.
+
+use crate::cpu::CacheType;
+
+/// Raw register values returned by a CPUID query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuidRegs {
+    pub eax: u32,
+    pub ebx: u32,
+    pub ecx: u32,
+    pub edx: u32,
+}
+
+/// Execute CPUID for the given leaf and subleaf.
+///
+/// Leaves above the maximum supported basic, extended, or hypervisor leaf return
+/// `None`, so callers never see the garbage values some CPUs return for unsupported
+/// leaves.
+///
+/// # Arguments
+///
+/// * `leaf` - CPUID leaf (EAX input)
+/// * `subleaf` - CPUID subleaf (ECX input)
+///
+/// # Returns
+///
+/// Returns `Some(CpuidRegs)` on x86/x86_64 when the leaf is supported, `None` otherwise.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[allow(unused_unsafe)] // `__cpuid_count` is only a safe fn on newer toolchains
+pub fn cpuid(leaf: u32, subleaf: u32) -> Option<CpuidRegs> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid_count;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid_count;
+
+    // The hypervisor range (0x40000000-0x4FFFFFFF) only exists when CPUID leaf 1
+    // advertises a hypervisor (ECX bit 31); bare metal returns garbage there.
+    let base = if (0x4000_0000..0x5000_0000).contains(&leaf) {
+        // SAFETY: CPUID is available on every x86_64 CPU and every x86 CPU Rust supports.
+        let ecx = unsafe { __cpuid_count(1, 0) }.ecx;
+        if ecx & (1 << 31) == 0 {
+            return None;
+        }
+        0x4000_0000
+    } else {
+        leaf & 0x8000_0000
+    };
+
+    // SAFETY: see above.
+    let max_leaf = unsafe { __cpuid_count(base, 0) }.eax;
+    if leaf > max_leaf {
+        return None;
+    }
+
+    // SAFETY: see above; the leaf has been checked against the supported range.
+    let result = unsafe { __cpuid_count(leaf, subleaf) };
+    Some(CpuidRegs {
+        eax: result.eax,
+        ebx: result.ebx,
+        ecx: result.ecx,
+        edx: result.edx,
+    })
+}
+
+/// Execute CPUID for the given leaf and subleaf (unsupported on this architecture).
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn cpuid(_leaf: u32, _subleaf: u32) -> Option<CpuidRegs> {
+    None
+}
+
+/// Check whether the CPU advertises AVX-512 Foundation (leaf 7, EBX bit 16).
+///
+/// This reports what the silicon advertises and ignores whether the OS has enabled
+/// the AVX-512 register state.
+pub fn has_avx512f() -> bool {
+    cpuid(7, 0).is_some_and(|regs| regs.ebx & (1 << 16) != 0)
+}
+
+/// Read the 12-character vendor string from CPUID leaf 0.
+///
+/// The string is stored in EBX, EDX, ECX (in that order), e.g. "GenuineIntel" or
+/// "AuthenticAMD".
+///
+/// # Returns
+///
+/// Returns `Some(String)` with the vendor string, or `None` off x86 or if empty.
+pub fn vendor() -> Option<String> {
+    let regs = cpuid(0, 0)?;
+    let mut bytes = Vec::with_capacity(12);
+    for reg in [regs.ebx, regs.edx, regs.ecx] {
+        bytes.extend_from_slice(&reg.to_le_bytes());
+    }
+    let vendor = String::from_utf8_lossy(&bytes).trim_matches(char::from(0)).trim().to_string();
+    if vendor.is_empty() { None } else { Some(vendor) }
+}
+
+/// Read the processor brand string from CPUID leaves 0x80000002-0x80000004.
+///
+/// # Returns
+///
+/// Returns `Some(String)` with the brand string (e.g. "AMD Ryzen 5 9600X 6-Core Processor"),
+/// or `None` off x86 or when the extended leaves are not supported.
+pub fn brand_string() -> Option<String> {
+    let mut bytes = Vec::with_capacity(48);
+    for leaf in 0x8000_0002..=0x8000_0004 {
+        let regs = cpuid(leaf, 0)?;
+        for reg in [regs.eax, regs.ebx, regs.ecx, regs.edx] {
+            bytes.extend_from_slice(&reg.to_le_bytes());
+        }
+    }
+    let brand = String::from_utf8_lossy(&bytes).trim_matches(char::from(0)).trim().to_string();
+    if brand.is_empty() { None } else { Some(brand) }
+}
+
+/// Read the display family, model, and stepping from CPUID leaf 1.
+///
+/// Applies the extended family/model adjustments described in the Intel and AMD
+/// manuals, so the values match the "cpu family" and "model" fields in /proc/cpuinfo.
+///
+/// # Returns
+///
+/// Returns `Some((family, model, stepping))`, or `None` off x86.
+pub fn signature() -> Option<(u32, u32, u32)> {
+    let eax = cpuid(1, 0)?.eax;
+    let stepping = eax & 0xF;
+    let base_model = (eax >> 4) & 0xF;
+    let base_family = (eax >> 8) & 0xF;
+    let ext_model = (eax >> 16) & 0xF;
+    let ext_family = (eax >> 20) & 0xFF;
+
+    let family = if base_family == 0xF { base_family + ext_family } else { base_family };
+    let model = if base_family == 0x6 || base_family == 0xF {
+        (ext_model << 4) | base_model
+    } else {
+        base_model
+    };
+    Some((family, model, stepping))
+}
+
+/// Read the base (nominal) frequency from CPUID leaf 0x16.
+///
+/// Leaf 0x16 is only implemented by Intel CPUs since Skylake; AMD CPUs and most
+/// hypervisors don't report it.
+///
+/// # Returns
+///
+/// Returns `Some(u32)` with the base frequency in MHz, or `None` if unavailable.
+pub fn base_frequency_mhz() -> Option<u32> {
+    let mhz = cpuid(0x16, 0)?.eax & 0xFFFF;
+    if mhz == 0 { None } else { Some(mhz) }
+}
+
+/// Read the bus (reference) frequency from CPUID leaf 0x16.
+///
+/// ECX bits 15:0 hold the frequency in MHz, i.e. the BCLK the core clock is a
+/// multiple of. Like the base frequency, only Intel CPUs since Skylake report it.
+///
+/// # Returns
+///
+/// Returns `Some(u32)` with the bus frequency in MHz, or `None` if unavailable.
+pub fn bus_frequency_mhz() -> Option<u32> {
+    let mhz = cpuid(0x16, 0)?.ecx & 0xFFFF;
+    if mhz == 0 { None } else { Some(mhz) }
+}
+
+/// Read the linear (virtual) address width from CPUID leaf 0x80000008.
+///
+/// EAX bits 15:8 hold the number of linear address bits the CPU implements, e.g. 48,
+/// or 57 on CPUs with 5-level paging support.
+///
+/// # Returns
+///
+/// Returns `Some(u32)` with the width in bits, or `None` if the leaf is unsupported.
+pub fn linear_address_bits() -> Option<u32> {
+    let bits = (cpuid(0x8000_0008, 0)?.eax >> 8) & 0xFF;
+    if bits == 0 { None } else { Some(bits) }
+}
+
+/// Cache geometry reported by the deterministic cache parameters leaf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheParameters {
+    /// Cache level (1, 2, 3, ...)
+    pub level: u32,
+    /// Data, instruction, or unified cache
+    pub cache_type: CacheType,
+    /// Ways of associativity
+    pub ways: u32,
+    /// Cache line size in bytes
+    pub line_size: u32,
+    /// Whether the cache is inclusive of the lower cache levels
+    pub inclusive: bool,
+    /// Size of one instance in KB
+    pub size_kb: u32,
+    /// Maximum number of logical CPUs sharing one instance
+    pub shared_cpus: u32,
+}
+
+/// Read the cache geometry from the deterministic cache parameters leaf.
+///
+/// Intel reports cache parameters in leaf 4 and AMD in leaf 0x8000001D; both use the
+/// same layout: EBX holds the ways (bits 31:22), partitions (bits 21:12), and line
+/// size (bits 11:0), ECX the number of sets, each minus one, EAX bits 25:14 the
+/// logical CPUs sharing the cache minus one, and EDX bit 1 is set when a cache is
+/// inclusive of the lower levels.
+///
+/// # Returns
+///
+/// Returns the parameters of each cache the CPU reports; empty off x86 or when the
+/// leaf is unsupported.
+pub fn cache_parameters() -> Vec<CacheParameters> {
+    let leaf = match vendor().as_deref() {
+        Some("AuthenticAMD") | Some("HygonGenuine") => 0x8000_001D,
+        _ => 4,
+    };
+
+    let mut caches = Vec::new();
+    for subleaf in 0..16 {
+        let Some(regs) = cpuid(leaf, subleaf) else {
+            break;
+        };
+        let cache_type = match regs.eax & 0x1F {
+            1 => CacheType::Data,
+            2 => CacheType::Instruction,
+            3 => CacheType::Unified,
+            _ => break, // 0 marks the end of the list
+        };
+        let ways = (regs.ebx >> 22) + 1;
+        let partitions = ((regs.ebx >> 12) & 0x3FF) + 1;
+        let line_size = (regs.ebx & 0xFFF) + 1;
+        let sets = regs.ecx as u64 + 1;
+        caches.push(CacheParameters {
+            level: (regs.eax >> 5) & 0x7,
+            cache_type,
+            ways,
+            line_size,
+            inclusive: regs.edx & (1 << 1) != 0,
+            size_kb: (ways as u64 * partitions as u64 * line_size as u64 * sets / 1024) as u32,
+            shared_cpus: ((regs.eax >> 14) & 0xFFF) + 1,
+        });
+    }
+    caches
+}
+
+/// Read the XCR0 register, which lists the register states the OS has enabled.
+///
+/// XCR0 is only readable (with XGETBV) when the OS has enabled XSAVE, which CPUID
+/// leaf 1 reports as OSXSAVE (ECX bit 27).
+///
+/// # Returns
+///
+/// Returns `Some(u64)` with the XCR0 bits, or `None` off x86 or when OSXSAVE is clear.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn xcr0() -> Option<u64> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::_xgetbv;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::_xgetbv;
+
+    #[target_feature(enable = "xsave")]
+    fn read_xcr0() -> u64 {
+        // SAFETY: only called after OSXSAVE has been checked.
+        unsafe { _xgetbv(0) }
+    }
+
+    if cpuid(1, 0)?.ecx & (1 << 27) == 0 {
+        return None;
+    }
+    // SAFETY: OSXSAVE is set, so the CPU supports XSAVE and XGETBV is enabled.
+    Some(unsafe { read_xcr0() })
+}
+
+/// Read the XCR0 register (unsupported on this architecture).
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn xcr0() -> Option<u64> {
+    None
+}
+
+/// XSAVE state components and area sizes reported by CPUID leaf 0x0D.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XsaveInfo {
+    /// XCR0 bits of the user state components the CPU supports
+    pub supported: u64,
+    /// Size in bytes of the XSAVE area holding every supported component
+    pub max_size: u32,
+    /// Size in bytes of the XSAVE area for the components enabled in XCR0
+    pub enabled_size: u32,
+}
+
+/// User state components, as `(first XCR0 bit, last XCR0 bit, name)`.
+const XSAVE_COMPONENTS: &[(u32, u32, &str)] = &[
+    (0, 0, "x87"),
+    (1, 1, "SSE"),
+    (2, 2, "AVX"),
+    (3, 4, "MPX"),
+    (5, 7, "AVX-512"),
+    (9, 9, "PKRU"),
+    (17, 18, "AMX"),
+    (19, 19, "APX"),
+];
+
+impl XsaveInfo {
+    /// Names of the supported state components, e.g. ["x87", "SSE", "AVX"].
+    ///
+    /// Components spanning several XCR0 bits (AVX-512's opmask and ZMM states, AMX's
+    /// tile config and tile data) are listed once when any of their bits is set.
+    pub fn components(&self) -> Vec<&'static str> {
+        XSAVE_COMPONENTS.iter()
+            .filter(|&&(first, last, _)| (first..=last).any(|bit| self.supported & (1 << bit) != 0))
+            .map(|&(_, _, name)| name)
+            .collect()
+    }
+}
+
+/// Read the XSAVE state components and area sizes from CPUID leaf 0x0D.
+///
+/// Subleaf 0 lists the supported user state components in EDX:EAX, the area size
+/// for all of them in ECX, and the size for the components currently enabled in
+/// XCR0 in EBX. The enabled size is what the kernel saves on every context switch.
+///
+/// # Returns
+///
+/// Returns `Some(XsaveInfo)`, or `None` off x86 or when the CPU lacks XSAVE (leaf 1,
+/// ECX bit 26).
+pub fn xsave_info() -> Option<XsaveInfo> {
+    if cpuid(1, 0)?.ecx & (1 << 26) == 0 {
+        return None;
+    }
+    let regs = cpuid(0x0D, 0)?;
+    if regs.ecx == 0 {
+        return None;
+    }
+    Some(XsaveInfo {
+        supported: (regs.edx as u64) << 32 | regs.eax as u64,
+        max_size: regs.ecx,
+        enabled_size: regs.ebx,
+    })
+}
+
+/// Read the hypervisor vendor signature from CPUID leaf 0x40000000.
+///
+/// The 12-character signature is stored in EBX, ECX, EDX (in that order), e.g.
+/// "KVMKVMKVM" or "Microsoft Hv". Only present when running under a hypervisor.
+///
+/// # Returns
+///
+/// Returns `Some(String)` with the signature, or `None` on bare metal or off x86.
+pub fn hypervisor_vendor() -> Option<String> {
+    let regs = cpuid(0x4000_0000, 0)?;
+    let mut bytes = Vec::with_capacity(12);
+    for reg in [regs.ebx, regs.ecx, regs.edx] {
+        bytes.extend_from_slice(&reg.to_le_bytes());
+    }
+    let vendor = String::from_utf8_lossy(&bytes).trim_matches(char::from(0)).trim().to_string();
+    if vendor.is_empty() { None } else { Some(vendor) }
+}
+
+/// CPUID feature bits and their Linux `/proc/cpuinfo` names, as
+/// `(leaf, subleaf, register, bit, name)`. Registers are indexed EAX=0, EBX=1,
+/// ECX=2, EDX=3.
+const FEATURE_FLAGS: &[(u32, u32, usize, u32, &str)] = &[
+    (1, 0, 3, 0, "fpu"), (1, 0, 3, 4, "tsc"), (1, 0, 3, 5, "msr"), (1, 0, 3, 6, "pae"),
+    (1, 0, 3, 8, "cx8"), (1, 0, 3, 9, "apic"), (1, 0, 3, 11, "sep"), (1, 0, 3, 12, "mtrr"),
+    (1, 0, 3, 13, "pge"), (1, 0, 3, 15, "cmov"), (1, 0, 3, 16, "pat"), (1, 0, 3, 19, "clflush"),
+    (1, 0, 3, 23, "mmx"), (1, 0, 3, 24, "fxsr"), (1, 0, 3, 25, "sse"), (1, 0, 3, 26, "sse2"),
+    (1, 0, 3, 28, "ht"),
+    (1, 0, 2, 0, "pni"), (1, 0, 2, 1, "pclmulqdq"), (1, 0, 2, 3, "monitor"), (1, 0, 2, 5, "vmx"),
+    (1, 0, 2, 9, "ssse3"), (1, 0, 2, 12, "fma"), (1, 0, 2, 13, "cx16"), (1, 0, 2, 19, "sse4_1"),
+    (1, 0, 2, 20, "sse4_2"), (1, 0, 2, 22, "movbe"), (1, 0, 2, 23, "popcnt"), (1, 0, 2, 25, "aes"),
+    (1, 0, 2, 26, "xsave"), (1, 0, 2, 28, "avx"), (1, 0, 2, 29, "f16c"), (1, 0, 2, 30, "rdrand"),
+    (1, 0, 2, 31, "hypervisor"),
+    (0x8000_0001, 0, 3, 11, "syscall"), (0x8000_0001, 0, 3, 20, "nx"), (0x8000_0001, 0, 3, 26, "pdpe1gb"),
+    (0x8000_0001, 0, 3, 27, "rdtscp"), (0x8000_0001, 0, 3, 29, "lm"),
+    (0x8000_0001, 0, 2, 0, "lahf_lm"), (0x8000_0001, 0, 2, 2, "svm"), (0x8000_0001, 0, 2, 5, "abm"),
+    (0x8000_0001, 0, 2, 6, "sse4a"), (0x8000_0001, 0, 2, 8, "3dnowprefetch"), (0x8000_0001, 0, 2, 16, "fma4"),
+    (7, 0, 1, 0, "fsgsbase"), (7, 0, 1, 3, "bmi1"), (7, 0, 1, 4, "hle"), (7, 0, 1, 5, "avx2"),
+    (7, 0, 1, 7, "smep"), (7, 0, 1, 8, "bmi2"), (7, 0, 1, 9, "erms"), (7, 0, 1, 10, "invpcid"),
+    (7, 0, 1, 11, "rtm"), (7, 0, 1, 16, "avx512f"), (7, 0, 1, 17, "avx512dq"), (7, 0, 1, 18, "rdseed"),
+    (7, 0, 1, 19, "adx"), (7, 0, 1, 20, "smap"), (7, 0, 1, 21, "avx512ifma"), (7, 0, 1, 23, "clflushopt"),
+    (7, 0, 1, 24, "clwb"), (7, 0, 1, 28, "avx512cd"), (7, 0, 1, 29, "sha_ni"), (7, 0, 1, 30, "avx512bw"),
+    (7, 0, 1, 31, "avx512vl"),
+    (7, 0, 2, 1, "avx512vbmi"), (7, 0, 2, 2, "umip"), (7, 0, 2, 4, "pku"), (7, 0, 2, 6, "avx512_vbmi2"),
+    (7, 0, 2, 8, "gfni"), (7, 0, 2, 9, "vaes"), (7, 0, 2, 10, "vpclmulqdq"), (7, 0, 2, 11, "avx512_vnni"),
+    (7, 0, 2, 12, "avx512_bitalg"), (7, 0, 2, 14, "avx512_vpopcntdq"), (7, 0, 2, 22, "rdpid"),
+    (7, 0, 2, 25, "cldemote"), (7, 0, 2, 27, "movdiri"), (7, 0, 2, 28, "movdir64b"),
+    (7, 0, 3, 4, "fsrm"), (7, 0, 3, 8, "avx512_vp2intersect"), (7, 0, 3, 14, "serialize"),
+    (7, 0, 3, 16, "tsxldtrk"), (7, 0, 3, 22, "amx_bf16"), (7, 0, 3, 23, "avx512_fp16"),
+    (7, 0, 3, 24, "amx_tile"), (7, 0, 3, 25, "amx_int8"),
+    (7, 1, 0, 4, "avx_vnni"), (7, 1, 0, 5, "avx512_bf16"),
+];
+
+/// Build a `/proc/cpuinfo`-style flag list from the CPUID feature leaves.
+///
+/// Used where the OS doesn't expose the flags itself, so the output (and the flag
+/// groups of `--group-flags`) looks the same as on Linux. Only the commonly used
+/// features are covered; Linux-synthesized flags such as `constant_tsc` are not.
+///
+/// # Returns
+///
+/// Returns the flags joined with spaces, or an empty string off x86.
+pub fn feature_flags() -> String {
+    let mut leaves: Vec<((u32, u32), Option<CpuidRegs>)> = Vec::new();
+    let mut flags = Vec::new();
+    for &(leaf, subleaf, register, bit, name) in FEATURE_FLAGS {
+        let regs = match leaves.iter().find(|(key, _)| *key == (leaf, subleaf)) {
+            Some((_, regs)) => *regs,
+            None => {
+                let regs = cpuid(leaf, subleaf);
+                leaves.push(((leaf, subleaf), regs));
+                regs
+            }
+        };
+        let Some(regs) = regs else { continue };
+        let value = [regs.eax, regs.ebx, regs.ecx, regs.edx][register];
+        if value & (1 << bit) != 0 {
+            flags.push(name);
+        }
+    }
+    flags.join(" ")
+}
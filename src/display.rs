@@ -0,0 +1,1069 @@
+//! Shared display helpers for rcpufetch.
+//!
+//! Every OS module builds its own logo and information lines, but the side-by-side
+//! layout math is the same everywhere. This module holds the layout options parsed
+//! from the command line and the print loop used by all `display_info_with_logo`
+//! implementations.
+
+use crate::art::logos::{color_name, css_color, Background, COLOR_RESET, C_FG_CYAN, C_FG_GREEN, C_FG_RED};
+use crate::cla::{Args, FreqMode, GroupBy, Width};
+use crate::cpu::{Cache, CoreKind, FIELD_KEYS};
+use crate::flags;
+use crate::json::{self, JsonValue};
+
+/// Default number of spaces between the logo and info columns.
+pub const DEFAULT_GAP: usize = 3;
+
+/// Default output width when a logo is shown.
+pub const DEFAULT_WIDTH: usize = 100;
+
+/// Default output width without a logo.
+pub const DEFAULT_NO_LOGO_WIDTH: usize = 80;
+
+/// Indentation used for wrapped flag lines (aligns with "Flags: ").
+pub const FLAG_INDENT: &str = "       ";
+
+/// Layout options shared by every OS display implementation.
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    /// Number of spaces between the logo and info columns (`--gap <n>`)
+    pub gap: usize,
+    /// Strip all ANSI colors from the output (`--no-color`)
+    pub no_color: bool,
+    /// Print a legend naming each logo color (`--color-legend`)
+    pub color_legend: bool,
+    /// Show additional, less commonly needed fields (`-v`/`--verbose`)
+    pub verbose: bool,
+    /// Leave out the logo (`-n`/`--no-logo`)
+    pub no_logo: bool,
+    /// Show CPU flags grouped by category (`--flags-grouped`)
+    pub flags_grouped: bool,
+    /// Show all cache levels on one line (`--compact-cache`)
+    pub compact_cache: bool,
+    /// Show the current frequency of each CPU (`--freq`)
+    pub freq: bool,
+    /// Which frequency the frequency line shows (`--freq-mode`); `None` uses the
+    /// platform default
+    pub freq_mode: Option<FreqMode>,
+    /// Output width (`--width <n>` or `--width=auto`); `None` uses the layout's default
+    pub width: Option<Width>,
+    /// Ignore the terminal width (`--fixed-width`)
+    pub fixed_width: bool,
+    /// Colors replacing the logo's palette (`--logo-colors`); `None` keeps the default
+    pub logo_colors: Option<Vec<&'static str>>,
+    /// Show per-cache details and latency estimates (`--cache-details`)
+    pub cache_details: bool,
+    /// Show power details such as idle states (`--power`)
+    pub power: bool,
+    /// Print the logo to the right of the information (`--logo-right`)
+    pub logo_right: bool,
+    /// Show the CPU temperature (`--temp`)
+    pub temp: bool,
+    /// Show memory-related CPU capabilities (`--memory`)
+    pub memory: bool,
+    /// Annotate values with their source (`--show-source`)
+    pub show_source: bool,
+    /// Show at most this many flags (`--max-flags <n>`); `None` shows all of them
+    pub max_flags: Option<usize>,
+    /// Terminal background the logo colors are adjusted to (`--auto-colors`); `None`
+    /// keeps the default colors
+    pub background: Option<Background>,
+    /// Print the size of the rendered block to stderr (`--dimensions`)
+    pub dimensions: bool,
+    /// Blank lines added above the logo (`--logo-padding <n>`)
+    pub logo_padding: usize,
+    /// Label overrides as `(field, label)`, with fields from `cpu::FIELD_KEYS` (`--label`)
+    pub labels: Vec<(&'static str, String)>,
+    /// Show control-flow protection features (`--security`)
+    pub security: bool,
+    /// Show a grid of the logical CPUs by core type (`--core-map`)
+    pub core_map: bool,
+    /// Show the effective frequency measured with the APERF/MPERF MSRs (`--msr`)
+    pub msr: bool,
+    /// Dimension to group per-core details by (`--group-by`); `None` keeps them flat
+    pub group_by: Option<GroupBy>,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            gap: DEFAULT_GAP,
+            no_color: false,
+            color_legend: false,
+            verbose: false,
+            no_logo: false,
+            flags_grouped: false,
+            compact_cache: false,
+            freq: false,
+            freq_mode: None,
+            width: None,
+            fixed_width: false,
+            logo_colors: None,
+            cache_details: false,
+            power: false,
+            logo_right: false,
+            temp: false,
+            memory: false,
+            show_source: false,
+            max_flags: None,
+            background: None,
+            dimensions: false,
+            logo_padding: 0,
+            labels: Vec::new(),
+            security: false,
+            core_map: false,
+            msr: false,
+            group_by: None,
+        }
+    }
+}
+
+impl DisplayOptions {
+    /// Build display options from the parsed command line arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Parsed command line arguments
+    ///
+    /// # Returns
+    ///
+    /// Returns a `DisplayOptions` with defaults filled in for unset options.
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            gap: args.gap.unwrap_or(DEFAULT_GAP),
+            // Shared snippets are pasted where ANSI colors would show up as garbage
+            no_color: args.no_color || args.share,
+            color_legend: args.color_legend,
+            verbose: args.verbose,
+            no_logo: args.no_logo,
+            flags_grouped: args.flags_grouped,
+            compact_cache: args.compact_cache,
+            freq: args.freq,
+            freq_mode: args.freq_mode,
+            width: args.width,
+            fixed_width: args.fixed_width,
+            logo_colors: args.logo_colors.clone(),
+            cache_details: args.cache_details,
+            power: args.power,
+            logo_right: args.logo_right,
+            temp: args.temp,
+            memory: args.memory,
+            show_source: args.show_source,
+            max_flags: args.max_flags,
+            // Queried separately, only when the text output will be shown
+            background: None,
+            dimensions: args.dimensions,
+            logo_padding: args.logo_padding.unwrap_or(0),
+            labels: args.labels.clone(),
+            security: args.security,
+            core_map: args.core_map,
+            msr: args.msr,
+            group_by: args.group_by,
+        }
+    }
+
+    /// Resolve the label to show in place of a default label.
+    ///
+    /// # Arguments
+    ///
+    /// * `default` - Label as built by the OS module, e.g. "Cores"
+    ///
+    /// # Returns
+    ///
+    /// Returns the `--label` override for the field covering `default`, or `default`
+    /// itself when it wasn't renamed.
+    pub fn label<'a>(&'a self, default: &'a str) -> &'a str {
+        self.labels.iter()
+            .find(|(field, _)| {
+                FIELD_KEYS.iter().any(|(key, labels, _)| key == field && labels.contains(&default))
+            })
+            .map_or(default, |(_, label)| label.as_str())
+    }
+
+    /// Whether ANSI colors should be emitted.
+    pub fn use_color(&self) -> bool {
+        !self.no_color
+    }
+
+    /// Whether per-cache details should be shown (`--cache-details`, or `--verbose`).
+    pub fn show_cache_details(&self) -> bool {
+        self.cache_details || self.verbose
+    }
+
+    /// Whether memory-related capabilities should be shown (`--memory`, or `--verbose`).
+    pub fn show_memory(&self) -> bool {
+        self.memory || self.verbose
+    }
+
+    /// Whether security features should be shown (`--security`, or `--verbose`).
+    pub fn show_security(&self) -> bool {
+        self.security || self.verbose
+    }
+
+    /// Resolve the width output should be wrapped to.
+    ///
+    /// Uses `--width <N>` as given. Otherwise `default` is capped to the terminal
+    /// width so output fits narrow terminals, unless `--fixed-width` is set, in which
+    /// case the output is identical in every environment. `--width=auto` measures the
+    /// terminal directly even when `COLUMNS` is set, so the sources are tried in this
+    /// order: `--width <N>`, `--width=auto`, `COLUMNS`, the terminal, and finally
+    /// `default` when stdout isn't a terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `default` - Width used when `--width <N>` is not given (e.g. `DEFAULT_WIDTH`)
+    pub fn resolve_width(&self, default: usize) -> usize {
+        let columns = std::env::var("COLUMNS").ok().and_then(|c| c.parse::<usize>().ok());
+        pick_width(self.width, self.fixed_width, default, columns, terminal_width_ioctl())
+    }
+
+    /// Get the separator placed between the logo and info columns.
+    pub fn separator(&self) -> String {
+        " ".repeat(self.gap)
+    }
+}
+
+/// Pick the output width from `--width`, `COLUMNS` and the terminal size.
+///
+/// Separated from `DisplayOptions::resolve_width` so the precedence can be tested
+/// without touching the environment or the terminal.
+///
+/// # Arguments
+///
+/// * `width` - The `--width` option, if given
+/// * `fixed_width` - Whether `--fixed-width` was given
+/// * `default` - Width used when `--width <N>` is not given
+/// * `columns` - The value of `COLUMNS`, if set and numeric
+/// * `terminal` - The width reported by the terminal, if stdout is one
+fn pick_width(width: Option<Width>, fixed_width: bool, default: usize, columns: Option<usize>, terminal: Option<usize>) -> usize {
+    let limit = match width {
+        Some(Width::Columns(width)) => return width,
+        Some(Width::Auto) => terminal,
+        None => columns.filter(|&columns| columns > 0).or(terminal),
+    };
+    if fixed_width {
+        return default;
+    }
+    limit.map_or(default, |limit| default.min(limit))
+}
+
+/// Query the terminal size of stdout with the `TIOCGWINSZ` ioctl.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn terminal_width_ioctl() -> Option<usize> {
+    use std::os::raw::{c_int, c_ulong, c_ushort};
+
+    #[repr(C)]
+    struct WinSize {
+        ws_row: c_ushort,
+        ws_col: c_ushort,
+        ws_xpixel: c_ushort,
+        ws_ypixel: c_ushort,
+    }
+
+    unsafe extern "C" {
+        fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+    }
+
+    #[cfg(target_os = "linux")]
+    const TIOCGWINSZ: c_ulong = 0x5413;
+    #[cfg(target_os = "macos")]
+    const TIOCGWINSZ: c_ulong = 0x4008_7468;
+
+    let mut size = WinSize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+    // SAFETY: TIOCGWINSZ writes a `struct winsize` to the pointer; `size` matches its layout.
+    let result = unsafe { ioctl(1, TIOCGWINSZ, &mut size as *mut WinSize) };
+    if result == 0 && size.ws_col > 0 {
+        Some(size.ws_col as usize)
+    } else {
+        None
+    }
+}
+
+/// Query the terminal size of stdout (not supported on this platform).
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn terminal_width_ioctl() -> Option<usize> {
+    None
+}
+
+/// Detect whether the terminal background is light or dark (`--auto-colors`).
+///
+/// Sends the OSC 11 query (`ESC ] 11 ; ? BEL`) to the controlling terminal, which
+/// answers with its background color such as `ESC ] 11 ; rgb:ffff/ffff/ffff BEL`.
+/// The terminal is switched to raw mode with `stty` while reading, so the answer is
+/// neither echoed nor line-buffered, and reads give up after 100 ms of silence.
+///
+/// # Returns
+///
+/// Returns `Some(Background)`, or `None` when stdout is not a terminal, the terminal
+/// doesn't answer in time, or the answer can't be parsed.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn terminal_background() -> Option<Background> {
+    use std::fs::OpenOptions;
+    use std::io::{IsTerminal, Read, Write};
+    use std::process::{Command, Stdio};
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let mut tty = OpenOptions::new().read(true).write(true).open("/dev/tty").ok()?;
+    // stty changes the settings of the terminal on its stdin
+    let stty_input = tty.try_clone().ok()?;
+    let stty = |args: &[&str]| -> Option<String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(Stdio::from(stty_input.try_clone().ok()?))
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let saved = stty(&["-g"])?;
+    stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+
+    let mut response = Vec::new();
+    if tty.write_all(b"\x1b]11;?\x07").and_then(|_| tty.flush()).is_ok() {
+        let mut buffer = [0u8; 64];
+        // Stop at the BEL or ST terminator, on timeout (a zero-length read), or
+        // when the terminal keeps sending something that isn't an answer
+        while response.len() < 256 {
+            match tty.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => response.extend_from_slice(&buffer[..n]),
+            }
+            if response.ends_with(b"\x07") || response.ends_with(b"\x1b\\") {
+                break;
+            }
+        }
+    }
+    stty(&[&saved]);
+
+    parse_background_response(&String::from_utf8_lossy(&response))
+}
+
+/// Detect the terminal background (not supported on this platform).
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn terminal_background() -> Option<Background> {
+    None
+}
+
+/// Parse a terminal's answer to the OSC 11 background color query.
+///
+/// The color is given as `rgb:R/G/B` with one to four hex digits per channel. The
+/// background counts as light when its relative luminance is above one half.
+///
+/// # Arguments
+///
+/// * `response` - Raw answer, e.g. "\x1b]11;rgb:ffff/ffff/ffff\x07"
+///
+/// # Returns
+///
+/// Returns `Some(Background)`, or `None` if the answer has no parsable color.
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+fn parse_background_response(response: &str) -> Option<Background> {
+    let color = response.split("rgb:").nth(1)?
+        .trim_end_matches(['\x07', '\x1b', '\\']);
+    let channels: Vec<f32> = color.split('/')
+        .map(|hex| {
+            let value = u32::from_str_radix(hex, 16).ok()?;
+            let max = (1u32 << (4 * hex.len() as u32).min(16)) - 1;
+            (1..=4).contains(&hex.len()).then(|| value as f32 / max as f32)
+        })
+        .collect::<Option<_>>()?;
+    let [r, g, b] = channels[..] else {
+        return None;
+    };
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance > 0.5 { Background::Light } else { Background::Dark })
+}
+
+/// Make sure the console attached to stdout interprets ANSI escape sequences.
+///
+/// Windows 10 and later support ANSI sequences, but only once virtual terminal
+/// processing has been switched on with `SetConsoleMode`. Older consoles reject the
+/// mode and would print the sequences literally.
+///
+/// # Returns
+///
+/// Returns `false` if stdout is a console that can't display ANSI colors, in which
+/// case colors should be disabled; `true` otherwise, including when stdout is
+/// redirected to a file or pipe.
+#[cfg(windows)]
+pub fn enable_ansi_support() -> bool {
+    use std::ffi::c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    unsafe extern "system" {
+        fn GetStdHandle(std_handle: u32) -> *mut c_void;
+        fn GetConsoleMode(console_handle: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console_handle: *mut c_void, mode: u32) -> i32;
+    }
+
+    // SAFETY: GetStdHandle takes no pointers; GetConsoleMode writes a u32 through
+    // `mode`, which is valid for writes; SetConsoleMode takes the mode by value.
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            // Not a console (redirected output); leave colors to the user
+            return true;
+        }
+        mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+            || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+/// Make sure stdout interprets ANSI escape sequences (always true off Windows).
+#[cfg(not(windows))]
+pub fn enable_ansi_support() -> bool {
+    true
+}
+
+/// Get the display width of the widest logo line.
+///
+/// # Arguments
+///
+/// * `logo_lines` - Logo lines as returned by `get_logo_lines_for_vendor`
+pub fn logo_width(logo_lines: &[String]) -> usize {
+    logo_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0)
+}
+
+/// Get the column where the info block starts, for wrapping flag lines.
+///
+/// # Arguments
+///
+/// * `logo_lines` - Logo lines as returned by `get_logo_lines_for_vendor`
+/// * `opts` - Layout options
+///
+/// # Returns
+///
+/// Returns the logo width plus the gap, or 0 when there is no logo, since the info
+/// block is then printed flush-left.
+pub fn info_column_offset(logo_lines: &[String], opts: &DisplayOptions) -> usize {
+    if logo_lines.is_empty() {
+        0
+    } else {
+        logo_width(logo_lines) + opts.gap
+    }
+}
+
+/// Format a cache size in KB for display.
+///
+/// Sizes of 1000KB and above are shown in MB with one decimal place, dropping the
+/// decimal for whole-MB values (e.g. 20480KB → "20MB", 1536KB → "1.5MB"). Smaller
+/// sizes are shown in KB (e.g. "999KB"). Large totals get thousands separators
+/// (e.g. "2,304MB").
+///
+/// The arithmetic is done on integers in `u64`, so aggregate totals of huge
+/// multi-socket systems neither overflow nor lose precision.
+///
+/// # Arguments
+///
+/// * `size_kb` - Cache size in kilobytes
+pub fn format_cache_size(size_kb: impl Into<u64>) -> String {
+    let size_kb = size_kb.into();
+    if size_kb >= 1000 {
+        // Round to tenths of a MB
+        let tenths = (size_kb * 10 + 512) / 1024;
+        match tenths % 10 {
+            0 => format!("{}MB", group_thousands(tenths / 10)),
+            fraction => format!("{}.{}MB", group_thousands(tenths / 10), fraction),
+        }
+    } else {
+        format!("{}KB", size_kb)
+    }
+}
+
+/// Format a number with commas between groups of three digits (e.g. 1234567 → "1,234,567").
+///
+/// # Arguments
+///
+/// * `value` - Number to format
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Build the `--compact-cache` summary line, e.g. "Cache: 80KB L1 / 2MB L2 / 32MB L3".
+///
+/// # Arguments
+///
+/// * `levels` - `(level name, total size in KB)` pairs; unknown levels are dropped
+///
+/// # Returns
+///
+/// Returns `Some(String)` with the summary, or `None` if no level is known.
+pub fn compact_cache_line(levels: &[(&str, Option<u64>)]) -> Option<String> {
+    let parts: Vec<String> = levels.iter()
+        .filter_map(|(name, size)| size.map(|kb| format!("{} {}", format_cache_size(kb), name)))
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(format!("Cache: {}", parts.join(" / ")))
+    }
+}
+
+/// Format per-CPU frequencies, collapsing runs of identical values into ranges.
+///
+/// Frequencies are compared after rounding to the displayed precision (0.1 GHz), so
+/// small fluctuations between otherwise idle cores don't split a range. For example
+/// sixteen readings become "CPUs 0-7: 3.2 GHz, CPUs 8-15: 2.4 GHz".
+///
+/// # Arguments
+///
+/// * `frequencies` - `(cpu id, frequency in MHz)` pairs, sorted by CPU id
+pub fn format_core_frequencies(frequencies: &[(u32, f32)]) -> String {
+    let mut groups: Vec<(u32, u32, String)> = Vec::new(); // (first cpu, last cpu, value)
+
+    for &(cpu, mhz) in frequencies {
+        let value = format!("{:.1} GHz", mhz / 1000.0);
+        match groups.last_mut() {
+            Some((_, last, group_value)) if *last + 1 == cpu && *group_value == value => *last = cpu,
+            _ => groups.push((cpu, cpu, value)),
+        }
+    }
+
+    groups.iter()
+        .map(|(first, last, value)| {
+            if first == last {
+                format!("CPU {}: {}", first, value)
+            } else {
+                format!("CPUs {}-{}: {}", first, last, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Format CPU numbers in the kernel's list format, e.g. "0-7,16-23".
+///
+/// # Arguments
+///
+/// * `cpus` - CPU numbers, sorted ascending
+pub fn format_cpu_list(cpus: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == cpu => *last = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges.iter()
+        .map(|&(first, last)| if first == last { first.to_string() } else { format!("{}-{}", first, last) })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Build one descriptive line per cache for verbose output.
+///
+/// Produces lines such as "L2 Cache: 8 instances × 2MB, 16-way, 64B lines, shared
+/// by 2 CPUs, inclusive", leaving out attributes the platform doesn't report.
+///
+/// # Arguments
+///
+/// * `caches` - Cache hierarchy as returned by `CpuInfo::caches`
+pub fn cache_detail_lines(caches: &[Cache]) -> Vec<String> {
+    caches.iter()
+        .map(|cache| {
+            let mut parts = vec![match cache.instances {
+                Some(instances) => format!(
+                    "{} instance{} × {}",
+                    instances, if instances == 1 { "" } else { "s" }, format_cache_size(cache.size_kb),
+                ),
+                None => format_cache_size(cache.size_kb),
+            }];
+            if let Some(ways) = cache.associativity {
+                parts.push(format!("{}-way", ways));
+            }
+            if let Some(line_size) = cache.line_size {
+                parts.push(format!("{}B lines", line_size));
+            }
+            if let Some(cpus) = cache.shared_cpus {
+                parts.push(format!("shared by {} CPU{}", cpus, if cpus == 1 { "" } else { "s" }));
+            }
+            if let Some(inclusive) = cache.inclusive {
+                parts.push(if inclusive { "inclusive" } else { "non-inclusive" }.to_string());
+            }
+            format!("{} Cache: {}", cache.name(), parts.join(", "))
+        })
+        .collect()
+}
+
+/// Wrap a labeled list of items to a maximum line width.
+///
+/// The first line starts with `label`; continuation lines are indented with
+/// `FLAG_INDENT` like the regular flag list.
+///
+/// # Arguments
+///
+/// * `label` - Leading label including its separator (e.g. "SIMD: ")
+/// * `items` - Items to list
+/// * `sep` - Separator placed between items (e.g. " " or ", ")
+/// * `width` - Maximum line width
+pub fn wrap_list(label: &str, items: &[&str], sep: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current_line = String::from(label);
+    let mut first = true;
+
+    for item in items {
+        if first {
+            current_line.push_str(item);
+            first = false;
+        } else if current_line.len() + sep.len() + item.len() > width {
+            lines.push(current_line);
+            current_line = format!("{}{}", FLAG_INDENT, item);
+        } else {
+            current_line.push_str(sep);
+            current_line.push_str(item);
+        }
+    }
+    lines.push(current_line);
+    lines
+}
+
+/// Build the `--flags-grouped` lines: one wrapped line per flag category.
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux or macOS format
+/// * `sep` - Separator placed between flags
+/// * `width` - Maximum line width
+pub fn grouped_flag_lines(flags: &str, sep: &str, width: usize) -> Vec<String> {
+    flags::group_flags(flags)
+        .into_iter()
+        .flat_map(|(category, members)| wrap_list(&format!("{}: ", category.label()), &members, sep, width))
+        .collect()
+}
+
+/// Apply the `--label` overrides to "Label: value" information lines.
+///
+/// Called at the end of every `get_info_lines` implementation; the flag label is
+/// applied by `flag_lines` itself, before wrapping.
+///
+/// # Arguments
+///
+/// * `lines` - Information lines to rename in place
+/// * `opts` - Display options holding the overrides
+pub fn relabel(lines: &mut [String], opts: &DisplayOptions) {
+    if opts.labels.is_empty() {
+        return;
+    }
+    for line in lines {
+        if let Some((label, value)) = line.split_once(": ") {
+            let renamed = opts.label(label);
+            if renamed != label {
+                *line = format!("{}: {}", renamed, value);
+            }
+        }
+    }
+}
+
+/// Build the flag lines shown below the information block.
+///
+/// Lists the flags either plainly or grouped by category (`--flags-grouped`). With
+/// `--max-flags <n>` only the first `n` flags are kept and the rest are summarized
+/// as "... (+N more)".
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux or macOS format
+/// * `sep` - Separator placed between flags
+/// * `width` - Maximum line width
+/// * `opts` - Display options (grouped flags, flag limit)
+pub fn flag_lines(flags: &str, sep: &str, width: usize, opts: &DisplayOptions) -> Vec<String> {
+    let all = flags::split_flags(flags);
+    let shown = opts.max_flags.map_or(all.len(), |max| max.min(all.len()));
+    let more = (shown < all.len()).then(|| format!("... (+{} more)", all.len() - shown));
+
+    if opts.flags_grouped {
+        let mut lines = grouped_flag_lines(&all[..shown].join(" "), sep, width);
+        lines.extend(more);
+        return lines;
+    }
+
+    let mut items = all[..shown].to_vec();
+    items.extend(more.as_deref());
+    wrap_list(&format!("{}: ", opts.label("Flags")), &items, sep, width)
+}
+
+/// Build the `--core-map` grid: one cell per logical CPU, colored by core type.
+///
+/// Cells are laid out in CPU number order, at most 16 per row, below a header line;
+/// a legend naming the core types follows. Without colors each cell is the core
+/// type's letter (P, E, or L) instead of a colored block.
+///
+/// # Arguments
+///
+/// * `kinds` - Core type of each logical CPU, in CPU number order
+/// * `width` - Maximum line width
+/// * `opts` - Display options (colors)
+pub fn core_map_lines(kinds: &[CoreKind], width: usize, opts: &DisplayOptions) -> Vec<String> {
+    let cell = |kind: CoreKind| {
+        if opts.use_color() {
+            let color = match kind {
+                CoreKind::Performance => C_FG_RED,
+                CoreKind::Efficiency => C_FG_CYAN,
+                CoreKind::LowPower => C_FG_GREEN,
+            };
+            format!("{}██{}", color, COLOR_RESET)
+        } else {
+            kind.letter().to_string()
+        }
+    };
+    let cell_width = if opts.use_color() { 3 } else { 2 };
+    let per_row = (width.saturating_sub(FLAG_INDENT.len()) / cell_width).clamp(1, 16);
+
+    let mut lines = vec![format!("Core Map: {} logical CPUs", kinds.len())];
+    for row in kinds.chunks(per_row) {
+        let cells: Vec<String> = row.iter().map(|&kind| cell(kind)).collect();
+        lines.push(format!("{}{}", FLAG_INDENT, cells.join(" ")));
+    }
+
+    let mut present = kinds.to_vec();
+    present.sort_unstable();
+    present.dedup();
+    let legend: Vec<String> = present.iter()
+        .map(|&kind| if opts.use_color() {
+            format!("{} {}", cell(kind), kind.name())
+        } else {
+            format!("{} = {}", kind.letter(), kind.name())
+        })
+        .collect();
+    lines.push(format!("{}{}", FLAG_INDENT, legend.join("  ")));
+    lines
+}
+
+/// Print the logo and info lines side by side, followed by the color legend.
+///
+/// This is the final step of every `display_info_with_logo` implementation.
+///
+/// # Arguments
+///
+/// * `logo_lines` - Colored logo lines (may be empty)
+/// * `logo_colors` - Colors substituted into the logo, in slot order
+/// * `info_lines` - Information lines, including any wrapped flag lines
+/// * `opts` - Layout options
+pub fn print_side_by_side(logo_lines: &[String], logo_colors: &[&str], info_lines: &[String], opts: &DisplayOptions) {
+    let mut lines = side_by_side_lines(logo_lines, info_lines, opts);
+    lines.extend(color_legend_lines(logo_colors, opts));
+    print_lines(&lines, opts);
+}
+
+/// Print rendered output lines.
+///
+/// With `--dimensions` the size of the block is also printed to stderr as a JSON
+/// object such as `{"width":96,"height":24}`, so tools embedding the output can
+/// reserve space for it.
+///
+/// # Arguments
+///
+/// * `lines` - Fully rendered lines
+/// * `opts` - Layout options
+pub fn print_lines(lines: &[String], opts: &DisplayOptions) {
+    for line in lines {
+        println!("{}", line);
+    }
+    if opts.dimensions {
+        let (width, height) = block_dimensions(lines);
+        let dimensions = JsonValue::Object(vec![
+            ("width".to_string(), JsonValue::from(width as u32)),
+            ("height".to_string(), JsonValue::from(height as u32)),
+        ]);
+        eprintln!("{}", json::to_string(&dimensions, false));
+    }
+}
+
+/// Measure a block of rendered lines in terminal cells.
+///
+/// ANSI escape sequences take up no space on screen, so they are skipped when
+/// measuring each line's width.
+///
+/// # Arguments
+///
+/// * `lines` - Rendered lines, possibly containing ANSI color codes
+///
+/// # Returns
+///
+/// Returns `(width, height)`: the widest line's visible width and the line count.
+fn block_dimensions(lines: &[String]) -> (usize, usize) {
+    let width = lines.iter().map(|line| visible_width(line.trim_end())).max().unwrap_or(0);
+    (width, lines.len())
+}
+
+/// Measure a line in terminal cells, skipping ANSI escape sequences.
+///
+/// # Arguments
+///
+/// * `line` - Line that may contain color codes
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip a CSI sequence up to and including its final byte (e.g. 'm')
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Lay out logo and info lines side by side.
+///
+/// The logo column is padded to the widest logo line and separated from the info
+/// column by `opts.gap` spaces. Once the logo runs out, wrapped flag lines lose their
+/// indentation so they line up with the rest of the info column.
+///
+/// With `--logo-right` the columns are swapped: the info column is padded to its
+/// widest line and the logo follows it. Both columns keep the same widths, so the
+/// flag wrapping done by the callers works for either order.
+///
+/// `--logo-padding` pushes the logo down by adding blank lines above it.
+///
+/// When there is no logo (e.g. an unknown vendor), the info block is laid out
+/// flush-left without the separator.
+///
+/// # Arguments
+///
+/// * `logo_lines` - Colored logo lines (may be empty)
+/// * `info_lines` - Information lines, including any wrapped flag lines
+/// * `opts` - Layout options
+fn side_by_side_lines(logo_lines: &[String], info_lines: &[String], opts: &DisplayOptions) -> Vec<String> {
+    if logo_lines.is_empty() {
+        return info_lines.iter()
+            .map(|info| info.strip_prefix(FLAG_INDENT).unwrap_or(info).to_string())
+            .collect();
+    }
+
+    let logo_lines: Vec<&str> = std::iter::repeat_n("", opts.logo_padding)
+        .chain(logo_lines.iter().map(|s| s.as_str()))
+        .collect();
+    let logo_width = logo_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let sep = opts.separator();
+    let max_lines = std::cmp::max(logo_lines.len(), info_lines.len());
+
+    if opts.logo_right {
+        // Info lines may be colored (e.g. `--core-map`), so pad by visible width
+        let info_width = info_lines.iter().map(|l| visible_width(l)).max().unwrap_or(0);
+        return (0..max_lines)
+            .map(|i| {
+                let info = info_lines.get(i).map(|s| s.as_str()).unwrap_or("");
+                match logo_lines.get(i) {
+                    Some(&logo) => format!("{}{}{}{}", info, " ".repeat(info_width - visible_width(info)), sep, logo),
+                    None => info.to_string(),
+                }
+            })
+            .collect();
+    }
+
+    (0..max_lines)
+        .map(|i| {
+            let logo = logo_lines.get(i).copied();
+            let mut info = info_lines.get(i).map(|s| s.as_str()).unwrap_or("");
+
+            // Once the logo has run out, remove the indent from flag lines
+            if logo.is_none() {
+                info = info.strip_prefix(FLAG_INDENT).unwrap_or(info);
+            }
+            let logo = logo.unwrap_or("");
+
+            format!("{:<width$}{}{}", logo, sep, info, width = logo_width)
+        })
+        .collect()
+}
+
+/// Build the legend naming each color used by the logo.
+///
+/// Produces a blank line and a line such as "Logo colors: ██ white  ██ red" so the
+/// colors can be identified without relying on color perception. Empty unless
+/// `--color-legend` is set, and under `--no-color`.
+///
+/// # Arguments
+///
+/// * `colors` - Colors substituted into the logo, in slot order
+/// * `opts` - Layout options
+fn color_legend_lines(colors: &[&str], opts: &DisplayOptions) -> Vec<String> {
+    if !opts.color_legend || !opts.use_color() || colors.is_empty() {
+        return Vec::new();
+    }
+
+    let entries: Vec<String> = colors.iter()
+        .map(|color| format!("{}██{} {}", color, COLOR_RESET, color_name(color)))
+        .collect();
+    vec![String::new(), format!("Logo colors: {}", entries.join("  "))]
+}
+
+/// Escape text for inclusion in HTML.
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Convert a colored logo line to HTML, turning ANSI color codes into styled spans.
+///
+/// # Arguments
+///
+/// * `line` - Logo line containing `C_FG_*` escape sequences
+fn logo_line_to_html(line: &str) -> String {
+    let mut html = String::new();
+    let mut span_open = false;
+    let mut rest = line;
+
+    while let Some(start) = rest.find("\x1b[") {
+        html.push_str(&html_escape(&rest[..start]));
+        let Some(end) = rest[start..].find('m') else {
+            rest = "";
+            break;
+        };
+        let code = &rest[start..start + end + 1];
+        if span_open {
+            html.push_str("</span>");
+            span_open = false;
+        }
+        if let Some(css) = css_color(code) {
+            html.push_str(&format!("<span style=\"color:{}\">", css));
+            span_open = true;
+        }
+        rest = &rest[start + end + 1..];
+    }
+    html.push_str(&html_escape(rest));
+    if span_open {
+        html.push_str("</span>");
+    }
+    html
+}
+
+/// Print logo and info as a self-contained HTML snippet (`--format html`).
+///
+/// The logo is emitted as a `<pre>` block with inline CSS colors translated from the
+/// ANSI palette, and each "Label: value" info line becomes an entry in a `<dl>`. All
+/// styling is inline so the snippet can be embedded in any page without external CSS.
+///
+/// # Arguments
+///
+/// * `logo_lines` - Colored logo lines (may be empty)
+/// * `info_lines` - Unwrapped "Label: value" information lines
+pub fn print_html(logo_lines: &[String], info_lines: &[String]) {
+    println!("<div class=\"rcpufetch\" style=\"display:flex;gap:2em;align-items:flex-start;background:#1e1e1e;color:#e5e5e5;padding:1em;font-family:monospace\">");
+
+    if !logo_lines.is_empty() {
+        println!("<pre style=\"margin:0;font-weight:bold\">");
+        for line in logo_lines {
+            println!("{}", logo_line_to_html(line));
+        }
+        println!("</pre>");
+    }
+
+    println!("<dl style=\"margin:0;display:grid;grid-template-columns:max-content auto;column-gap:1em\">");
+    for line in info_lines {
+        let (label, value) = line.split_once(": ").unwrap_or(("", line.as_str()));
+        println!("  <dt style=\"font-weight:bold\">{}</dt><dd style=\"margin:0\">{}</dd>", html_escape(label), html_escape(value));
+    }
+    println!("</dl>");
+    println!("</div>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_explicit_columns_win() {
+        assert_eq!(pick_width(Some(Width::Columns(120)), false, 100, Some(40), Some(60)), 120);
+    }
+
+    #[test]
+    fn width_auto_ignores_columns() {
+        assert_eq!(pick_width(Some(Width::Auto), false, 100, Some(40), Some(60)), 60);
+    }
+
+    #[test]
+    fn width_columns_env_before_terminal() {
+        assert_eq!(pick_width(None, false, 100, Some(40), Some(60)), 40);
+    }
+
+    #[test]
+    fn width_terminal_without_columns_env() {
+        assert_eq!(pick_width(None, false, 100, None, Some(60)), 60);
+    }
+
+    #[test]
+    fn width_default_without_terminal() {
+        assert_eq!(pick_width(None, false, 100, None, None), 100);
+    }
+
+    #[test]
+    fn unknown_vendor_without_logo_is_flush_left() {
+        let info = vec![
+            "Name: Mystery CPU".to_string(),
+            "Flags: fpu sse".to_string(),
+            format!("{}sse2 avx", FLAG_INDENT),
+        ];
+        let expected = ["Name: Mystery CPU", "Flags: fpu sse", "sse2 avx"];
+        for logo_padding in [0, 3] {
+            let opts = DisplayOptions { gap: 4, logo_padding, ..Default::default() };
+            assert_eq!(side_by_side_lines(&[], &info, &opts), expected);
+        }
+    }
+
+    #[test]
+    fn relabel_renames_only_the_given_field() {
+        let opts = DisplayOptions {
+            labels: vec![("cores", "CPUs".to_string()), ("l3_kb", "Last Level".to_string())],
+            ..Default::default()
+        };
+        let mut lines = vec![
+            "Cores: 8 cores (16 threads)".to_string(),
+            "L3 Cache Size: 32MB".to_string(),
+            "Vendor: AuthenticAMD".to_string(),
+        ];
+        relabel(&mut lines, &opts);
+        assert_eq!(lines, ["CPUs: 8 cores (16 threads)", "Last Level: 32MB", "Vendor: AuthenticAMD"]);
+    }
+
+    #[test]
+    fn cache_size_whole_megabytes() {
+        assert_eq!(format_cache_size(20480u32), "20MB");
+    }
+
+    #[test]
+    fn cache_size_fractional_megabytes() {
+        assert_eq!(format_cache_size(1536u32), "1.5MB");
+    }
+
+    #[test]
+    fn cache_size_kilobytes() {
+        assert_eq!(format_cache_size(999u32), "999KB");
+    }
+
+    #[test]
+    fn cache_size_just_below_one_mebibyte() {
+        assert_eq!(format_cache_size(1000u32), "1MB");
+        assert_eq!(format_cache_size(1023u32), "1MB");
+    }
+
+    #[test]
+    fn cache_size_thousands_separator() {
+        assert_eq!(format_cache_size(2304u64 * 1024), "2,304MB");
+    }
+}
@@ -0,0 +1,476 @@
+//! CPU flag helpers for rcpufetch.
+//!
+//! Linux reports flags as a space-separated list of lowercase names ("sse4_2 avx2"),
+//! while macOS reports comma-separated sysctl names ("FEAT_AES,AdvSIMD"). This module
+//! normalizes both formats and classifies flags into categories for grouped display.
+
+/// Category used when grouping flags for display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagCategory {
+    Simd,
+    Crypto,
+    Virtualization,
+    Security,
+    Power,
+    Other,
+}
+
+impl FlagCategory {
+    /// All categories, in display order.
+    pub const ALL: [FlagCategory; 6] = [
+        FlagCategory::Simd,
+        FlagCategory::Crypto,
+        FlagCategory::Virtualization,
+        FlagCategory::Security,
+        FlagCategory::Power,
+        FlagCategory::Other,
+    ];
+
+    /// Display label for the category.
+    pub fn label(self) -> &'static str {
+        match self {
+            FlagCategory::Simd => "SIMD",
+            FlagCategory::Crypto => "Crypto",
+            FlagCategory::Virtualization => "Virtualization",
+            FlagCategory::Security => "Security",
+            FlagCategory::Power => "Power",
+            FlagCategory::Other => "Other",
+        }
+    }
+}
+
+/// Classification table keyed by normalized flag name.
+///
+/// Covers x86 (/proc/cpuinfo), ARM (/proc/cpuinfo "Features"), and macOS
+/// (`hw.optional.arm.FEAT_*`) names after normalization. Flags missing from both
+/// this table and `FLAG_PREFIXES` are reported by `--unknown-flags`.
+const FLAG_TABLE: &[(&str, FlagCategory)] = &[
+    // SIMD
+    ("mmx", FlagCategory::Simd),
+    ("mmxext", FlagCategory::Simd),
+    ("3dnow", FlagCategory::Simd),
+    ("3dnowext", FlagCategory::Simd),
+    ("3dnowprefetch", FlagCategory::Simd),
+    ("sse", FlagCategory::Simd),
+    ("sse2", FlagCategory::Simd),
+    ("sse3", FlagCategory::Simd),
+    ("pni", FlagCategory::Simd),
+    ("ssse3", FlagCategory::Simd),
+    ("sse4_1", FlagCategory::Simd),
+    ("sse4_2", FlagCategory::Simd),
+    ("sse4a", FlagCategory::Simd),
+    ("misalignsse", FlagCategory::Simd),
+    ("avx", FlagCategory::Simd),
+    ("avx2", FlagCategory::Simd),
+    ("fma", FlagCategory::Simd),
+    ("fma4", FlagCategory::Simd),
+    ("f16c", FlagCategory::Simd),
+    ("xop", FlagCategory::Simd),
+    ("fp", FlagCategory::Simd),
+    ("fphp", FlagCategory::Simd),
+    ("fp16", FlagCategory::Simd),
+    ("asimd", FlagCategory::Simd),
+    ("advsimd", FlagCategory::Simd),
+    ("advsimd_hpfpcvt", FlagCategory::Simd),
+    ("dotprod", FlagCategory::Simd),
+    ("i8mm", FlagCategory::Simd),
+    ("bf16", FlagCategory::Simd),
+    ("ebf16", FlagCategory::Simd),
+    ("fhm", FlagCategory::Simd),
+    ("rdm", FlagCategory::Simd),
+    ("fcma", FlagCategory::Simd),
+    ("jscvt", FlagCategory::Simd),
+    ("frintts", FlagCategory::Simd),
+    // Crypto
+    ("aes", FlagCategory::Crypto),
+    ("vaes", FlagCategory::Crypto),
+    ("pclmulqdq", FlagCategory::Crypto),
+    ("vpclmulqdq", FlagCategory::Crypto),
+    ("pmull", FlagCategory::Crypto),
+    ("sha", FlagCategory::Crypto),
+    ("sha_ni", FlagCategory::Crypto),
+    ("sha1", FlagCategory::Crypto),
+    ("sha2", FlagCategory::Crypto),
+    ("sha3", FlagCategory::Crypto),
+    ("sha256", FlagCategory::Crypto),
+    ("sha512", FlagCategory::Crypto),
+    ("sm3", FlagCategory::Crypto),
+    ("sm4", FlagCategory::Crypto),
+    ("gfni", FlagCategory::Crypto),
+    ("rdrand", FlagCategory::Crypto),
+    ("rdseed", FlagCategory::Crypto),
+    ("rng", FlagCategory::Crypto),
+    ("crc32", FlagCategory::Crypto),
+    // Virtualization
+    ("vmx", FlagCategory::Virtualization),
+    ("svm", FlagCategory::Virtualization),
+    ("hypervisor", FlagCategory::Virtualization),
+    ("ept", FlagCategory::Virtualization),
+    ("ept_ad", FlagCategory::Virtualization),
+    ("vpid", FlagCategory::Virtualization),
+    ("npt", FlagCategory::Virtualization),
+    ("flexpriority", FlagCategory::Virtualization),
+    ("tpr_shadow", FlagCategory::Virtualization),
+    ("vnmi", FlagCategory::Virtualization),
+    ("vmmcall", FlagCategory::Virtualization),
+    ("v_vmsave_vmload", FlagCategory::Virtualization),
+    ("vgif", FlagCategory::Virtualization),
+    ("avic", FlagCategory::Virtualization),
+    ("x2avic", FlagCategory::Virtualization),
+    ("nrip_save", FlagCategory::Virtualization),
+    ("lbrv", FlagCategory::Virtualization),
+    ("svm_lock", FlagCategory::Virtualization),
+    ("decodeassists", FlagCategory::Virtualization),
+    ("pausefilter", FlagCategory::Virtualization),
+    ("pfthreshold", FlagCategory::Virtualization),
+    ("tsc_scale", FlagCategory::Virtualization),
+    ("vmcb_clean", FlagCategory::Virtualization),
+    ("flushbyasid", FlagCategory::Virtualization),
+    ("v_spec_ctrl", FlagCategory::Virtualization),
+    // Security
+    ("nx", FlagCategory::Security),
+    ("smep", FlagCategory::Security),
+    ("smap", FlagCategory::Security),
+    ("umip", FlagCategory::Security),
+    ("pku", FlagCategory::Security),
+    ("ospke", FlagCategory::Security),
+    ("ibrs", FlagCategory::Security),
+    ("ibrs_enhanced", FlagCategory::Security),
+    ("ibpb", FlagCategory::Security),
+    ("stibp", FlagCategory::Security),
+    ("ssbd", FlagCategory::Security),
+    ("amd_ssbd", FlagCategory::Security),
+    ("virt_ssbd", FlagCategory::Security),
+    ("amd_ibpb", FlagCategory::Security),
+    ("amd_stibp", FlagCategory::Security),
+    ("md_clear", FlagCategory::Security),
+    ("flush_l1d", FlagCategory::Security),
+    ("arch_capabilities", FlagCategory::Security),
+    ("spec_ctrl", FlagCategory::Security),
+    ("intel_stibp", FlagCategory::Security),
+    ("pti", FlagCategory::Security),
+    ("ibt", FlagCategory::Security),
+    ("shstk", FlagCategory::Security),
+    ("user_shstk", FlagCategory::Security),
+    ("sgx", FlagCategory::Security),
+    ("sgx_lc", FlagCategory::Security),
+    ("tme", FlagCategory::Security),
+    ("sme", FlagCategory::Security),
+    ("sev", FlagCategory::Security),
+    ("sev_es", FlagCategory::Security),
+    ("sev_snp", FlagCategory::Security),
+    ("paca", FlagCategory::Security),
+    ("pacg", FlagCategory::Security),
+    ("pauth", FlagCategory::Security),
+    ("bti", FlagCategory::Security),
+    ("ssbs", FlagCategory::Security),
+    ("sb", FlagCategory::Security),
+    ("mte", FlagCategory::Security),
+    ("csv2", FlagCategory::Security),
+    ("csv3", FlagCategory::Security),
+    ("dit", FlagCategory::Security),
+    ("specres", FlagCategory::Security),
+    // Power
+    ("acpi", FlagCategory::Power),
+    ("est", FlagCategory::Power),
+    ("tm", FlagCategory::Power),
+    ("tm2", FlagCategory::Power),
+    ("aperfmperf", FlagCategory::Power),
+    ("hwp", FlagCategory::Power),
+    ("hwp_notify", FlagCategory::Power),
+    ("hwp_act_window", FlagCategory::Power),
+    ("hwp_epp", FlagCategory::Power),
+    ("hwp_pkg_req", FlagCategory::Power),
+    ("epb", FlagCategory::Power),
+    ("dtherm", FlagCategory::Power),
+    ("ida", FlagCategory::Power),
+    ("arat", FlagCategory::Power),
+    ("pln", FlagCategory::Power),
+    ("pts", FlagCategory::Power),
+    ("cpb", FlagCategory::Power),
+    ("hw_pstate", FlagCategory::Power),
+    ("cppc", FlagCategory::Power),
+    ("rapl", FlagCategory::Power),
+    ("monitor", FlagCategory::Power),
+    ("mwaitx", FlagCategory::Power),
+    ("waitpkg", FlagCategory::Power),
+    // Other: recognized, but not in a display category. Listing them here keeps
+    // them out of `--unknown-flags`.
+    ("fpu", FlagCategory::Other),
+    ("vme", FlagCategory::Other),
+    ("de", FlagCategory::Other),
+    ("pse", FlagCategory::Other),
+    ("tsc", FlagCategory::Other),
+    ("msr", FlagCategory::Other),
+    ("pae", FlagCategory::Other),
+    ("mce", FlagCategory::Other),
+    ("cx8", FlagCategory::Other),
+    ("apic", FlagCategory::Other),
+    ("sep", FlagCategory::Other),
+    ("mtrr", FlagCategory::Other),
+    ("pge", FlagCategory::Other),
+    ("mca", FlagCategory::Other),
+    ("cmov", FlagCategory::Other),
+    ("pat", FlagCategory::Other),
+    ("pse36", FlagCategory::Other),
+    ("clflush", FlagCategory::Other),
+    ("fxsr", FlagCategory::Other),
+    ("ss", FlagCategory::Other),
+    ("ht", FlagCategory::Other),
+    ("syscall", FlagCategory::Other),
+    ("pdpe1gb", FlagCategory::Other),
+    ("rdtscp", FlagCategory::Other),
+    ("lm", FlagCategory::Other),
+    ("constant_tsc", FlagCategory::Other),
+    ("rep_good", FlagCategory::Other),
+    ("nopl", FlagCategory::Other),
+    ("xtopology", FlagCategory::Other),
+    ("nonstop_tsc", FlagCategory::Other),
+    ("cpuid", FlagCategory::Other),
+    ("tsc_known_freq", FlagCategory::Other),
+    ("cx16", FlagCategory::Other),
+    ("pcid", FlagCategory::Other),
+    ("x2apic", FlagCategory::Other),
+    ("movbe", FlagCategory::Other),
+    ("popcnt", FlagCategory::Other),
+    ("tsc_deadline_timer", FlagCategory::Other),
+    ("xsave", FlagCategory::Other),
+    ("osxsave", FlagCategory::Other),
+    ("lahf_lm", FlagCategory::Other),
+    ("abm", FlagCategory::Other),
+    ("cpuid_fault", FlagCategory::Other),
+    ("fsgsbase", FlagCategory::Other),
+    ("tsc_adjust", FlagCategory::Other),
+    ("bmi1", FlagCategory::Other),
+    ("bmi2", FlagCategory::Other),
+    ("erms", FlagCategory::Other),
+    ("invpcid", FlagCategory::Other),
+    ("adx", FlagCategory::Other),
+    ("clflushopt", FlagCategory::Other),
+    ("clwb", FlagCategory::Other),
+    ("xsaveopt", FlagCategory::Other),
+    ("xsavec", FlagCategory::Other),
+    ("xgetbv1", FlagCategory::Other),
+    ("xsaves", FlagCategory::Other),
+    ("wbnoinvd", FlagCategory::Other),
+    ("rdpid", FlagCategory::Other),
+    ("bus_lock_detect", FlagCategory::Other),
+    ("cldemote", FlagCategory::Other),
+    ("movdiri", FlagCategory::Other),
+    ("movdir64b", FlagCategory::Other),
+    ("fsrm", FlagCategory::Other),
+    ("serialize", FlagCategory::Other),
+    ("tsxldtrk", FlagCategory::Other),
+    ("hle", FlagCategory::Other),
+    ("rtm", FlagCategory::Other),
+    ("mpx", FlagCategory::Other),
+    ("lzcnt", FlagCategory::Other),
+    ("clzero", FlagCategory::Other),
+    ("evtstrm", FlagCategory::Other),
+    ("atomics", FlagCategory::Other),
+    ("lse", FlagCategory::Other),
+    ("lrcpc", FlagCategory::Other),
+    ("ilrcpc", FlagCategory::Other),
+    ("uscat", FlagCategory::Other),
+    ("flagm", FlagCategory::Other),
+    ("flagm2", FlagCategory::Other),
+    ("dcpop", FlagCategory::Other),
+    ("dcpodp", FlagCategory::Other),
+    ("lrcpc2", FlagCategory::Other),
+    ("lse2", FlagCategory::Other),
+    ("ecv", FlagCategory::Other),
+    ("afp", FlagCategory::Other),
+    ("rpres", FlagCategory::Other),
+];
+
+/// Prefix rules for flag families too large to list individually.
+const FLAG_PREFIXES: &[(&str, FlagCategory)] = &[
+    ("avx512", FlagCategory::Simd),
+    ("avx_", FlagCategory::Simd),
+    ("amx", FlagCategory::Simd),
+    ("sve", FlagCategory::Simd),
+    ("sme", FlagCategory::Simd),
+    ("asimd", FlagCategory::Simd),
+    ("sha", FlagCategory::Crypto),
+    ("sev", FlagCategory::Security),
+];
+
+/// AVX-512 subsets in their conventional order, keyed by Linux flag name.
+const AVX512_SUBSETS: &[(&str, &str)] = &[
+    ("avx512f", "F"),
+    ("avx512cd", "CD"),
+    ("avx512bw", "BW"),
+    ("avx512dq", "DQ"),
+    ("avx512vl", "VL"),
+    ("avx512ifma", "IFMA"),
+    ("avx512vbmi", "VBMI"),
+    ("avx512_vbmi2", "VBMI2"),
+    ("avx512_vnni", "VNNI"),
+    ("avx512_bitalg", "BITALG"),
+    ("avx512_vpopcntdq", "VPOPCNTDQ"),
+    ("avx512_bf16", "BF16"),
+    ("avx512_fp16", "FP16"),
+    ("avx512_vp2intersect", "VP2INTERSECT"),
+    ("avx512er", "ER"),
+    ("avx512pf", "PF"),
+    ("avx512_4vnniw", "4VNNIW"),
+    ("avx512_4fmaps", "4FMAPS"),
+];
+
+/// List the AVX-512 subsets present, using their conventional short names.
+///
+/// Subsets missing from `AVX512_SUBSETS` are still listed, named by stripping the
+/// "avx512" prefix and uppercasing (e.g. "avx512_new" → "NEW").
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux format
+///
+/// # Returns
+///
+/// Returns short names such as `["F", "CD", "VL", "VNNI"]`, known subsets first in
+/// conventional order; empty when there is no AVX-512 support.
+pub fn avx512_subsets(flags: &str) -> Vec<String> {
+    let present: Vec<&str> = split_flags(flags).into_iter()
+        .filter(|flag| flag.starts_with("avx512"))
+        .collect();
+
+    let mut subsets: Vec<String> = AVX512_SUBSETS.iter()
+        .filter(|(flag, _)| present.contains(flag))
+        .map(|(_, name)| name.to_string())
+        .collect();
+    for flag in present {
+        if !AVX512_SUBSETS.iter().any(|(known, _)| *known == flag) {
+            subsets.push(flag.trim_start_matches("avx512").trim_start_matches('_').to_uppercase());
+        }
+    }
+    subsets
+}
+
+/// List the control-flow and privilege protection features present.
+///
+/// CET (Control-flow Enforcement Technology) is reported by Linux as `user_shstk`
+/// (user-space shadow stacks, only listed when the kernel enables them) and `ibt`
+/// (Indirect Branch Tracking). SMEP and SMAP stop the kernel from executing or
+/// accessing user-space pages, and UMIP (User-Mode Instruction Prevention) blocks
+/// instructions such as SGDT from leaking kernel addresses to user space.
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux format
+///
+/// # Returns
+///
+/// Returns names such as `["CET (shadow stack, IBT)", "SMEP", "SMAP", "UMIP"]`, or
+/// an empty `Vec` when none of the features are present.
+pub fn control_flow_protection(flags: &str) -> Vec<String> {
+    let present = split_flags(flags);
+    let has_flag = |name: &str| present.contains(&name);
+
+    let mut features = Vec::new();
+    let cet: Vec<&str> = [("user_shstk", "shadow stack"), ("ibt", "IBT")].iter()
+        .filter(|(flag, _)| has_flag(flag))
+        .map(|(_, name)| *name)
+        .collect();
+    if !cet.is_empty() {
+        features.push(format!("CET ({})", cet.join(", ")));
+    }
+    for (flag, name) in [("smep", "SMEP"), ("smap", "SMAP"), ("umip", "UMIP")] {
+        if has_flag(flag) {
+            features.push(name.to_string());
+        }
+    }
+    features
+}
+
+/// Split a flag string in either the Linux (space-separated) or macOS
+/// (comma-separated) format into individual flags.
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string
+pub fn split_flags(flags: &str) -> Vec<&str> {
+    flags.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|flag| !flag.is_empty())
+        .collect()
+}
+
+/// Normalize a flag name for lookups.
+///
+/// Lowercases the name and strips the macOS `FEAT_` prefix, so "FEAT_AES" and "aes"
+/// compare equal.
+///
+/// # Arguments
+///
+/// * `flag` - Flag name as reported by the OS
+pub fn normalize(flag: &str) -> String {
+    let lower = flag.to_lowercase();
+    lower.strip_prefix("feat_").map(str::to_string).unwrap_or(lower)
+}
+
+/// Look up a flag in the classification table and prefix rules.
+///
+/// Exact matches in the table win over prefix rules.
+///
+/// # Arguments
+///
+/// * `flag` - Flag name as reported by the OS
+fn lookup(flag: &str) -> Option<FlagCategory> {
+    let name = normalize(flag);
+    FLAG_TABLE.iter()
+        .find(|(known, _)| *known == name)
+        .or_else(|| FLAG_PREFIXES.iter().find(|(prefix, _)| name.starts_with(prefix)))
+        .map(|(_, category)| *category)
+}
+
+/// Classify a single flag.
+///
+/// Anything unrecognized is classified as `FlagCategory::Other`.
+///
+/// # Arguments
+///
+/// * `flag` - Flag name as reported by the OS
+pub fn classify(flag: &str) -> FlagCategory {
+    lookup(flag).unwrap_or(FlagCategory::Other)
+}
+
+/// List the flags missing from the classification table (`--unknown-flags`).
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux or macOS format
+///
+/// # Returns
+///
+/// Returns the unrecognized flags in their original order and spelling, without
+/// duplicates.
+pub fn unknown_flags(flags: &str) -> Vec<&str> {
+    let mut unknown: Vec<&str> = Vec::new();
+    for flag in split_flags(flags) {
+        if lookup(flag).is_none() && !unknown.contains(&flag) {
+            unknown.push(flag);
+        }
+    }
+    unknown
+}
+
+/// Group flags by category.
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string in Linux or macOS format
+///
+/// # Returns
+///
+/// Returns the non-empty groups in display order, each with its flags in their
+/// original order and spelling.
+pub fn group_flags(flags: &str) -> Vec<(FlagCategory, Vec<&str>)> {
+    let all = split_flags(flags);
+    FlagCategory::ALL.iter()
+        .map(|&category| {
+            let members: Vec<&str> = all.iter().copied().filter(|flag| classify(flag) == category).collect();
+            (category, members)
+        })
+        .filter(|(_, members)| !members.is_empty())
+        .collect()
+}
@@ -0,0 +1,238 @@
+//! Best-effort CPU detection for operating systems without a dedicated module.
+//!
+//! The BSDs, illumos, and other Unix variants don't have their own module yet. Rather
+//! than printing nothing, this module reports what can be found portably: the logical
+//! CPU count from the standard library (`sysconf` on Unix), the architecture rcpufetch
+//! was built for, and on x86 everything CPUID provides (vendor, model, caches, flags).
+//! The output carries a note that support on the OS is limited.
+
+use crate::art::logos::get_logo_for_vendor;
+use crate::cpu::{self, Cache, CpuInfo};
+use crate::cpuid;
+use crate::display::{self, DisplayOptions};
+use crate::json::{self, JsonValue};
+use std::process::Command;
+
+/// CPU information gathered without OS-specific support.
+pub struct GenericCpuInfo {
+    /// Operating system name (e.g. "freebsd")
+    os: &'static str,
+    model: String,
+    vendor: String,
+    architecture: String,
+    /// Logical CPUs available to rcpufetch
+    logical_cores: u32,
+    /// Base frequency in MHz from CPUID leaf 0x16 (recent Intel CPUs only)
+    base_mhz: Option<u32>,
+    caches: Vec<Cache>,
+    /// Flags joined with spaces, in Linux format
+    flags: String,
+}
+
+impl GenericCpuInfo {
+    /// Gather whatever CPU information is available on an unsupported OS.
+    ///
+    /// # Arguments
+    ///
+    /// * `os` - Operating system name from `std::env::consts::OS`
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(GenericCpuInfo)`; fields that can't be detected are reported as
+    /// unknown, since partial output is the point of this module.
+    pub fn new(os: &'static str) -> Result<Self, String> {
+        let logical_cores = std::thread::available_parallelism().map_or(0, |n| n.get() as u32);
+        let caches = cpuid::cache_parameters().into_iter()
+            .map(|p| Cache {
+                level: p.level,
+                cache_type: p.cache_type,
+                size_kb: p.size_kb,
+                associativity: Some(p.ways),
+                line_size: Some(p.line_size),
+                shared_cpus: Some(p.shared_cpus),
+                instances: None,
+                inclusive: Some(p.inclusive),
+            })
+            .collect();
+
+        Ok(GenericCpuInfo {
+            os,
+            model: cpuid::brand_string().or_else(Self::sysctl_model).unwrap_or_else(|| "Unknown".to_string()),
+            vendor: cpuid::vendor().unwrap_or_else(|| "Unknown".to_string()),
+            architecture: std::env::consts::ARCH.to_string(),
+            logical_cores,
+            base_mhz: cpuid::base_frequency_mhz(),
+            caches,
+            flags: cpuid::feature_flags(),
+        })
+    }
+
+    /// Read the model from the `hw.model` sysctl, which every BSD provides.
+    ///
+    /// # Returns
+    ///
+    /// Returns the model string, or `None` if `sysctl` is missing or the key unknown.
+    fn sysctl_model() -> Option<String> {
+        let output = Command::new("sysctl").args(["-n", "hw.model"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let model = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!model.is_empty()).then_some(model)
+    }
+
+    /// Get the formatted information lines, excluding flags.
+    fn get_info_lines(&self, opts: &DisplayOptions) -> Vec<String> {
+        let mut lines = vec![
+            format!("Name: {}", self.model),
+            format!("Architecture: {}", self.architecture),
+            format!("Vendor: {}", self.vendor),
+        ];
+        if let Some(mhz) = self.base_mhz {
+            lines.push(format!("Base Frequency: {:.3} GHz", mhz as f32 / 1000.0));
+        }
+        lines.push(format!("Cores: {} threads", self.logical_cores));
+
+        if opts.compact_cache {
+            let level_size = |level: u32| {
+                let sizes: Vec<u64> = self.caches.iter().filter(|c| c.level == level).map(|c| c.size_kb as u64).collect();
+                (!sizes.is_empty()).then(|| sizes.iter().sum())
+            };
+            lines.extend(display::compact_cache_line(&[
+                ("L1", level_size(1)),
+                ("L2", level_size(2)),
+                ("L3", level_size(3)),
+            ]));
+        } else {
+            for cache in &self.caches {
+                lines.push(format!("{} Size: {}", cache.name(), display::format_cache_size(cache.size_kb as u64)));
+            }
+        }
+
+        if opts.show_cache_details() {
+            lines.extend(display::cache_detail_lines(&self.caches));
+        }
+        lines.push(format!("Note: limited support on {}", self.os));
+        display::relabel(&mut lines, opts);
+        lines
+    }
+
+    /// Get the wrapped flag lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options (grouped flags, flag limit)
+    /// * `wrap_width` - Width available to the info column
+    fn flag_lines(&self, opts: &DisplayOptions, wrap_width: usize) -> Vec<String> {
+        if self.flags.is_empty() {
+            return Vec::new();
+        }
+        display::flag_lines(&self.flags, " ", wrap_width, opts)
+    }
+}
+
+impl CpuInfo for GenericCpuInfo {
+    fn caches(&self) -> Vec<Cache> {
+        self.caches.clone()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn force_architecture(&mut self, architecture: &str) {
+        self.architecture = architecture.to_string();
+    }
+
+    fn flags(&self) -> &str {
+        &self.flags
+    }
+
+    fn max_frequency_ghz(&self) -> Option<f32> {
+        self.base_mhz.map(|mhz| mhz as f32 / 1000.0)
+    }
+
+    fn fingerprint_fields(&self) -> Vec<(&'static str, String)> {
+        let cache_kb = |name: &str| cpu::canonical_size(self.caches.iter().find(|c| c.name() == name).map(|c| c.size_kb));
+        vec![
+            ("vendor", self.vendor.clone()),
+            ("model", self.model.clone()),
+            ("architecture", self.architecture.clone()),
+            ("logical_cores", self.logical_cores.to_string()),
+            ("l1d_kb", cache_kb("L1d")),
+            ("l1i_kb", cache_kb("L1i")),
+            ("l2_kb", cache_kb("L2")),
+            ("l3_kb", cache_kb("L3")),
+            ("flags", cpu::canonical_flags(&self.flags)),
+        ]
+    }
+
+    fn to_json(&self) -> JsonValue {
+        json::CommonFields {
+            model: &self.model,
+            vendor: &self.vendor,
+            architecture: Some(&self.architecture),
+            byte_order: Some(cpu::byte_order()),
+            physical_cores: None,
+            logical_cores: self.logical_cores,
+            max_frequency_ghz: self.max_frequency_ghz(),
+            l1d_kb: json::cache_total_kb(&self.caches, "L1d"),
+            l1i_kb: json::cache_total_kb(&self.caches, "L1i"),
+            l2_kb: json::cache_total_kb(&self.caches, "L2"),
+            l3_kb: json::cache_total_kb(&self.caches, "L3"),
+            caches: &self.caches,
+            flags: &self.flags,
+        }.into_json(vec![
+            ("base_frequency_mhz".to_string(), JsonValue::optional(self.base_mhz)),
+            ("limited_support".to_string(), self.os.into()),
+        ])
+    }
+
+    /// Display CPU information with logo (side-by-side layout).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Layout options
+    fn display_info_with_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let (logo_lines, logo_colors) = get_logo_for_vendor(logo_override.unwrap_or(&self.vendor), opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+            .map_or_else(Default::default, |l| (l.lines, l.colors));
+
+        let wrap_width = opts.resolve_width(display::DEFAULT_WIDTH).saturating_sub(display::info_column_offset(&logo_lines, opts));
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, wrap_width));
+
+        display::print_side_by_side(&logo_lines, &logo_colors, &info_lines, opts);
+    }
+
+    /// Display CPU information without any logo.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Layout options
+    fn display_info_no_logo(&self, opts: &DisplayOptions) {
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, opts.resolve_width(display::DEFAULT_NO_LOGO_WIDTH)));
+        display::print_lines(&info_lines, opts);
+    }
+
+    /// Display CPU information as a self-contained HTML snippet (`--format html`).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Display options (colors, logo)
+    fn display_info_html(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let logo_lines = if opts.no_logo {
+            Vec::new()
+        } else {
+            get_logo_for_vendor(logo_override.unwrap_or(&self.vendor), opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+                .map(|l| l.lines)
+                .unwrap_or_default()
+        };
+
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, usize::MAX));
+        display::print_html(&logo_lines, &info_lines);
+    }
+}
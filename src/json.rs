@@ -0,0 +1,631 @@
+//! Minimal JSON serializer for rcpufetch (`--format json`).
+//!
+//! rcpufetch has no dependencies, so instead of serde this module provides a small
+//! JSON value type that the OS modules build their output from, a serializer with
+//! a compact (single-line) and a pretty (two-space indented) mode, and a parser used
+//! to load snapshots back in (`--from-json`).
+
+use crate::cpu::{Cache, CacheType};
+
+/// Version of the JSON output schema, written as `schema_version`.
+///
+/// Bump it when a field is removed or changes meaning; adding fields is compatible.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// Object members, kept in insertion order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Build a number from an `f32`, keeping its shortest decimal representation
+    /// (so 2.1f32 serializes as `2.1` rather than `2.0999999046325684`).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Number to convert
+    pub fn from_f32(value: f32) -> Self {
+        JsonValue::Number(value.to_string().parse().unwrap_or(f64::NAN))
+    }
+
+    /// Build a string value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - String to convert
+    pub fn string(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+
+    /// Build a value from an optional one, using `null` for `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Optional value
+    pub fn optional<T: Into<JsonValue>>(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+
+    /// Look up an object member.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Member name
+    ///
+    /// # Returns
+    ///
+    /// Returns the member's value, or `None` if this isn't an object or has no such
+    /// member.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a string, if it is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a number, if it is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a non-negative integer that fits in a `u32`, if it is one.
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_f64()
+            .filter(|n| n.fract() == 0.0 && *n >= 0.0 && *n <= u32::MAX as f64)
+            .map(|n| n as u32)
+    }
+
+    /// Get the value as a boolean, if it is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Get the elements of an array, or an empty slice if this isn't an array.
+    pub fn as_array(&self) -> &[JsonValue] {
+        match self {
+            JsonValue::Array(items) => items,
+            _ => &[],
+        }
+    }
+}
+
+impl From<u32> for JsonValue {
+    fn from(value: u32) -> Self {
+        JsonValue::Number(value as f64)
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Bool(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::string(value)
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+impl From<&String> for JsonValue {
+    fn from(value: &String) -> Self {
+        JsonValue::string(value)
+    }
+}
+
+/// Serialize a value to JSON text.
+///
+/// # Arguments
+///
+/// * `value` - Value to serialize
+/// * `pretty` - Indent with two spaces and put each member on its own line
+///   (`--pretty`); otherwise the output is a single line
+pub fn to_string(value: &JsonValue, pretty: bool) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, pretty, 0);
+    out
+}
+
+/// Append a value to `out`.
+///
+/// # Arguments
+///
+/// * `out` - Output buffer
+/// * `value` - Value to write
+/// * `pretty` - Whether to indent
+/// * `depth` - Current nesting depth, for indentation
+fn write_value(out: &mut String, value: &JsonValue, pretty: bool, depth: usize) {
+    match value {
+        JsonValue::Null => out.push_str("null"),
+        JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        // JSON has no NaN or infinity
+        JsonValue::Number(n) if !n.is_finite() => out.push_str("null"),
+        JsonValue::Number(n) => out.push_str(&n.to_string()),
+        JsonValue::String(s) => write_string(out, s),
+        JsonValue::Array(items) => {
+            write_container(out, '[', ']', items.len(), pretty, depth, |out, i| {
+                write_value(out, &items[i], pretty, depth + 1);
+            });
+        }
+        JsonValue::Object(members) => {
+            write_container(out, '{', '}', members.len(), pretty, depth, |out, i| {
+                let (key, value) = &members[i];
+                write_string(out, key);
+                out.push_str(if pretty { ": " } else { ":" });
+                write_value(out, value, pretty, depth + 1);
+            });
+        }
+    }
+}
+
+/// Append an array or object, placing each element on its own line when pretty.
+///
+/// # Arguments
+///
+/// * `out` - Output buffer
+/// * `open`, `close` - Delimiters
+/// * `len` - Number of elements
+/// * `pretty` - Whether to indent
+/// * `depth` - Nesting depth of the container itself
+/// * `write_element` - Writes element `i`
+fn write_container<F>(out: &mut String, open: char, close: char, len: usize, pretty: bool, depth: usize, mut write_element: F)
+where
+    F: FnMut(&mut String, usize),
+{
+    out.push(open);
+    for i in 0..len {
+        if i > 0 {
+            out.push(',');
+        }
+        if pretty {
+            out.push('\n');
+            out.push_str(&"  ".repeat(depth + 1));
+        }
+        write_element(out, i);
+    }
+    if pretty && len > 0 {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    }
+    out.push(close);
+}
+
+/// Append a quoted, escaped JSON string.
+///
+/// # Arguments
+///
+/// * `out` - Output buffer
+/// * `s` - String to write
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Parse JSON text.
+///
+/// Accepts standard JSON (RFC 8259). Numbers are read as `f64`, and object members
+/// keep their order; for duplicate keys `JsonValue::get` returns the first.
+///
+/// # Arguments
+///
+/// * `text` - JSON document
+///
+/// # Returns
+///
+/// Returns the parsed value, or `Err` describing the first syntax error and its
+/// byte offset.
+pub fn parse(text: &str) -> Result<JsonValue, String> {
+    let mut parser = Parser { bytes: text.as_bytes(), pos: 0 };
+    let value = parser.parse_value(0)?;
+    parser.skip_whitespace();
+    if parser.pos < parser.bytes.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+/// Maximum nesting depth accepted by the parser, to bound recursion.
+const MAX_DEPTH: usize = 128;
+
+/// Recursive-descent JSON parser over the bytes of the input.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Build an error message pointing at the current position.
+    fn error(&self, message: &str) -> String {
+        format!("invalid JSON at byte {}: {}", self.pos, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `literal` if the input continues with it.
+    fn eat(&mut self, literal: &str) -> bool {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.parse_object(depth),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) if self.eat("null") => Ok(JsonValue::Null),
+            Some(_) if self.eat("true") => Ok(JsonValue::Bool(true)),
+            Some(_) if self.eat("false") => Ok(JsonValue::Bool(false)),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonValue, String> {
+        self.pos += 1; // '{'
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.bytes.get(self.pos) != Some(&b'"') {
+                return Err(self.error("expected a member name"));
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.error("expected ':'"));
+            }
+            members.push((key, self.parse_value(depth + 1)?));
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(JsonValue::Object(members));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue, String> {
+        self.pos += 1; // '['
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(JsonValue::Array(items));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.pos;
+        self.eat("-");
+        let digits = |parser: &mut Self| {
+            let begin = parser.pos;
+            while parser.bytes.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+                parser.pos += 1;
+            }
+            parser.pos > begin
+        };
+        if !digits(self) {
+            return Err(self.error("expected digits"));
+        }
+        if self.eat(".") && !digits(self) {
+            return Err(self.error("expected digits after '.'"));
+        }
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("expected exponent digits"));
+            }
+        }
+        // The slice is ASCII, so this can't fail to be valid UTF-8
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or_default();
+        text.parse().map(JsonValue::Number).map_err(|_| self.error("invalid number"))
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.pos += 1; // opening quote
+        let mut bytes = Vec::new();
+        loop {
+            match self.bytes.get(self.pos) {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    return String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"));
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.bytes.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    self.pos += 1;
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                Some(&byte) if byte < 0x20 => return Err(self.error("control character in string")),
+                Some(&byte) => {
+                    bytes.push(byte);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Parse the hex digits of a `\uXXXX` escape (with `pos` on the `u`), combining
+    /// surrogate pairs. Leaves `pos` on the last hex digit.
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            // A high surrogate must be followed by an escaped low surrogate
+            self.pos += 1;
+            if !self.eat("\\") || self.bytes.get(self.pos) != Some(&b'u') {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    /// Read four hex digits following `pos`, leaving `pos` on the last one.
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let hex = self.bytes.get(self.pos + 1..self.pos + 5)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(hex)
+    }
+}
+
+/// Convert the cache hierarchy to a JSON array.
+///
+/// # Arguments
+///
+/// * `caches` - Caches as returned by `CpuInfo::caches`
+pub fn caches_to_json(caches: &[Cache]) -> JsonValue {
+    JsonValue::Array(caches.iter()
+        .map(|cache| {
+            let cache_type = match cache.cache_type {
+                CacheType::Data => "data",
+                CacheType::Instruction => "instruction",
+                CacheType::Unified => "unified",
+            };
+            JsonValue::Object(vec![
+                ("name".to_string(), JsonValue::string(&cache.name())),
+                ("level".to_string(), cache.level.into()),
+                ("type".to_string(), cache_type.into()),
+                ("size_kb".to_string(), cache.size_kb.into()),
+                ("associativity".to_string(), JsonValue::optional(cache.associativity)),
+                ("line_size".to_string(), JsonValue::optional(cache.line_size)),
+                ("shared_cpus".to_string(), JsonValue::optional(cache.shared_cpus)),
+                ("instances".to_string(), JsonValue::optional(cache.instances)),
+                ("inclusive".to_string(), JsonValue::optional(cache.inclusive)),
+            ])
+        })
+        .collect())
+}
+
+/// Total size of a cache across all instances, for the `l*_kb` fields.
+///
+/// # Arguments
+///
+/// * `caches` - Cache hierarchy
+/// * `name` - Cache name as returned by `Cache::name` (e.g. "L1d")
+///
+/// # Returns
+///
+/// Returns the size in KB, or `None` when there is no cache of that name (an L1
+/// reported as unified has neither "L1d" nor "L1i").
+pub fn cache_total_kb(caches: &[Cache], name: &str) -> Option<u64> {
+    caches.iter().find(|cache| cache.name() == name).map(Cache::total_kb)
+}
+
+/// The fields shared by the JSON output of every platform.
+///
+/// Each OS module fills this in and appends its own fields, so scripts can rely on
+/// these keys whichever OS produced the output.
+pub struct CommonFields<'a> {
+    pub model: &'a str,
+    pub vendor: &'a str,
+    pub architecture: Option<&'a str>,
+    pub byte_order: Option<&'a str>,
+    pub physical_cores: Option<u32>,
+    pub logical_cores: u32,
+    /// From `CpuInfo::max_frequency_ghz`
+    pub max_frequency_ghz: Option<f32>,
+    /// Total L1 data cache size across all instances, in KB
+    pub l1d_kb: Option<u64>,
+    /// Total L1 instruction cache size across all instances, in KB
+    pub l1i_kb: Option<u64>,
+    /// Total L2 cache size across all instances, in KB
+    pub l2_kb: Option<u64>,
+    /// Total L3 cache size across all instances, in KB
+    pub l3_kb: Option<u64>,
+    pub caches: &'a [Cache],
+    /// Raw flag string in Linux or macOS format
+    pub flags: &'a str,
+}
+
+impl CommonFields<'_> {
+    /// Build the JSON object: the common fields, then the platform's own.
+    ///
+    /// # Arguments
+    ///
+    /// * `extra` - Platform-specific fields, appended after the common ones
+    pub fn into_json(self, extra: Vec<(String, JsonValue)>) -> JsonValue {
+        let cache_kb = |kb: Option<u64>| kb.map_or(JsonValue::Null, |kb| JsonValue::Number(kb as f64));
+        let mut fields = vec![
+            ("schema_version".to_string(), SCHEMA_VERSION.into()),
+            ("model".to_string(), self.model.into()),
+            ("vendor".to_string(), self.vendor.into()),
+            ("architecture".to_string(), JsonValue::optional(self.architecture)),
+            ("byte_order".to_string(), JsonValue::optional(self.byte_order)),
+            ("physical_cores".to_string(), JsonValue::optional(self.physical_cores)),
+            ("logical_cores".to_string(), self.logical_cores.into()),
+            ("max_frequency_ghz".to_string(), self.max_frequency_ghz.map_or(JsonValue::Null, JsonValue::from_f32)),
+            ("l1d_kb".to_string(), cache_kb(self.l1d_kb)),
+            ("l1i_kb".to_string(), cache_kb(self.l1i_kb)),
+            ("l2_kb".to_string(), cache_kb(self.l2_kb)),
+            ("l3_kb".to_string(), cache_kb(self.l3_kb)),
+            ("caches".to_string(), caches_to_json(self.caches)),
+            ("flags".to_string(), flags_to_json(self.flags)),
+        ];
+        fields.extend(extra);
+        JsonValue::Object(fields)
+    }
+}
+
+/// Convert a flag string in Linux or macOS format to a JSON array of flag names.
+///
+/// # Arguments
+///
+/// * `flags` - Raw flag string
+pub fn flags_to_json(flags: &str) -> JsonValue {
+    JsonValue::Array(crate::flags::split_flags(flags).into_iter().map(JsonValue::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_compact_and_pretty() {
+        let value = JsonValue::Object(vec![
+            ("schema_version".to_string(), JsonValue::Number(2.0)),
+            ("model".to_string(), JsonValue::string("Intel(R) Core(TM) i7 \"K\" \\ \u{e9}\n")),
+            ("max_frequency_ghz".to_string(), JsonValue::from_f32(4.7)),
+            ("hybrid".to_string(), JsonValue::Bool(false)),
+            ("serial".to_string(), JsonValue::Null),
+            ("flags".to_string(), JsonValue::Array(vec![JsonValue::string("sse"), JsonValue::string("avx2")])),
+            ("caches".to_string(), JsonValue::Array(vec![
+                JsonValue::Object(vec![("level".to_string(), JsonValue::Number(1.0))]),
+                JsonValue::Object(Vec::new()),
+            ])),
+            ("empty".to_string(), JsonValue::Array(Vec::new())),
+        ]);
+        assert_eq!(parse(&to_string(&value, false)).unwrap(), value);
+        assert_eq!(parse(&to_string(&value, true)).unwrap(), value);
+    }
+
+    #[test]
+    fn common_fields_have_the_same_keys_on_every_platform() {
+        let cache = |level, cache_type, size_kb, instances| Cache {
+            level,
+            cache_type,
+            size_kb,
+            associativity: None,
+            line_size: None,
+            shared_cpus: None,
+            instances,
+            inclusive: None,
+        };
+        let caches = [cache(1, CacheType::Data, 48, Some(8)), cache(2, CacheType::Unified, 1280, None)];
+        let value = CommonFields {
+            model: "Test CPU",
+            vendor: "GenuineIntel",
+            architecture: None,
+            byte_order: None,
+            physical_cores: None,
+            logical_cores: 8,
+            max_frequency_ghz: Some(4.7),
+            l1d_kb: cache_total_kb(&caches, "L1d"),
+            l1i_kb: cache_total_kb(&caches, "L1i"),
+            l2_kb: cache_total_kb(&caches, "L2"),
+            l3_kb: cache_total_kb(&caches, "L3"),
+            caches: &caches,
+            flags: "sse avx2",
+        }.into_json(vec![("limited_support".to_string(), "freebsd".into())]);
+
+        let JsonValue::Object(fields) = &value else { panic!("not an object") };
+        let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, [
+            "schema_version", "model", "vendor", "architecture", "byte_order", "physical_cores", "logical_cores",
+            "max_frequency_ghz", "l1d_kb", "l1i_kb", "l2_kb", "l3_kb", "caches", "flags", "limited_support",
+        ]);
+
+        assert_eq!(value.get("l1d_kb"), Some(&JsonValue::Number(384.0)));
+        assert_eq!(value.get("l1i_kb"), Some(&JsonValue::Null));
+        assert_eq!(value.get("l2_kb"), Some(&JsonValue::Number(1280.0)));
+        assert_eq!(value.get("max_frequency_ghz"), Some(&JsonValue::from_f32(4.7)));
+        assert_eq!(value.get("architecture"), Some(&JsonValue::Null));
+    }
+}
@@ -1,6 +1,20 @@
 use std::fs;
 use std::process::Command;
-use crate::art::logos::get_logo_lines_for_vendor;
+use std::thread;
+use std::time::Duration;
+use crate::art::logos::{get_logo_for_vendor, Logo};
+use crate::cla::{FreqMode, GroupBy};
+use crate::cpu::{self, Cache, CacheType, CoreKind, CpuInfo};
+use crate::json::{self, JsonValue};
+use crate::cpuid;
+use crate::uarch::{self, PackageLayout, Uarch};
+use crate::virt;
+use crate::flags;
+use super::smbios;
+use crate::display::{self, DisplayOptions};
+
+/// Cache sizes as `(L1d, L1i, L2, L3)`, each `Option<(per_core_kb, total_kb)>`.
+type CacheSizes = (Option<(u32, u64)>, Option<(u32, u64)>, Option<(u32, u64)>, Option<(u32, u64)>);
 
 /// Struct representing parsed Linux CPU information.
 ///
@@ -10,8 +24,10 @@ use crate::art::logos::get_logo_lines_for_vendor;
 pub struct LinuxCpuInfo {
     /// CPU model name (e.g., "AMD Ryzen 5 9600X 6-Core Processor")
     model: String,
-    /// CPU vendor ID (e.g., "AuthenticAMD", "GenuineIntel")
+    /// CPU vendor ID from /proc/cpuinfo (e.g., "AuthenticAMD", "GenuineIntel")
     vendor: String,
+    /// CPU vendor ID read directly from CPUID leaf 0 (x86 only)
+    cpuid_vendor: Option<String>,
     /// CPU architecture (e.g., "x86_64")
     architecture: String,
     /// CPU byte order (e.g., "Little Endian")
@@ -22,16 +38,52 @@ pub struct LinuxCpuInfo {
     physical_cores: u32,
     /// Number of logical CPU cores (threads)
     logical_cores: u32,
+    /// Logical CPUs configured and online according to `sysconf` (if available)
+    cpu_counts: Option<(u32, u32)>, // (configured, online)
+    /// Hardware threads per core as `(threads, cores)`, most threads first
+    threads_per_core: Vec<(u32, u32)>,
     /// Maximum CPU frequency in GHz (if available)
     max_mhz: Option<f32>,
     /// L1 data cache size (per core, total) in KB
-    l1d_size: Option<(u32, u32)>, // (per core, total)
+    l1d_size: Option<(u32, u64)>, // (per core, total)
     /// L1 instruction cache size (per core, total) in KB
-    l1i_size: Option<(u32, u32)>,
+    l1i_size: Option<(u32, u64)>,
     /// L2 cache size (per core, total) in KB
-    l2_size: Option<(u32, u32)>,
+    l2_size: Option<(u32, u64)>,
     /// L3 cache size (largest, total) in KB
-    l3_size: Option<(u32, u32)>,
+    l3_size: Option<(u32, u64)>,
+    /// Detected microarchitecture (if known)
+    uarch: Option<&'static Uarch>,
+    /// Hypervisor name when running in a virtual machine (e.g., "KVM")
+    hypervisor: Option<String>,
+    /// Container type when running inside a container (e.g., "Docker container")
+    container: Option<String>,
+    /// CPU time quota of the cgroup in CPUs (e.g., 2.5), if one is set
+    cpu_quota: Option<f64>,
+    /// x86 translation layer running rcpufetch (e.g., "FEX-Emu (native: aarch64)")
+    translation_layer: Option<String>,
+    /// SMBIOS socket designation (e.g., "CPU 0"), if SMBIOS was consulted
+    socket: Option<String>,
+    /// Serial number from /proc/cpuinfo (ARM boards such as the Raspberry Pi)
+    serial: Option<String>,
+    /// Whether processor blocks in /proc/cpuinfo report different flag sets
+    heterogeneous_isa: bool,
+    /// Achievable and rated base frequency in GHz when the CPU is capped far below spec
+    below_spec: Option<(f32, f32)>,
+    /// BogoMIPS from /proc/cpuinfo (if reported)
+    bogomips: Option<f32>,
+    /// Virtual address width from the "address sizes" field of /proc/cpuinfo (x86)
+    virtual_address_bits: Option<u32>,
+    /// Line size flushed by CLFLUSH in bytes, from "clflush size" in /proc/cpuinfo (x86)
+    clflush_size: Option<u32>,
+    /// L2 layout per core type on hybrid chips (e.g., "8× 2MB (P) + 2× 4MB shared (E-cluster)")
+    hybrid_l2: Option<String>,
+    /// NUMA nodes and the physical packages they belong to (if sysfs exposes them)
+    numa: Option<NumaTopology>,
+    /// cpufreq policies (independent frequency domains), ordered by their first CPU
+    frequency_domains: Vec<FrequencyDomain>,
+    /// Where each displayed value came from (`--show-source`)
+    sources: FieldSources,
 }
 
 impl LinuxCpuInfo {
@@ -53,47 +105,121 @@ impl LinuxCpuInfo {
     /// # Errors
     ///
     /// This function will return an error if:
-    /// - /proc/cpuinfo cannot be read
-    /// - uname command fails to execute
     /// - Critical CPU information cannot be parsed
+    ///
+    /// A missing /proc/cpuinfo (GNU/Hurd, minimal containers, other /proc variants) is
+    /// not an error: model, vendor, and topology fall back to CPUID and sysfs, and any
+    /// field that still cannot be determined is reported as unknown.
     pub fn new() -> Result<Self, String> {
-        // Read /proc/cpuinfo directly
-        let cpuinfo_content = fs::read_to_string("/proc/cpuinfo")
-        .map_err(|e| format!("Failed to read /proc/cpuinfo: {}", e))?;
+        // Read /proc/cpuinfo directly; an empty string triggers the fallbacks below
+        let cpuinfo_content = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
 
-        // Get architecture using uname
-        let uname_output = Command::new("uname")
-        .args(["-m"])
-        .output()
-        .map_err(|e| format!("Failed to get architecture: {}", e))?;
-        let architecture = String::from_utf8_lossy(&uname_output.stdout).trim().to_string();
+        // Get architecture using uname (fallback to the compile-time target architecture)
+        let uname_architecture = Command::new("uname")
+            .args(["-m"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|arch| !arch.is_empty());
 
-        // Parse /proc/cpuinfo
-        let parsed_info = Self::parse_cpuinfo(&cpuinfo_content)?;
+        // Parse /proc/cpuinfo, then fill in anything it didn't provide
+        let mut parsed_info = Self::parse_cpuinfo(&cpuinfo_content)?;
+        Self::apply_fallbacks(&mut parsed_info);
+
+        parsed_info.sources.architecture = if uname_architecture.is_some() { "uname -m" } else { "compile-time target" };
+        let architecture = uname_architecture.unwrap_or_else(|| std::env::consts::ARCH.to_string());
 
         // Get byte order information
-        let byte_order = Self::get_byte_order();
+        let byte_order = cpu::byte_order().to_string();
+
+        // Get configured vs online logical CPU counts
+        let cpu_counts = Self::get_cpu_counts();
+
+        // Get the exact SMT width of each core from the sysfs topology
+        let threads_per_core = Self::get_threads_per_core();
+        if !threads_per_core.is_empty() {
+            parsed_info.sources.threads_per_core = "sysfs thread_siblings_list";
+        }
 
         // Get maximum frequency
-        let max_mhz = Self::get_max_frequency().or(parsed_info.max_mhz);
+        let cpufreq_max = Self::get_max_frequency();
+        if cpufreq_max.is_some() {
+            parsed_info.sources.max_frequency = "cpufreq scaling_max_freq";
+        }
+        let max_mhz = cpufreq_max.or(parsed_info.max_mhz);
+
+        // CPUID is authoritative for the vendor; /proc/cpuinfo may be altered in VMs
+        let cpuid_vendor = Self::cpuid_vendor();
+        if cpuid_vendor.is_some() {
+            parsed_info.sources.vendor = "CPUID leaf 0";
+        }
+        let vendor_id = cpuid_vendor.as_deref().unwrap_or(&parsed_info.vendor);
+
+        // Look up the microarchitecture from the family/model signature
+        let uarch = match (parsed_info.family, parsed_info.cpu_model) {
+            (Some(family), Some(model)) => uarch::detect(vendor_id, family, model),
+            _ => None,
+        };
+
+        // Detect whether we're running under a hypervisor
+        let hypervisor = virt::detect_hypervisor(&parsed_info.flags);
+        if hypervisor.is_some() {
+            parsed_info.sources.hypervisor = if cpuid::hypervisor_vendor().is_some() {
+                "CPUID leaf 0x40000000"
+            } else {
+                "hypervisor CPU flag"
+            };
+        }
+        let container = virt::detect_container();
+        let translation_layer = virt::detect_translation_layer();
+        // CPUID leaf 0x16 names the exact base; AMD doesn't implement it, so fall back to
+        // the lowest base of any SKU with this signature
+        let rated_base = cpuid::base_frequency_mhz()
+            .or(uarch.and_then(|u| u.min_base_mhz))
+            .map(|mhz| mhz as f32 / 1000.0);
+        let below_spec = Self::detect_below_spec(Self::highest_cpufreq("cpuinfo_max_freq"), rated_base, hypervisor.is_some());
 
         // Get cache information from sysfs (fallback to /proc/cpuinfo values)
-        let (l1d_size, l1i_size, l2_size, l3_size) = Self::get_cache_info()
+        let sysfs_caches = Self::get_cache_info();
+        if sysfs_caches.is_some() {
+            parsed_info.sources.caches = "sysfs cache/index*";
+        }
+        let (l1d_size, l1i_size, l2_size, l3_size) = sysfs_caches
         .unwrap_or((parsed_info.l1d_size, parsed_info.l1i_size, parsed_info.l2_size, parsed_info.l3_size));
 
         Ok(LinuxCpuInfo {
             model: parsed_info.model,
             vendor: parsed_info.vendor,
+            cpuid_vendor,
             architecture,
             byte_order,
             flags: parsed_info.flags,
             physical_cores: parsed_info.physical_cores,
             logical_cores: parsed_info.logical_cores,
+            cpu_counts,
+            threads_per_core,
             max_mhz,
             l1d_size,
             l1i_size,
             l2_size,
             l3_size,
+            uarch,
+            hypervisor,
+            container,
+            cpu_quota: virt::cpu_quota(),
+            translation_layer,
+            socket: parsed_info.socket,
+            serial: parsed_info.serial,
+            heterogeneous_isa: parsed_info.heterogeneous_isa,
+            below_spec,
+            bogomips: parsed_info.bogomips,
+            virtual_address_bits: parsed_info.virtual_address_bits,
+            clflush_size: parsed_info.clflush_size,
+            hybrid_l2: Self::get_hybrid_l2(),
+            numa: Self::get_numa_topology(),
+            frequency_domains: Self::get_frequency_domains(),
+            sources: parsed_info.sources,
         })
     }
 
@@ -114,9 +240,22 @@ impl LinuxCpuInfo {
     fn parse_cpuinfo(content: &str) -> Result<ParsedCpuInfo, String> {
         let mut model = String::new();
         let mut vendor = String::new();
-        let mut flags = String::new();
+        let mut flags: Vec<&str> = Vec::new();
         let mut cache_size = None;
         let mut max_mhz = None;
+        let mut family = None;
+        let mut cpu_model = None;
+        let mut serial = None;
+        let mut bogomips = None;
+        let mut virtual_address_bits = None;
+        let mut clflush_size = None;
+
+        // Flag set of the first block, compared against every later block; the
+        // displayed flags are the union across all blocks so flags present only on
+        // some core types are not lost
+        let mut first_flag_set: Option<std::collections::BTreeSet<&str>> = None;
+        let mut seen_flags = std::collections::HashSet::new();
+        let mut heterogeneous_isa = false;
 
         // Track unique physical IDs and core IDs for accurate counting
         let mut physical_ids = std::collections::HashSet::new();
@@ -144,27 +283,25 @@ impl LinuxCpuInfo {
                     let value = value.trim();
 
                     match key {
-                        "model name" => {
-                            if model.is_empty() {
-                                model = value.to_string();
-                            }
+                        "model name" if model.is_empty() => {
+                            model = value.to_string();
                         },
-                        "vendor_id" => {
-                            if vendor.is_empty() {
-                                vendor = value.to_string();
-                            }
+                        "vendor_id" if vendor.is_empty() => {
+                            vendor = value.to_string();
                         },
-                        "flags" => {
-                            if flags.is_empty() {
-                                flags = value.to_string();
+                        // x86 reports "flags", ARM reports "Features"
+                        "flags" | "Features" => {
+                            let flag_set = value.split_whitespace().collect();
+                            match &first_flag_set {
+                                Some(first) => heterogeneous_isa |= *first != flag_set,
+                                None => first_flag_set = Some(flag_set),
                             }
+                            flags.extend(value.split_whitespace().filter(|flag| seen_flags.insert(*flag)));
                         },
-                        "cache size" => {
-                            if cache_size.is_none() {
-                                // Parse cache size (e.g., "1024 KB" -> 1024)
-                                if let Some(size_str) = value.split_whitespace().next() {
-                                    cache_size = size_str.parse::<u32>().ok();
-                                }
+                        "cache size" if cache_size.is_none() => {
+                            // Parse cache size (e.g., "1024 KB" -> 1024)
+                            if let Some(size_str) = value.split_whitespace().next() {
+                                cache_size = size_str.parse::<u32>().ok().filter(|&kb| kb > 0);
                             }
                         },
                         "cpu MHz" => {
@@ -183,6 +320,29 @@ impl LinuxCpuInfo {
                                 current_core_id = Some(id);
                             }
                         },
+                        "cpu family" if family.is_none() => {
+                            family = value.parse::<u32>().ok();
+                        },
+                        "model" if cpu_model.is_none() => {
+                            cpu_model = value.parse::<u32>().ok();
+                        },
+                        // Unprogrammed serials read as all zeros
+                        "Serial" if serial.is_none() && !value.chars().all(|c| c == '0') => {
+                            serial = Some(value.to_string());
+                        },
+                        // x86 reports "bogomips", ARM reports "BogoMIPS"
+                        "bogomips" | "BogoMIPS" if bogomips.is_none() => {
+                            bogomips = value.parse::<f32>().ok();
+                        },
+                        // e.g. "46 bits physical, 48 bits virtual"
+                        "address sizes" if virtual_address_bits.is_none() => {
+                            virtual_address_bits = value.split(',')
+                                .find_map(|part| part.trim().strip_suffix("bits virtual"))
+                                .and_then(|bits| bits.trim().parse::<u32>().ok());
+                        },
+                        "clflush size" if clflush_size.is_none() => {
+                            clflush_size = value.parse::<u32>().ok();
+                        },
                         _ => {}
                     }
                 }
@@ -211,14 +371,29 @@ impl LinuxCpuInfo {
         // Convert max MHz to GHz
         let max_mhz = max_mhz.map(|mhz| mhz / 1000.0);
 
+        let sources = FieldSources {
+            model: if model.is_empty() { "" } else { "/proc/cpuinfo model name" },
+            vendor: if vendor.is_empty() { "" } else { "/proc/cpuinfo vendor_id" },
+            cores: if !core_ids.is_empty() {
+                "/proc/cpuinfo core id"
+            } else if !physical_ids.is_empty() {
+                "/proc/cpuinfo physical id"
+            } else {
+                "assumed"
+            },
+            max_frequency: if max_mhz.is_some() { "/proc/cpuinfo cpu MHz" } else { "" },
+            caches: if cache_size.is_some() { "/proc/cpuinfo cache size" } else { "" },
+            ..Default::default()
+        };
+
         // For cache sizes, we'll use the cache size from /proc/cpuinfo as L2 cache
         // and try to infer other cache levels (this is a limitation of /proc/cpuinfo)
-        let l2_size = cache_size.map(|size| (size, size * physical_cores));
+        let l2_size = cache_size.map(|size| (size, size as u64 * physical_cores as u64));
 
         Ok(ParsedCpuInfo {
             model,
             vendor,
-            flags,
+            flags: flags.join(" "),
             physical_cores,
             logical_cores,
             max_mhz,
@@ -226,419 +401,2497 @@ impl LinuxCpuInfo {
             l1i_size: None, // Not typically available in /proc/cpuinfo
             l2_size,
             l3_size: None, // Not typically available in /proc/cpuinfo
+            family,
+            cpu_model,
+            socket: None,
+            serial,
+            heterogeneous_isa,
+            bogomips,
+            virtual_address_bits,
+            clflush_size,
+            sources,
         })
     }
 
-    /// Determine the system's byte order.
+    /// Fill in fields that /proc/cpuinfo did not provide.
     ///
-    /// This function determines whether the system uses little-endian or big-endian
-    /// byte ordering by checking the native byte order of the current architecture.
+    /// Model, vendor, and family/model fall back to CPUID on x86, then model and vendor
+    /// fall back to SMBIOS processor information (common on ARM servers), and
+    /// core counts fall back to the sysfs CPU topology. Anything that is still missing
+    /// is reported as "Unknown" rather than left blank.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// Returns a string indicating the byte order: "Little Endian" or "Big Endian".
-    fn get_byte_order() -> String {
-        if cfg!(target_endian = "little") {
-            "Little Endian".to_string()
-        } else {
-            "Big Endian".to_string()
+    /// * `info` - Partially populated information parsed from /proc/cpuinfo
+    fn apply_fallbacks(info: &mut ParsedCpuInfo) {
+        if info.model.is_empty() {
+            info.model = cpuid::brand_string().unwrap_or_default();
+            info.sources.model = if info.model.is_empty() { "" } else { "CPUID brand string" };
+        }
+        if info.vendor.is_empty() {
+            info.vendor = cpuid::vendor().unwrap_or_default();
+            info.sources.vendor = if info.vendor.is_empty() { "" } else { "CPUID leaf 0" };
+        }
+        if info.model.is_empty() || info.vendor.is_empty() {
+            let processor = smbios::read_processor_info().unwrap_or_default();
+            if info.model.is_empty() {
+                if processor.version.is_some() {
+                    info.sources.model = "SMBIOS processor version";
+                }
+                info.model = processor.version.unwrap_or_else(|| "Unknown".to_string());
+            }
+            if info.vendor.is_empty() {
+                if processor.manufacturer.is_some() {
+                    info.sources.vendor = "SMBIOS processor manufacturer";
+                }
+                info.vendor = processor.manufacturer.unwrap_or_else(|| "Unknown".to_string());
+            }
+            if processor.socket.is_some() {
+                info.sources.socket = "SMBIOS processor socket designation";
+            }
+            info.socket = processor.socket;
+        }
+        if (info.family.is_none() || info.cpu_model.is_none())
+            && let Some((family, model, _)) = cpuid::signature() {
+            info.family = Some(family);
+            info.cpu_model = Some(model);
+        }
+        if info.logical_cores == 0
+            && let Some((physical, logical)) = Self::get_sysfs_topology() {
+            info.physical_cores = physical;
+            info.logical_cores = logical;
+            info.sources.cores = "sysfs topology";
         }
     }
 
-    /// Get maximum CPU frequency from cpufreq information.
+    /// Count physical and logical cores from the sysfs CPU topology.
     ///
-    /// This function attempts to read the maximum CPU frequency from the Linux
-    /// cpufreq subsystem by checking scaling_max_freq files for all CPU cores.
+    /// Each `/sys/devices/system/cpu/cpuN` directory is one logical CPU. Physical
+    /// cores are counted as unique `(physical_package_id, core_id)` pairs read from
+    /// each CPU's `topology` directory.
     ///
     /// # Returns
     ///
-    /// Returns `Some(f32)` with the maximum frequency in GHz if available,
-    /// or `None` if the information cannot be read.
-    fn get_max_frequency() -> Option<f32> {
-        // Try to read from cpufreq
-        if let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") {
-            let mut max_freq = 0u64;
+    /// Returns `Some((physical, logical))`, or `None` if no CPUs are listed in sysfs.
+    fn get_sysfs_topology() -> Option<(u32, u32)> {
+        let entries = fs::read_dir("/sys/devices/system/cpu").ok()?;
+        let mut logical = 0;
+        let mut cores = std::collections::HashSet::new();
 
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if name.starts_with("cpu") && name[3..].chars().all(|c| c.is_ascii_digit()) {
-                        let freq_path = path.join("cpufreq/scaling_max_freq");
-                        if let Ok(freq_str) = fs::read_to_string(&freq_path) {
-                            if let Ok(freq) = freq_str.trim().parse::<u64>() {
-                                max_freq = max_freq.max(freq);
-                            }
-                        }
-                    }
-                }
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            if !name.starts_with("cpu") || name.len() == 3 || !name[3..].chars().all(|c| c.is_ascii_digit()) {
+                continue;
             }
 
-            if max_freq > 0 {
-                // Convert from kHz to GHz
-                return Some((max_freq as f32) / 1_000_000.0);
+            logical += 1;
+            let read_id = |file: &str| {
+                fs::read_to_string(path.join("topology").join(file))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<i64>().ok())
+            };
+            if let (Some(package), Some(core)) = (read_id("physical_package_id"), read_id("core_id")) {
+                cores.insert((package, core));
             }
         }
 
-        None
+        if logical == 0 {
+            return None;
+        }
+        let physical = if cores.is_empty() { logical } else { cores.len() as u32 };
+        Some((physical, logical))
     }
 
-    /// Get detailed cache information from sysfs.
+    /// Count the hardware threads of each core from the sysfs topology.
     ///
-    /// This function reads cache information directly from the Linux sysfs filesystem
-    /// at `/sys/devices/system/cpu/cpu*/cache/index*/` to get accurate cache sizes
-    /// for all cache levels. It reads cache information from cpu0 only to avoid
-    /// double-counting, then calculates totals based on sharing characteristics:
-    /// - L1 and L2 caches are typically per-core, so multiply by physical core count
-    /// - L3 cache is typically shared across all cores
+    /// Every CPU lists itself and its SMT siblings in `topology/thread_siblings_list`,
+    /// so each distinct list is one core. This is exact even where cores differ in
+    /// SMT width (e.g. hybrid x86, or POWER with SMT8), unlike dividing the logical
+    /// count by the physical count.
     ///
     /// # Returns
     ///
-    /// Returns a tuple of optional cache sizes in the format:
-    /// `(L1d, L1i, L2, L3)` where each element is `Option<(per_core_kb, total_kb)>`
-    /// Only total cache sizes are reported for each level.
-    fn get_cache_info() -> Option<(Option<(u32, u32)>, Option<(u32, u32)>, Option<(u32, u32)>, Option<(u32, u32)>)> {
-        use std::collections::HashMap;
-        
-        let mut cache_sizes: HashMap<String, u32> = HashMap::new();
-        
-        // Read cache information from cpu0 only to avoid double-counting
-        let cpu0_cache_dir = std::path::Path::new("/sys/devices/system/cpu/cpu0/cache");
-        if let Ok(cache_entries) = fs::read_dir(cpu0_cache_dir) {
-            for cache_entry in cache_entries.flatten() {
-                let cache_path = cache_entry.path();
-                if let Some(index_name) = cache_path.file_name().and_then(|n| n.to_str()) {
-                    if index_name.starts_with("index") {
-                        // Read cache level, type, and size
-                        let level_path = cache_path.join("level");
-                        let type_path = cache_path.join("type");
-                        let size_path = cache_path.join("size");
-                        
-                        if let (Ok(level_str), Ok(type_str), Ok(size_str)) = (
-                            fs::read_to_string(&level_path),
-                            fs::read_to_string(&type_path),
-                            fs::read_to_string(&size_path)
-                        ) {
-                            let level = level_str.trim();
-                            let cache_type = type_str.trim();
-                            let size_str = size_str.trim();
-                            
-                            // Parse size (e.g., "32K" -> 32, "1024K" -> 1024)
-                            if let Some(size_kb) = Self::parse_cache_size(size_str) {
-                                let cache_key = format!("L{}_{}", level, cache_type);
-                                cache_sizes.insert(cache_key, size_kb);
-                            }
-                        }
-                    }
-                }
+    /// Returns `(threads, cores)` pairs sorted by thread count, most threads first,
+    /// or an empty list if the topology is unavailable.
+    fn get_threads_per_core() -> Vec<(u32, u32)> {
+        let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+            return Vec::new();
+        };
+        let mut sibling_lists = std::collections::HashSet::new();
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            if !name.starts_with("cpu") || name.len() == 3 || !name[3..].chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            if let Ok(list) = fs::read_to_string(path.join("topology/thread_siblings_list")) {
+                sibling_lists.insert(list.trim().to_string());
             }
         }
-        
-        // Get physical core count for calculating totals
-        let physical_cores = Self::get_physical_core_count().unwrap_or(1);
-        
-        // Calculate totals based on cache sharing characteristics
-        let l1d_total = cache_sizes.get("L1_Data")
-            .map(|&size| size * physical_cores); // L1 data is per-core
-        let l1i_total = cache_sizes.get("L1_Instruction") 
-            .map(|&size| size * physical_cores); // L1 instruction is per-core
-        let l2_total = cache_sizes.get("L2_Unified")
-            .map(|&size| size * physical_cores); // L2 is typically per-core
-        let l3_total = cache_sizes.get("L3_Unified")
-            .copied(); // L3 is typically shared across all cores
-        
-        Some((
-            l1d_total.map(|total| (0, total)), // Only report total, per-core not used
-            l1i_total.map(|total| (0, total)),
-            l2_total.map(|total| (0, total)),
-            l3_total.map(|total| (0, total)),
-        ))
+
+        let mut cores_by_threads = std::collections::BTreeMap::new();
+        for list in &sibling_lists {
+            if let Some(threads) = Self::count_cpu_list(list) {
+                *cores_by_threads.entry(threads).or_insert(0) += 1;
+            }
+        }
+        cores_by_threads.into_iter().rev().collect()
     }
 
-    /// Parse cache size string from sysfs.
+    /// Format the threads-per-core line.
     ///
-    /// This helper function parses cache size strings from sysfs files,
-    /// which can be in formats like "32K", "1024K", "32768K", etc.
+    /// # Returns
     ///
-    /// # Arguments
+    /// Returns "Threads per core: 2" when every core has the same number of threads,
+    /// a breakdown such as "Threads per core: 2 (8 cores), 1 (16 cores)" when it
+    /// varies, or `None` if the topology is unavailable.
+    fn format_threads_per_core(&self) -> Option<String> {
+        match self.threads_per_core.as_slice() {
+            [] => None,
+            [(threads, _)] => Some(format!("Threads per core: {}", threads)),
+            groups => {
+                let breakdown: Vec<String> = groups.iter()
+                    .map(|(threads, cores)| format!("{} ({} {})", threads, cores, if *cores == 1 { "core" } else { "cores" }))
+                    .collect();
+                Some(format!("Threads per core: {}", breakdown.join(", ")))
+            }
+        }
+    }
+
+    /// Get the configured and online logical CPU counts using `sysconf`.
     ///
-    /// * `size_str` - Cache size string from sysfs (e.g., "32K")
+    /// `_SC_NPROCESSORS_CONF` counts every CPU the kernel knows about, while
+    /// `_SC_NPROCESSORS_ONLN` only counts CPUs that are currently online. The two
+    /// differ when CPUs have been hot-unplugged or taken offline.
     ///
     /// # Returns
     ///
-    /// Returns the cache size in kilobytes, or `None` if parsing fails.
-    fn parse_cache_size(size_str: &str) -> Option<u32> {
-        if size_str.ends_with('K') {
-            size_str[..size_str.len() - 1].parse::<u32>().ok()
-        } else if size_str.ends_with("KB") {
-            size_str[..size_str.len() - 2].parse::<u32>().ok()
+    /// Returns `Some((configured, online))`, or `None` if `sysconf` fails.
+    #[cfg(target_os = "linux")]
+    fn get_cpu_counts() -> Option<(u32, u32)> {
+        use std::os::raw::{c_int, c_long};
+
+        // Values from <bits/confname.h>, shared by glibc and musl
+        const SC_NPROCESSORS_CONF: c_int = 83;
+        const SC_NPROCESSORS_ONLN: c_int = 84;
+
+        unsafe extern "C" {
+            fn sysconf(name: c_int) -> c_long;
+        }
+
+        // SAFETY: sysconf only reads system configuration and has no preconditions.
+        let configured = unsafe { sysconf(SC_NPROCESSORS_CONF) };
+        let online = unsafe { sysconf(SC_NPROCESSORS_ONLN) };
+        if configured > 0 && online > 0 {
+            Some((configured as u32, online as u32))
         } else {
-            // Try parsing as plain number (assume KB)
-            size_str.parse::<u32>().ok()
+            None
         }
     }
 
-    /// Print the CPU information with an optional logo override.
+    /// Get the configured and online logical CPU counts (unsupported on this OS).
+    #[cfg(not(target_os = "linux"))]
+    fn get_cpu_counts() -> Option<(u32, u32)> {
+        None
+    }
+
+    /// Get the size of a memory page using `sysconf`.
     ///
-    /// This function displays comprehensive CPU information in a formatted layout
-    /// alongside the specified vendor logo or the detected vendor logo if none is specified.
+    /// # Returns
     ///
-    /// # Arguments
+    /// Returns the page size in bytes, or `None` if `sysconf` fails.
+    #[cfg(target_os = "linux")]
+    fn get_page_size() -> Option<u64> {
+        use std::os::raw::{c_int, c_long};
+
+        // Value from <bits/confname.h>, shared by glibc and musl
+        const SC_PAGESIZE: c_int = 30;
+
+        unsafe extern "C" {
+            fn sysconf(name: c_int) -> c_long;
+        }
+
+        // SAFETY: sysconf only reads system configuration and has no preconditions.
+        let page_size = unsafe { sysconf(SC_PAGESIZE) };
+        (page_size > 0).then_some(page_size as u64)
+    }
+
+    /// Get the size of a memory page (unsupported on this OS).
+    #[cfg(not(target_os = "linux"))]
+    fn get_page_size() -> Option<u64> {
+        None
+    }
+
+    /// Describe the huge page sizes the hardware supports.
     ///
-    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
-    pub fn display_info_with_logo(&self, logo_override: Option<&str>) {
-        let vendor_to_use = logo_override.unwrap_or(&self.vendor);
-        
-        // Fallback to ARM logo for ARM32/ARM64 architectures when vendor is unknown or no logo available
-        let logo_lines = get_logo_lines_for_vendor(vendor_to_use)
-            .or_else(|| {
-                // Check if architecture is ARM-based and fallback to ARM logo
-                if (self.architecture.contains("arm") || self.architecture.contains("aarch64")) 
-                    && (vendor_to_use == "Unknown" || vendor_to_use.is_empty() || get_logo_lines_for_vendor(vendor_to_use).is_none()) {
-                    get_logo_lines_for_vendor("ARM")
-                } else {
-                    None
+    /// This is the hardware capability, not the hugepages the OS has configured. On
+    /// x86 it comes from the `pse` (2MB pages) and `pdpe1gb` (1GB pages) flags. On
+    /// ARM the block sizes depend on the translation granule, which is the kernel's
+    /// page size: 2MB and 1GB with 4KB pages, 32MB with 16KB pages, and 512MB with
+    /// 64KB pages.
+    ///
+    /// # Returns
+    ///
+    /// Returns a list such as "2MB (PSE), 1GB (PDPE1GB)", or `None` when it can't be
+    /// determined.
+    fn huge_page_sizes(&self) -> Option<String> {
+        let has_flag = |name: &str| self.flags.split_whitespace().any(|flag| flag == name);
+        let sizes = match self.architecture.as_str() {
+            "x86_64" | "i386" | "i486" | "i586" | "i686" => {
+                let mut sizes = Vec::new();
+                if has_flag("pse") {
+                    sizes.push("2MB (PSE)");
                 }
-            })
-            .unwrap_or_else(|| vec![]);
-        
-        let info_lines = vec![
-            format!("Name: {:<30}", self.model),
-                format!("Architecture: {:<30}", self.architecture),
-                    format!("Byte Order: {:<30}", self.byte_order),
-                        format!("Vendor: {:<30}", self.vendor),
-                            format!("Max Frequency: {:>7}", match self.max_mhz { Some(ghz) => format!("{:.3} GHz", ghz), None => "Unknown".to_string() }),
-                                format!("Cores: {:>2} cores ({} threads)", self.physical_cores, self.logical_cores),
-                                    format!("L1i Size: {}", match self.l1i_size { Some((_, total)) => Self::format_cache_size(total), None => "Unknown".to_string() }),
-                                        format!("L1d Size: {}", match self.l1d_size { Some((_, total)) => Self::format_cache_size(total), None => "Unknown".to_string() }),
-                                            format!("L1 Size: {}", match (self.l1i_size, self.l1d_size) {
-                                                (Some((_, l1i_total)), Some((_, l1d_total))) => Self::format_cache_size(l1i_total + l1d_total),
-                                                (Some((_, l1i_total)), None) => Self::format_cache_size(l1i_total),
-                                                (None, Some((_, l1d_total))) => Self::format_cache_size(l1d_total),
-                                                (None, None) => "Unknown".to_string()
-                                            }),
-                                                format!("L2 Size: {}", match self.l2_size { Some((_, total)) => Self::format_cache_size(total), None => "Unknown".to_string() }),
-                                                    format!("L3 Size: {}", match self.l3_size { Some((_, total)) => Self::format_cache_size(total), None => "Unknown".to_string() }),
-        ];
-
-        let logo_width = logo_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
-        let sep = "   ";
-        let left_margin = logo_width + sep.len();
-        let total_width = 100; // You can adjust this to your preferred terminal width
-        let flag_label = "Flags: ";
-        let indent = "       "; // 7 spaces
-        let wrap_width = total_width - left_margin;
-
-        // Wrap flags so that each line starts at the info column
-        let mut flag_lines = Vec::new();
-        let mut current_line = String::from(flag_label);
-        for word in self.flags.split_whitespace() {
-            if current_line.len() + word.len() + 1 > wrap_width {
-                flag_lines.push(current_line);
-                current_line = format!("{}{}", indent, word);
-            } else {
-                if current_line.trim_end().ends_with(":") {
-                    current_line.push_str(word);
-                } else {
-                    current_line.push(' ');
-                    current_line.push_str(word);
+                if has_flag("pdpe1gb") {
+                    sizes.push("1GB (PDPE1GB)");
                 }
+                sizes.join(", ")
             }
+            "aarch64" | "arm64" => match Self::get_page_size()? {
+                4096 => "2MB, 1GB (4KB granule)".to_string(),
+                16384 => "32MB (16KB granule)".to_string(),
+                65536 => "512MB (64KB granule)".to_string(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        (!sizes.is_empty()).then_some(sizes)
+    }
+
+    /// Describe how the memory slots and channels are populated.
+    ///
+    /// Slots come from SMBIOS Type 17, which lists empty slots too but is normally
+    /// readable by root only. Otherwise the DIMMs registered with EDAC are used;
+    /// EDAC only knows about installed modules, so the slot count and empty channels
+    /// are unknown there.
+    ///
+    /// # Returns
+    ///
+    /// Returns a string such as "2 of 4 slots (single-channel — unbalanced)", or
+    /// `None` if neither source lists any installed memory.
+    fn memory_population() -> Option<String> {
+        let slots = smbios::read_memory_devices();
+        if !slots.is_empty() {
+            return Self::format_memory_population(&slots, true);
         }
-        if !current_line.trim().is_empty() {
-            flag_lines.push(current_line);
-        }
+        Self::format_memory_population(&Self::edac_dimms(), false)
+    }
 
-        let info_len = info_lines.len();
-        let logo_len = logo_lines.len();
-        let max_lines = std::cmp::max(logo_len, info_len + flag_lines.len());
+    /// Read the installed DIMMs from the EDAC memory controllers in sysfs.
+    ///
+    /// Each `mc*/dimm*` directory has a `size` in MB and a `dimm_location` such as
+    /// "channel 1 slot 0" (or "csrow 0 channel 1" on older drivers).
+    fn edac_dimms() -> Vec<smbios::MemoryDevice> {
+        let Ok(controllers) = fs::read_dir("/sys/devices/system/edac/mc") else {
+            return Vec::new();
+        };
 
-        // Print logo and info side by side for overlapping lines
-        let mut info_idx = 0;
-        for i in 0..max_lines {
-            let logo = logo_lines.get(i).map(|s| s.as_str()).unwrap_or("");
-            let info = if info_idx < info_lines.len() {
-                let s = &info_lines[info_idx];
-                info_idx += 1;
-                s.as_str().to_string()
-            } else if !flag_lines.is_empty() {
-                let mut flag_line = flag_lines.remove(0);
-                // If there's no logo content on this line, remove the indent from flag lines
-                if logo.is_empty() && flag_line.starts_with(indent) {
-                    flag_line = flag_line[indent.len()..].to_string();
+        let mut dimms = Vec::new();
+        for controller in controllers.flatten() {
+            let controller_name = controller.file_name().to_string_lossy().to_string();
+            let Ok(entries) = fs::read_dir(controller.path()) else { continue };
+            for entry in entries.flatten() {
+                if !entry.file_name().to_string_lossy().starts_with("dimm") {
+                    continue;
                 }
-                flag_line
-            } else {
-                String::new()
-            };
-            println!("{:<width$}{}{}", logo, sep, info.as_str(), width=logo_width);
+                let read = |file: &str| fs::read_to_string(entry.path().join(file)).ok();
+                let Some(size_mb) = read("size").and_then(|size| size.trim().parse::<u64>().ok()) else {
+                    continue;
+                };
+                let channel = read("dimm_location").and_then(|location| {
+                    let mut words = location.split_whitespace();
+                    words.position(|word| word == "channel")?;
+                    Some(format!("{} channel {}", controller_name, words.next()?))
+                });
+                dimms.push(smbios::MemoryDevice { size_mb: Some(size_mb), channel });
+            }
         }
+        dimms
     }
 
-    /// Print the CPU information without any logo.
+    /// Format the slot and channel population of the memory.
     ///
-    /// This function displays comprehensive CPU information in a simple list format
-    /// without any vendor logo or side-by-side alignment.
-    pub fn display_info_no_logo(&self) {
-        let info_lines = self.get_info_lines();
-        
-        // Print CPU information without logo
-        for line in info_lines {
-            println!("{}", line);
+    /// Population is unbalanced when some channels are empty, or when the populated
+    /// channels differ in module count or capacity, since bandwidth then drops to
+    /// what the least populated channels provide. The channel mode is only shown when
+    /// every slot names its channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `slots` - Memory slots, including empty ones when `slots_known` is set
+    /// * `slots_known` - Whether `slots` lists every slot (SMBIOS) or only installed
+    ///   modules (EDAC)
+    fn format_memory_population(slots: &[smbios::MemoryDevice], slots_known: bool) -> Option<String> {
+        let installed: Vec<&smbios::MemoryDevice> = slots.iter().filter(|slot| slot.size_mb != Some(0)).collect();
+        if installed.is_empty() {
+            return None;
         }
-        
-        // Print flags with wrapping
-        print!("Flags: ");
-        let wrap_width = 80; // Standard terminal width
-        let mut current_line_len = 7; // "Flags: " length
-        let mut first_flag = true;
-        
-        for word in self.flags.split_whitespace() {
-            if !first_flag && current_line_len + word.len() + 1 > wrap_width {
-                println!();
-                print!("       {}", word); // 7 spaces to align with "Flags: "
-                current_line_len = 7 + word.len();
-            } else {
-                if first_flag {
-                    print!("{}", word);
-                    current_line_len += word.len();
-                    first_flag = false;
-                } else {
-                    print!(" {}", word);
-                    current_line_len += word.len() + 1;
+        let count = if slots_known {
+            format!("{} of {} slot{}", installed.len(), slots.len(), if slots.len() == 1 { "" } else { "s" })
+        } else {
+            format!("{} DIMM{}", installed.len(), if installed.len() == 1 { "" } else { "s" })
+        };
+
+        if slots.iter().any(|slot| slot.channel.is_none()) {
+            return Some(count);
+        }
+
+        // (channel, modules, total MB) for each populated channel
+        let mut channels: Vec<(&str, u32, Option<u64>)> = Vec::new();
+        for dimm in &installed {
+            let channel = dimm.channel.as_deref().unwrap_or_default();
+            match channels.iter_mut().find(|(name, _, _)| *name == channel) {
+                Some((_, modules, size)) => {
+                    *modules += 1;
+                    *size = size.zip(dimm.size_mb).map(|(a, b)| a + b);
                 }
+                None => channels.push((channel, 1, dimm.size_mb)),
             }
         }
-        println!(); // Final newline
+        let mut all_channels: Vec<&str> = slots.iter().filter_map(|slot| slot.channel.as_deref()).collect();
+        all_channels.sort_unstable();
+        all_channels.dedup();
+
+        let (_, modules, size) = channels[0];
+        let balanced = channels.len() == all_channels.len()
+            && channels.iter().all(|&(_, m, s)| m == modules && (s == size || s.is_none() || size.is_none()));
+        let mode = match channels.len() {
+            1 => "single-channel".to_string(),
+            2 => "dual-channel".to_string(),
+            3 => "triple-channel".to_string(),
+            4 => "quad-channel".to_string(),
+            n => format!("{}-channel", n),
+        };
+        Some(format!("{} ({}{})", count, mode, if balanced { "" } else { " — unbalanced" }))
     }
 
-    /// Get the formatted information lines for display.
+    /// Describe the virtual address width and the paging mode in use (x86).
     ///
-    /// This helper function generates the formatted CPU information lines
-    /// that are used by both logo and no-logo display methods.
-    fn get_info_lines(&self) -> Vec<String> {
-        vec![
-            format!("Name: {}", self.model),
-            format!("Architecture: {}", self.architecture),
-            format!("Byte Order: {}", self.byte_order),
-            format!("Vendor: {}", self.vendor),
-            format!("Max Frequency: {}", match self.max_mhz { 
-                Some(ghz) => format!("{:.3} GHz", ghz), 
-                None => "Unknown".to_string() 
-            }),
-            format!("Cores: {} cores ({} threads)", self.physical_cores, self.logical_cores),
-            format!("L1i Size: {}", match self.l1i_size { 
-                Some((_, total)) => Self::format_cache_size(total), 
-                None => "Unknown".to_string() 
-            }),
-            format!("L1d Size: {}", match self.l1d_size { 
-                Some((_, total)) => Self::format_cache_size(total), 
-                None => "Unknown".to_string() 
-            }),
-            format!("L1 Size: {}", match (self.l1i_size, self.l1d_size) {
-                (Some((_, l1i_total)), Some((_, l1d_total))) => Self::format_cache_size(l1i_total + l1d_total),
-                (Some((_, l1i_total)), None) => Self::format_cache_size(l1i_total),
-                (None, Some((_, l1d_total))) => Self::format_cache_size(l1d_total),
-                (None, None) => "Unknown".to_string()
-            }),
-            format!("L2 Size: {}", match self.l2_size { 
-                Some((_, total)) => Self::format_cache_size(total), 
-                None => "Unknown".to_string() 
-            }),
-            format!("L3 Size: {}", match self.l3_size { 
-                Some((_, total)) => Self::format_cache_size(total), 
-                None => "Unknown".to_string() 
-            }),
-        ]
+    /// The width is the linear address size from CPUID leaf 0x80000008, falling back
+    /// to the "address sizes" field of /proc/cpuinfo. 64-bit kernels only list the
+    /// `la57` flag when they run with 5-level paging, so it tells whether the full
+    /// 57-bit space is actually usable; otherwise 4-level paging limits it to 48 bits
+    /// and the usable width is shown with the CPU's width noted.
+    ///
+    /// # Returns
+    ///
+    /// Returns a string such as "57-bit (5-level paging)" or "48-bit (4-level paging;
+    /// CPU supports 57-bit)", or `None` off x86 or when the width is unknown.
+    fn virtual_addressing(&self) -> Option<String> {
+        if !matches!(self.architecture.as_str(), "x86_64" | "i386" | "i486" | "i586" | "i686") {
+            return None;
+        }
+        let bits = cpuid::linear_address_bits().or(self.virtual_address_bits)?;
+        let has_flag = |name: &str| self.flags.split_whitespace().any(|flag| flag == name);
+        Some(Self::format_virtual_addressing(bits, has_flag("lm"), has_flag("la57")))
     }
 
-    /// Format cache size with appropriate units (KB or MB).
-    ///
-    /// This helper function formats cache sizes in a human-readable format,
-    /// converting sizes above 1000KB to megabytes with decimal precision.
+    /// Format the virtual address width for `virtual_addressing`.
     ///
     /// # Arguments
     ///
-    /// * `size_kb` - Cache size in kilobytes
+    /// * `bits` - Linear address width supported by the CPU
+    /// * `long_mode` - Whether the CPU runs in 64-bit mode (`lm` flag)
+    /// * `la57` - Whether the kernel uses 5-level paging (`la57` flag)
+    fn format_virtual_addressing(bits: u32, long_mode: bool, la57: bool) -> String {
+        if !long_mode {
+            format!("{}-bit", bits)
+        } else if la57 {
+            format!("{}-bit (5-level paging)", bits)
+        } else if bits > 48 {
+            format!("48-bit (4-level paging; CPU supports {}-bit)", bits)
+        } else {
+            format!("{}-bit (4-level paging)", bits)
+        }
+    }
+
+    /// Format the thread portion of the "Cores" line.
+    ///
+    /// Shows the single logical core count when the configured and online counts
+    /// agree, and both counts when some CPUs are offline.
     ///
     /// # Returns
     ///
-    /// Returns a formatted string with appropriate units (e.g., "288KB" or "6.0MB")
-    fn format_cache_size(size_kb: u32) -> String {
-        if size_kb >= 1000 {
-            let size_mb = size_kb as f32 / 1024.0;
-            format!("{:.1}MB", size_mb)
-        } else {
-            format!("{}KB", size_kb)
+    /// Returns a string such as "16 threads" or "16 configured, 12 online threads".
+    fn format_threads(&self) -> String {
+        match self.cpu_counts {
+            Some((configured, online)) if configured != online => {
+                format!("{} configured, {} online threads", configured, online)
+            }
+            _ => format!("{} threads", self.logical_cores),
         }
     }
 
-    /// Get the number of physical CPU cores from /proc/cpuinfo.
+    /// Get maximum CPU frequency from cpufreq information.
     ///
-    /// This helper function determines the number of physical cores by parsing
-    /// /proc/cpuinfo and counting unique (physical_id, core_id) pairs.
+    /// This function attempts to read the maximum CPU frequency from the Linux
+    /// cpufreq subsystem by checking scaling_max_freq files for all CPU cores.
     ///
     /// # Returns
     ///
-    /// Returns the number of physical cores, or `None` if the count cannot be determined.
-    fn get_physical_core_count() -> Option<u32> {
-        let cpuinfo_content = fs::read_to_string("/proc/cpuinfo").ok()?;
-        let mut physical_ids = std::collections::HashSet::new();
-        let mut core_ids = std::collections::HashSet::new();
-        
-        for processor_block in cpuinfo_content.split("\n\n") {
-            if processor_block.trim().is_empty() {
-                continue;
-            }
-            
-            let mut current_physical_id = None;
-            let mut current_core_id = None;
-            
-            for line in processor_block.lines() {
-                if let Some((key, value)) = line.split_once(':') {
-                    match key.trim() {
-                        "physical id" => {
-                            if let Ok(id) = value.trim().parse::<u32>() {
-                                current_physical_id = Some(id);
-                            }
-                        },
-                        "core id" => {
-                            if let Ok(id) = value.trim().parse::<u32>() {
-                                current_core_id = Some(id);
-                            }
-                        },
-                        _ => {}
-                    }
-                }
-            }
-            
-            if let Some(phys_id) = current_physical_id {
-                physical_ids.insert(phys_id);
-            }
-            if let (Some(phys_id), Some(core_id)) = (current_physical_id, current_core_id) {
-                core_ids.insert((phys_id, core_id));
+    /// Returns `Some(f32)` with the maximum frequency in GHz if available,
+    /// or `None` if the information cannot be read.
+    fn get_max_frequency() -> Option<f32> {
+        Self::highest_cpufreq("scaling_max_freq")
+    }
+
+    /// Read a cpufreq frequency file of every CPU and return the highest value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - File under `cpu*/cpufreq`, e.g. "scaling_max_freq"
+    ///
+    /// # Returns
+    ///
+    /// Returns the highest frequency in GHz, or `None` if no CPU has the file.
+    fn highest_cpufreq(name: &str) -> Option<f32> {
+        let mut max_freq = 0u64;
+        for entry in fs::read_dir("/sys/devices/system/cpu").into_iter().flatten().flatten() {
+            let path = entry.path();
+            if let Some(cpu) = path.file_name().and_then(|n| n.to_str())
+                && cpu.starts_with("cpu") && cpu[3..].chars().all(|c| c.is_ascii_digit())
+                && let Ok(freq_str) = fs::read_to_string(path.join("cpufreq").join(name))
+                && let Ok(freq) = freq_str.trim().parse::<u64>() {
+                max_freq = max_freq.max(freq);
             }
         }
-        
-        if !core_ids.is_empty() {
-            Some(core_ids.len() as u32)
-        } else if !physical_ids.is_empty() {
-            Some(physical_ids.len() as u32)
-        } else {
-            Some(1) // Default fallback
+
+        // Convert from kHz to GHz
+        (max_freq > 0).then(|| max_freq as f32 / 1_000_000.0)
+    }
+
+    /// Get the base (nominal) CPU frequency.
+    ///
+    /// Uses CPUID leaf 0x16 on x86, falling back to cpufreq's `base_frequency`
+    /// (provided by intel_pstate).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(f32)` with the base frequency in GHz, or `None` if unavailable.
+    fn get_base_frequency() -> Option<f32> {
+        if let Some(mhz) = cpuid::base_frequency_mhz() {
+            return Some(mhz as f32 / 1000.0);
         }
+        fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/base_frequency")
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|khz| khz as f32 / 1_000_000.0)
     }
-}
 
-/// Intermediate struct for holding parsed CPU information from /proc/cpuinfo.
-///
-/// This struct is used internally during the parsing process to collect
-/// information before creating the final `LinuxCpuInfo` struct.
+    /// Check whether the CPU is capped far below its rated base frequency.
+    ///
+    /// Firmware occasionally boots a CPU in a degraded mode (after a thermal event or
+    /// with a bad configuration) where cpufreq never allows it to reach its rated
+    /// speed. The hardware ceiling (the highest `cpuinfo_max_freq`) is compared, not
+    /// `scaling_max_freq`, which users, TLP and power profiles lower on purpose. The
+    /// base is the frequency the CPU guarantees, so a ceiling well below it is not
+    /// explained by turbo being disabled. The check is skipped without both values and
+    /// under a hypervisor, where cpufreq and CPUID describe the host rather than the
+    /// guest.
+    ///
+    /// # Arguments
+    ///
+    /// * `hardware_max` - Highest `cpuinfo_max_freq` across all CPUs, in GHz
+    /// * `rated_base` - Rated base frequency in GHz, from CPUID leaf 0x16 or the
+    ///   microarchitecture table
+    /// * `virtualized` - Whether a hypervisor was detected
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some((achievable, rated))` in GHz when the achievable maximum is below
+    /// `BELOW_SPEC_RATIO` of the rated base frequency, `None` otherwise.
+    fn detect_below_spec(hardware_max: Option<f32>, rated_base: Option<f32>, virtualized: bool) -> Option<(f32, f32)> {
+        if virtualized {
+            return None;
+        }
+        let achievable = hardware_max?;
+        let rated = rated_base?;
+        (achievable < rated * BELOW_SPEC_RATIO).then_some((achievable, rated))
+    }
+
+    /// Read the cpufreq policies, each an independent frequency domain.
+    ///
+    /// Every `/sys/devices/system/cpu/cpufreq/policy*` directory covers the CPUs in
+    /// its `related_cpus`, which share one clock. ARM clusters usually get one policy
+    /// each, while most x86 drivers create one policy per CPU.
+    ///
+    /// # Returns
+    ///
+    /// Returns the policies ordered by their first CPU; empty without cpufreq.
+    fn get_frequency_domains() -> Vec<FrequencyDomain> {
+        let Ok(entries) = fs::read_dir("/sys/devices/system/cpu/cpufreq") else {
+            return Vec::new();
+        };
+
+        let mut domains = Vec::new();
+        for entry in entries.flatten() {
+            let is_policy = entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix("policy"))
+                .is_some_and(|n| n.parse::<u32>().is_ok());
+            if !is_policy {
+                continue;
+            }
+
+            let read = |name: &str| fs::read_to_string(entry.path().join(name)).ok();
+            let cpus: Vec<u32> = read("related_cpus").unwrap_or_default()
+                .split_whitespace()
+                .filter_map(|cpu| cpu.parse().ok())
+                .collect();
+            let max_khz = read("scaling_max_freq").or_else(|| read("cpuinfo_max_freq"))
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .filter(|&khz| khz > 0);
+            if !cpus.is_empty() && let Some(max_khz) = max_khz {
+                domains.push(FrequencyDomain { cpus, max_khz });
+            }
+        }
+
+        domains.sort_by_key(|domain| domain.cpus[0]);
+        domains
+    }
+
+    /// Merge the frequency domains into clusters with the same maximum frequency.
+    ///
+    /// Per-CPU policies on x86 would otherwise list every CPU separately; merging by
+    /// frequency leaves one entry per core type.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(max_khz, cpu_count)` per cluster, ordered by the cluster's first CPU.
+    fn frequency_clusters(&self) -> Vec<(u64, usize)> {
+        let mut clusters: Vec<(u64, usize)> = Vec::new();
+        for domain in &self.frequency_domains {
+            match clusters.iter_mut().find(|(khz, _)| *khz == domain.max_khz) {
+                Some((_, count)) => *count += domain.cpus.len(),
+                None => clusters.push((domain.max_khz, domain.cpus.len())),
+            }
+        }
+        clusters
+    }
+
+    /// Get the current CPU frequency, sampled from the fastest running CPU.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(f32)` with the current frequency in GHz, or `None` if unavailable.
+    fn get_current_frequency() -> Option<f32> {
+        Self::get_core_frequencies()
+            .into_iter()
+            .map(|(_, mhz)| mhz)
+            .reduce(f32::max)
+            .map(|mhz| mhz / 1000.0)
+    }
+
+    /// Check whether cpufreq reports the current frequency, which `get_core_frequencies`
+    /// prefers over /proc/cpuinfo.
+    fn has_cpufreq_current() -> bool {
+        fs::read_dir("/sys/devices/system/cpu").into_iter().flatten().flatten()
+            .any(|entry| entry.path().join("cpufreq/scaling_cur_freq").exists())
+    }
+
+    /// Measure the average effective frequency of all CPUs.
+    ///
+    /// APERF counts at the actual core clock and MPERF at a fixed reference (the base
+    /// frequency), and both only advance while the CPU is running (C0). Over a short
+    /// window, base × ΔAPERF / ΔMPERF is therefore the true average frequency of the
+    /// time spent running, including turbo and throttling that a single cpufreq
+    /// sample misses. The deltas are summed over all CPUs, so CPUs are weighted by
+    /// how long they ran and idle CPUs don't drag the average down.
+    ///
+    /// Reading the MSRs needs the `msr` kernel module and root. Without them (or
+    /// without a known base frequency) the average of `scaling_cur_freq` is used.
+    ///
+    /// # Returns
+    ///
+    /// Returns e.g. "4.350 GHz avg (APERF/MPERF)", or `None` if no frequency could
+    /// be read.
+    fn effective_frequency() -> Option<String> {
+        if let Some(base_mhz) = Self::get_base_frequency().map(|ghz| ghz * 1000.0).or_else(Self::amd_nominal_frequency) {
+            let cpus = Self::msr_cpus();
+            let before: Vec<(u64, u64)> = cpus.iter().filter_map(|&cpu| Self::read_aperf_mperf(cpu)).collect();
+            if !before.is_empty() {
+                thread::sleep(MSR_SAMPLE_WINDOW);
+                let after: Vec<(u64, u64)> = cpus.iter().filter_map(|&cpu| Self::read_aperf_mperf(cpu)).collect();
+                if before.len() == after.len() {
+                    let (aperf, mperf) = before.iter().zip(&after)
+                        .map(|(&(a0, m0), &(a1, m1))| (a1.wrapping_sub(a0), m1.wrapping_sub(m0)))
+                        .fold((0u64, 0u64), |(a, m), (da, dm)| (a + da, m + dm));
+                    if mperf > 0 {
+                        let ghz = base_mhz as f64 * aperf as f64 / mperf as f64 / 1000.0;
+                        return Some(format!("{:.3} GHz avg (APERF/MPERF)", ghz));
+                    }
+                }
+            }
+        }
+
+        let frequencies = Self::get_core_frequencies();
+        if frequencies.is_empty() {
+            return None;
+        }
+        let average_mhz = frequencies.iter().map(|&(_, mhz)| mhz).sum::<f32>() / frequencies.len() as f32;
+        Some(format!("{:.3} GHz avg (scaling_cur_freq)", average_mhz / 1000.0))
+    }
+
+    /// Get the nominal frequency reported by the amd-pstate driver.
+    ///
+    /// AMD CPUs don't report a base frequency in CPUID, but MPERF counts at the
+    /// nominal (P0) frequency, which amd-pstate exposes.
+    ///
+    /// # Returns
+    ///
+    /// Returns the nominal frequency in MHz, or `None` without amd-pstate.
+    fn amd_nominal_frequency() -> Option<f32> {
+        fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/amd_pstate_nominal_freq")
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|khz| khz as f32 / 1000.0)
+    }
+
+    /// List the CPUs with an MSR device node in `/dev/cpu`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the CPU ids sorted ascending; empty without the `msr` module.
+    fn msr_cpus() -> Vec<u32> {
+        let mut cpus: Vec<u32> = fs::read_dir("/dev/cpu")
+            .map(|entries| {
+                entries.flatten()
+                    .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                    .filter(|cpu| std::path::Path::new(&format!("/dev/cpu/{}/msr", cpu)).exists())
+                    .collect()
+            })
+            .unwrap_or_default();
+        cpus.sort_unstable();
+        cpus
+    }
+
+    /// Read the APERF and MPERF counters of one CPU through the `msr` driver.
+    ///
+    /// The driver maps the file offset to the MSR address and performs the read on
+    /// the CPU the file belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `cpu` - Logical CPU id
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some((aperf, mperf))`, or `None` if the MSRs can't be read (not root,
+    /// or not an x86 CPU).
+    #[cfg(target_os = "linux")]
+    fn read_aperf_mperf(cpu: u32) -> Option<(u64, u64)> {
+        use std::os::unix::fs::FileExt;
+
+        let file = fs::File::open(format!("/dev/cpu/{}/msr", cpu)).ok()?;
+        let read = |msr: u64| {
+            let mut value = [0u8; 8];
+            file.read_exact_at(&mut value, msr).ok()?;
+            Some(u64::from_le_bytes(value))
+        };
+        Some((read(MSR_IA32_APERF)?, read(MSR_IA32_MPERF)?))
+    }
+
+    /// Read the APERF and MPERF counters of one CPU (unsupported on this OS).
+    #[cfg(not(target_os = "linux"))]
+    fn read_aperf_mperf(_cpu: u32) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// Find the hwmon directory of the CPU temperature sensor.
+    ///
+    /// Looks for a hwmon device whose `name` is one of the CPU sensor drivers in
+    /// `CPU_TEMP_SENSORS`. The first temperature channel (`temp1`) of these drivers
+    /// is the package (coretemp) or control (k10temp Tctl) temperature.
+    ///
+    /// # Returns
+    ///
+    /// Returns the hwmon directory, or `None` if no CPU sensor is exposed.
+    fn find_cpu_hwmon() -> Option<std::path::PathBuf> {
+        fs::read_dir("/sys/class/hwmon").ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| {
+                fs::read_to_string(path.join("name"))
+                    .is_ok_and(|name| CPU_TEMP_SENSORS.contains(&name.trim()))
+            })
+    }
+
+    /// Read a hwmon temperature file of the CPU sensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File name, e.g. "temp1_input"
+    ///
+    /// # Returns
+    ///
+    /// Returns the temperature in °C (hwmon reports millidegrees), or `None` if the
+    /// file is missing or unreadable.
+    fn read_cpu_hwmon_temp(file: &str) -> Option<f32> {
+        let millidegrees = fs::read_to_string(Self::find_cpu_hwmon()?.join(file)).ok()?;
+        millidegrees.trim().parse::<f32>().ok().map(|m| m / 1000.0)
+    }
+
+    /// Get the current CPU temperature from hwmon.
+    ///
+    /// # Returns
+    ///
+    /// Returns the temperature in °C, or `None` if no CPU sensor is exposed (common in
+    /// VMs and containers).
+    fn get_temperature() -> Option<f32> {
+        Self::read_cpu_hwmon_temp("temp1_input")
+    }
+
+    /// Get the CPU's maximum junction temperature (Tjmax) from hwmon.
+    ///
+    /// Uses the critical temperature (`temp1_crit`), falling back to the maximum
+    /// (`temp1_max`) for drivers that only report that.
+    ///
+    /// # Returns
+    ///
+    /// Returns Tjmax in °C, or `None` if the sensor doesn't expose it.
+    pub fn get_critical_temperature() -> Option<f32> {
+        Self::read_cpu_hwmon_temp("temp1_crit")
+            .or_else(|| Self::read_cpu_hwmon_temp("temp1_max"))
+    }
+
+    /// Describe the cpufreq scaling driver and who manages frequencies.
+    ///
+    /// Reads `cpu0/cpufreq/scaling_driver` and, for the P-state drivers, their mode
+    /// from `intel_pstate/status` or `amd_pstate/status`. In active mode with HWP
+    /// (Intel) or EPP (AMD) the hardware picks frequencies itself; otherwise the
+    /// kernel governor does.
+    ///
+    /// # Returns
+    ///
+    /// Returns a description such as "intel_pstate (active/HWP)", "amd-pstate (EPP)",
+    /// or "acpi-cpufreq", or `None` if cpufreq is unavailable.
+    fn scaling_driver(&self) -> Option<String> {
+        let driver = fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_driver").ok()?;
+        let read_status = |path: &str| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+
+        let description = match driver.trim() {
+            "intel_pstate" => {
+                let has_hwp = self.flags.split_whitespace().any(|flag| flag == "hwp");
+                match read_status("/sys/devices/system/cpu/intel_pstate/status").as_deref() {
+                    Some("active") if has_hwp => "intel_pstate (active/HWP)".to_string(),
+                    Some(status) => format!("intel_pstate ({})", status),
+                    None => "intel_pstate".to_string(),
+                }
+            }
+            // intel_pstate in passive mode registers as intel_cpufreq
+            "intel_cpufreq" => "intel_cpufreq (intel_pstate passive)".to_string(),
+            "amd-pstate-epp" => "amd-pstate (EPP)".to_string(),
+            "amd-pstate" => match read_status("/sys/devices/system/cpu/amd_pstate/status") {
+                Some(status) => format!("amd-pstate ({})", status),
+                None => "amd-pstate".to_string(),
+            },
+            other => other.to_string(),
+        };
+        Some(description)
+    }
+
+    /// Format the `--power` ACPI CPPC (Collaborative Processor Performance Control) lines.
+    ///
+    /// Reads the abstract performance levels from `cpuN/acpi_cppc/`. The nominal and
+    /// lowest levels come from CPU 0; the highest level is taken across all CPUs,
+    /// since on chips with favored (preferred) cores the firmware gives a few cores
+    /// a higher `highest_perf` so the scheduler puts demanding work on them first.
+    ///
+    /// # Returns
+    ///
+    /// Returns lines such as "CPPC: highest 255, nominal 180, lowest 20" and
+    /// "Favored Cores: 2 of 16 CPUs (highest_perf 255 vs 236)", or nothing when
+    /// CPPC isn't exposed.
+    fn cppc_lines() -> Vec<String> {
+        let read_perf = |cpu: &str, name: &str| {
+            fs::read_to_string(format!("/sys/devices/system/cpu/{}/acpi_cppc/{}", cpu, name)).ok()
+                .and_then(|s| s.trim().parse::<u32>().ok())
+        };
+
+        let highest: Vec<u32> = fs::read_dir("/sys/devices/system/cpu")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_prefix("cpu")?.parse::<u32>().ok()?;
+                read_perf(&name, "highest_perf")
+            })
+            .collect();
+        let (Some(&max), Some(&min)) = (highest.iter().max(), highest.iter().min()) else {
+            return Vec::new();
+        };
+
+        let mut summary = format!("CPPC: highest {}", max);
+        if let Some(nominal) = read_perf("cpu0", "nominal_perf") {
+            summary.push_str(&format!(", nominal {}", nominal));
+        }
+        if let Some(lowest) = read_perf("cpu0", "lowest_perf") {
+            summary.push_str(&format!(", lowest {}", lowest));
+        }
+
+        let mut lines = vec![summary];
+        if max != min {
+            let favored = highest.iter().filter(|&&perf| perf == max).count();
+            lines.push(format!(
+                "Favored Cores: {} of {} CPUs (highest_perf {} vs {})",
+                favored, highest.len(), max, min,
+            ));
+        }
+        lines
+    }
+
+    /// Get the idle states supported by CPU 0 from cpuidle.
+    ///
+    /// Each `cpuidle/stateN` directory describes one state, from the shallowest
+    /// (usually POLL) to the deepest. The description carries the hardware hint the
+    /// driver uses, e.g. "MWAIT 0x60" for intel_idle or "ACPI FFH MWAIT 0x60" for
+    /// acpi_idle.
+    ///
+    /// # Returns
+    ///
+    /// Returns the states ordered from shallowest to deepest, or an empty list if
+    /// cpuidle is unavailable (common in VMs and containers).
+    fn get_idle_states() -> Vec<IdleState> {
+        let base = std::path::Path::new("/sys/devices/system/cpu/cpu0/cpuidle");
+        let mut states = Vec::new();
+
+        for index in 0.. {
+            let dir = base.join(format!("state{}", index));
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok().map(|s| s.trim().to_string());
+            let Some(name) = read("name") else { break };
+            states.push(IdleState {
+                name,
+                desc: read("desc").unwrap_or_default(),
+                latency_us: read("latency").and_then(|s| s.parse().ok()),
+                disabled: read("disable").is_some_and(|s| s == "1"),
+            });
+        }
+
+        states
+    }
+
+    /// Format the `--power` idle state lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - Idle states ordered from shallowest to deepest
+    ///
+    /// # Returns
+    ///
+    /// Returns lines such as "Idle States: POLL, C1, C1E, C6 (intel_idle)" and
+    /// "Deepest Idle State: C6 (MWAIT 0x20, 170 µs exit latency)", or nothing if no
+    /// idle states are known. Disabled states are marked and never reported as the
+    /// deepest one, since the CPU can't enter them.
+    fn idle_state_lines(states: &[IdleState]) -> Vec<String> {
+        if states.is_empty() {
+            return Vec::new();
+        }
+
+        let names: Vec<String> = states.iter()
+            .map(|state| if state.disabled { format!("{} (disabled)", state.name) } else { state.name.clone() })
+            .collect();
+        let driver = fs::read_to_string("/sys/devices/system/cpu/cpuidle/current_driver")
+            .ok()
+            .map(|driver| format!(" ({})", driver.trim()))
+            .unwrap_or_default();
+        let mut lines = vec![format!("Idle States: {}{}", names.join(", "), driver)];
+
+        if let Some(deepest) = states.iter().rev().find(|state| !state.disabled) {
+            let mut details = Vec::new();
+            if !deepest.desc.is_empty() && deepest.desc != deepest.name {
+                details.push(deepest.desc.clone());
+            }
+            if let Some(latency) = deepest.latency_us {
+                details.push(format!("{} µs exit latency", latency));
+            }
+            if details.is_empty() {
+                lines.push(format!("Deepest Idle State: {}", deepest.name));
+            } else {
+                lines.push(format!("Deepest Idle State: {} ({})", deepest.name, details.join(", ")));
+            }
+        }
+
+        lines
+    }
+
+    /// Read the Intel energy-performance bias (EPB) hint of CPU 0.
+    ///
+    /// The EPB ranges from 0 (maximum performance) to 15 (maximum energy saving) and
+    /// steers the hardware's own power decisions, such as turbo aggressiveness, even
+    /// where cpufreq's EPP doesn't apply. The kernel only exposes it on CPUs that
+    /// support it.
+    ///
+    /// # Returns
+    ///
+    /// Returns e.g. "6 (balanced)", or `None` when `energy_perf_bias` is missing.
+    fn energy_perf_bias() -> Option<String> {
+        let bias = fs::read_to_string("/sys/devices/system/cpu/cpu0/power/energy_perf_bias").ok()?
+            .trim()
+            .parse::<u32>()
+            .ok()?;
+        // Ranges around the kernel's named values: 0, 4, 6 (default), 8, and 15
+        let label = match bias {
+            0..=3 => "performance",
+            4..=5 => "balance-performance",
+            6..=7 => "balanced",
+            8..=14 => "balance-power",
+            _ => "powersave",
+        };
+        Some(format!("{} ({})", bias, label))
+    }
+
+    /// Get the current frequency of every logical CPU.
+    ///
+    /// Reads `scaling_cur_freq` from cpufreq for each CPU, falling back to the per-processor
+    /// "cpu MHz" entries in /proc/cpuinfo when cpufreq is unavailable (e.g. in VMs).
+    ///
+    /// # Returns
+    ///
+    /// Returns `(cpu id, frequency in MHz)` pairs sorted by CPU id; empty if nothing
+    /// could be read.
+    fn get_core_frequencies() -> Vec<(u32, f32)> {
+        let mut frequencies = Vec::new();
+
+        if let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") {
+            for entry in entries.flatten() {
+                let Some(cpu) = entry.file_name().to_str()
+                    .and_then(|name| name.strip_prefix("cpu"))
+                    .and_then(|id| id.parse::<u32>().ok()) else {
+                    continue;
+                };
+                if let Ok(freq_str) = fs::read_to_string(entry.path().join("cpufreq/scaling_cur_freq"))
+                    && let Ok(khz) = freq_str.trim().parse::<u64>() {
+                    frequencies.push((cpu, khz as f32 / 1000.0));
+                }
+            }
+        }
+
+        if frequencies.is_empty() {
+            let cpuinfo_content = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+            for processor_block in cpuinfo_content.split("\n\n") {
+                let mut cpu = None;
+                let mut mhz = None;
+                for line in processor_block.lines() {
+                    if let Some((key, value)) = line.split_once(':') {
+                        match key.trim() {
+                            "processor" => cpu = value.trim().parse::<u32>().ok(),
+                            "cpu MHz" => mhz = value.trim().parse::<f32>().ok(),
+                            _ => {}
+                        }
+                    }
+                }
+                if let (Some(cpu), Some(mhz)) = (cpu, mhz) {
+                    frequencies.push((cpu, mhz));
+                }
+            }
+        }
+
+        frequencies.sort_by_key(|&(cpu, _)| cpu);
+        frequencies
+    }
+
+    /// Get detailed cache information from sysfs.
+    ///
+    /// This function reads cache information directly from the Linux sysfs filesystem
+    /// at `/sys/devices/system/cpu/cpu*/cache/index*/` for every CPU. Each cache
+    /// instance is identified by its level, type, and `shared_cpu_list`, so a cache
+    /// shared by several CPUs (SMT siblings, an E-core cluster, an L3 slice) is
+    /// counted once. This keeps the totals right on hybrid chips, where the cores
+    /// don't all have the same caches and multiplying cpu0's sizes by the core count
+    /// would overcount.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of optional cache sizes in the format:
+    /// `(L1d, L1i, L2, L3)` where each element is `Option<(per_core_kb, total_kb)>`
+    /// Only total cache sizes are reported for each level. Returns `None` when sysfs
+    /// reports no cache with a usable size, so the /proc/cpuinfo values are used.
+    fn get_cache_info() -> Option<CacheSizes> {
+        let entries = Self::cache_index_dirs().into_iter().filter_map(|(cpu, cache_path)| {
+            let size = fs::read_to_string(cache_path.join("size")).ok()?;
+            Some((Self::cache_signature(cpu, &cache_path)?, size))
+        });
+        Self::sum_cache_sizes(entries)
+    }
+
+    /// Sum the sizes of the distinct caches at each level.
+    ///
+    /// Entries with the same signature (see `cache_signature`) describe the same
+    /// cache and are only counted once. Entries whose size is missing or zero are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - `(signature, sysfs size)` pairs, e.g. `((2, "Unified", "id 0"), "1024K")`
+    ///
+    /// # Returns
+    ///
+    /// Returns the totals as in `get_cache_info`, or `None` if no entry has a usable size.
+    fn sum_cache_sizes(entries: impl IntoIterator<Item = ((u32, String, String), String)>) -> Option<CacheSizes> {
+        use std::collections::{HashMap, HashSet};
+
+        let mut cache_sizes: HashMap<String, u64> = HashMap::new();
+        let mut seen: HashSet<(u32, String, String)> = HashSet::new();
+
+        for (signature, size) in entries {
+            // Parse size (e.g., "32K" -> 32, "1024K" -> 1024)
+            if let Some(size_kb) = Self::parse_cache_size(size.trim()) {
+                let cache_key = format!("L{}_{}", signature.0, signature.1);
+                if seen.insert(signature) {
+                    *cache_sizes.entry(cache_key).or_insert(0) += size_kb as u64;
+                }
+            }
+        }
+
+        if cache_sizes.is_empty() {
+            return None;
+        }
+
+        Some((
+            cache_sizes.get("L1_Data").map(|&total| (0, total)), // Only report total, per-core not used
+            cache_sizes.get("L1_Instruction").map(|&total| (0, total)),
+            cache_sizes.get("L2_Unified").map(|&total| (0, total)),
+            cache_sizes.get("L3_Unified").map(|&total| (0, total)),
+        ))
+    }
+
+    /// List the `cache/index*` directories of every CPU in sysfs.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(cpu number, index directory)` pairs.
+    fn cache_index_dirs() -> Vec<(u32, std::path::PathBuf)> {
+        let mut dirs = Vec::new();
+        for cpu_entry in fs::read_dir("/sys/devices/system/cpu").into_iter().flatten().flatten() {
+            let cpu = match cpu_entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .and_then(|n| n.parse::<u32>().ok())
+            {
+                Some(cpu) => cpu,
+                None => continue,
+            };
+            for cache_entry in fs::read_dir(cpu_entry.path().join("cache")).into_iter().flatten().flatten() {
+                let cache_path = cache_entry.path();
+                if cache_path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("index")) {
+                    dirs.push((cpu, cache_path));
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Count the distinct instances of each cache across all CPUs.
+    ///
+    /// CPUs that share a cache report the same `shared_cpu_list` for it, so each
+    /// distinct list is one physical cache.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of instances keyed by `(level, sysfs type)`, e.g.
+    /// `(2, "Unified")`. Caches without a sharing list are not counted.
+    fn count_cache_instances() -> std::collections::HashMap<(u32, String), u32> {
+        use std::collections::{HashMap, HashSet};
+
+        let mut seen: HashSet<(u32, String, String)> = HashSet::new();
+        let mut instances: HashMap<(u32, String), u32> = HashMap::new();
+        for (cpu, cache_path) in Self::cache_index_dirs() {
+            // A cache without an id or sharing list can't be told apart from its copies
+            let has_identity = cache_path.join("id").exists() || cache_path.join("shared_cpu_list").exists();
+            if let Some(signature) = Self::cache_signature(cpu, &cache_path).filter(|_| has_identity)
+                && seen.insert(signature.clone()) {
+                *instances.entry((signature.0, signature.1)).or_insert(0) += 1;
+            }
+        }
+        instances
+    }
+
+    /// Identify the physical cache an `index*` directory describes.
+    ///
+    /// Every CPU lists the caches it uses, so a shared cache appears once per CPU, and
+    /// some kernels even list the same cache under several `index*` entries of one
+    /// CPU. Entries with the same level, type, and cache `id` (or, on kernels without
+    /// `id`, the same `shared_cpu_list`) are the same cache and must only be counted
+    /// once when summing sizes or instances.
+    ///
+    /// # Arguments
+    ///
+    /// * `cpu` - CPU the directory belongs to; caches without an id or sharing list
+    ///   are assumed to be private to it
+    /// * `cache_path` - The `cache/index*` directory
+    ///
+    /// # Returns
+    ///
+    /// Returns `(level, sysfs type, identity)`, or `None` if the level or type can't
+    /// be read.
+    fn cache_signature(cpu: u32, cache_path: &std::path::Path) -> Option<(u32, String, String)> {
+        let read = |name: &str| fs::read_to_string(cache_path.join(name)).ok().map(|s| s.trim().to_string());
+        let level = read("level")?.parse::<u32>().ok()?;
+        let cache_type = read("type")?;
+        let identity = Self::cache_identity(cpu, read("id"), read("shared_cpu_list"));
+        Some((level, cache_type, identity))
+    }
+
+    /// Build the identity part of a cache signature.
+    ///
+    /// Prefers the cache `id`, then the `shared_cpu_list`, and finally the CPU
+    /// number, so a cache without either is treated as private to its CPU.
+    fn cache_identity(cpu: u32, id: Option<String>, shared_cpu_list: Option<String>) -> String {
+        id.map(|id| format!("id {}", id))
+            .or(shared_cpu_list)
+            .unwrap_or_else(|| cpu.to_string())
+    }
+
+    /// Describe the L2 cache per core type on hybrid Intel chips.
+    ///
+    /// The kernel lists the CPUs of each core type under `/sys/devices/cpu_core/cpus`
+    /// (P-cores) and `/sys/devices/cpu_atom/cpus` (E-cores). For each type this reads
+    /// the L2 of its CPUs and counts the distinct instances; E-cores share one L2 per
+    /// cluster of four, which is what `cpu0 × cores` got wrong. L2s of different sizes
+    /// within one type (the low-power E-cores of Meteor Lake and Lunar Lake) are listed
+    /// separately.
+    ///
+    /// # Returns
+    ///
+    /// Returns a description such as "8× 2MB (P) + 2× 4MB shared (E-cluster)", or
+    /// `None` on chips with a single core type.
+    fn get_hybrid_l2() -> Option<String> {
+        use std::collections::HashMap;
+
+        let read_cpus = |path: &str| fs::read_to_string(path).ok().and_then(|list| Self::parse_cpu_list(&list));
+        let core_types = [("P", read_cpus("/sys/devices/cpu_core/cpus")?), ("E", read_cpus("/sys/devices/cpu_atom/cpus")?)];
+
+        // The L2 of every CPU: (identity, size, shared beyond the core's SMT siblings)
+        let mut l2_by_cpu: HashMap<u32, (String, u32, bool)> = HashMap::new();
+        for (cpu, cache_path) in Self::cache_index_dirs() {
+            let read = |name: &str| fs::read_to_string(cache_path.join(name)).ok().map(|s| s.trim().to_string());
+            let Some((2, cache_type, identity)) = Self::cache_signature(cpu, &cache_path) else { continue };
+            if cache_type != "Unified" {
+                continue;
+            }
+            let Some(size_kb) = read("size").and_then(|s| Self::parse_cache_size(&s)) else { continue };
+            let siblings = fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/topology/thread_siblings_list", cpu))
+                .ok()
+                .and_then(|s| Self::count_cpu_list(&s))
+                .unwrap_or(1);
+            let shared_by_cores = read("shared_cpu_list").and_then(|s| Self::count_cpu_list(&s)).unwrap_or(1) > siblings;
+            l2_by_cpu.insert(cpu, (identity, size_kb, shared_by_cores));
+        }
+
+        let mut parts = Vec::new();
+        for (label, cpus) in core_types {
+            let l2s: Vec<(String, u32, bool)> = cpus.iter().filter_map(|cpu| l2_by_cpu.get(cpu).cloned()).collect();
+            if l2s.is_empty() {
+                return None;
+            }
+            parts.extend(Self::format_l2_groups(label, &l2s));
+        }
+        Some(parts.join(" + "))
+    }
+
+    /// Describe the L2 instances of one core type, one group per cache size.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Core type label ("P" or "E")
+    /// * `l2s` - `(identity, size in KB, shared by cores)` of each CPU's L2; CPUs
+    ///   sharing an L2 report the same identity
+    ///
+    /// # Returns
+    ///
+    /// Returns one description per size, largest first, e.g. "2× 4MB shared (E-cluster)".
+    fn format_l2_groups(label: &str, l2s: &[(String, u32, bool)]) -> Vec<String> {
+        use std::collections::{BTreeMap, HashSet};
+
+        let mut groups: BTreeMap<u32, (HashSet<&str>, bool)> = BTreeMap::new();
+        for (identity, size_kb, shared_by_cores) in l2s {
+            let group = groups.entry(*size_kb).or_default();
+            group.0.insert(identity);
+            group.1 |= *shared_by_cores;
+        }
+        groups.iter().rev().map(|(&size_kb, (instances, shared_by_cores))| {
+            if *shared_by_cores {
+                format!("{}× {} shared ({}-cluster)", instances.len(), display::format_cache_size(size_kb), label)
+            } else {
+                format!("{}× {} ({})", instances.len(), display::format_cache_size(size_kb), label)
+            }
+        }).collect()
+    }
+
+    /// Build the `--core-map` lines, or nothing when the option isn't set.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Width available to the info column
+    /// * `opts` - Display options (core map, colors)
+    fn core_map_lines(width: usize, opts: &DisplayOptions) -> Vec<String> {
+        if !opts.core_map {
+            return Vec::new();
+        }
+        let kinds: Vec<CoreKind> = Self::get_core_kinds().into_iter().map(|(_, kind)| kind).collect();
+        if kinds.is_empty() {
+            return vec!["Core Map: single core type (not a hybrid CPU)".to_string()];
+        }
+        display::core_map_lines(&kinds, width, opts)
+    }
+
+    /// Determine the core type of every logical CPU on hybrid chips.
+    ///
+    /// Intel lists P-cores under `/sys/devices/cpu_core/cpus` and E-cores under
+    /// `/sys/devices/cpu_atom/cpus`; E-cores without an L3 cache are the low-power
+    /// E-cores of the SoC tile (Meteor Lake and later). Elsewhere (ARM) the
+    /// `cpu_capacity` of each CPU is compared: the highest capacity is a P-core, the
+    /// lowest an E-core, or a low-power core when there are three or more tiers.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(cpu number, core type)` pairs in CPU number order, or an empty `Vec`
+    /// when all cores are the same type or sysfs doesn't say.
+    fn get_core_kinds() -> Vec<(u32, CoreKind)> {
+        let read_cpus = |path: &str| fs::read_to_string(path).ok().and_then(|list| Self::parse_cpu_list(&list));
+        if let (Some(p_cores), Some(e_cores)) = (read_cpus("/sys/devices/cpu_core/cpus"), read_cpus("/sys/devices/cpu_atom/cpus")) {
+            let l3_cpus: std::collections::HashSet<u32> = Self::cache_index_dirs().into_iter()
+                .filter(|(_, dir)| fs::read_to_string(dir.join("level")).is_ok_and(|level| level.trim() == "3"))
+                .map(|(cpu, _)| cpu)
+                .collect();
+            let has_l3 = |cpu: u32| l3_cpus.contains(&cpu);
+            // Only tell LP E-cores apart when the other cores do have an L3
+            let split_low_power = p_cores.iter().any(|&cpu| has_l3(cpu));
+
+            let mut kinds: Vec<(u32, CoreKind)> = p_cores.iter().map(|&cpu| (cpu, CoreKind::Performance)).collect();
+            kinds.extend(e_cores.iter().map(|&cpu| {
+                let kind = if split_low_power && !has_l3(cpu) { CoreKind::LowPower } else { CoreKind::Efficiency };
+                (cpu, kind)
+            }));
+            kinds.sort_unstable();
+            return kinds;
+        }
+
+        let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+            return Vec::new();
+        };
+        let mut capacities: Vec<(u32, u32)> = entries.flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let cpu = name.strip_prefix("cpu")?.parse::<u32>().ok()?;
+                let capacity = fs::read_to_string(entry.path().join("cpu_capacity")).ok()?.trim().parse::<u32>().ok()?;
+                Some((cpu, capacity))
+            })
+            .collect();
+        capacities.sort_unstable();
+
+        let mut tiers: Vec<u32> = capacities.iter().map(|&(_, capacity)| capacity).collect();
+        tiers.sort_unstable_by(|a, b| b.cmp(a));
+        tiers.dedup();
+        if tiers.len() < 2 {
+            return Vec::new();
+        }
+        capacities.iter()
+            .map(|&(cpu, capacity)| {
+                let kind = match tiers.iter().position(|&tier| tier == capacity) {
+                    Some(0) => CoreKind::Performance,
+                    Some(tier) if tier == tiers.len() - 1 && tiers.len() >= 3 => CoreKind::LowPower,
+                    _ => CoreKind::Efficiency,
+                };
+                (cpu, kind)
+            })
+            .collect()
+    }
+
+    /// Split the logical CPUs into groups for `--group-by`.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_by` - Dimension to group by
+    ///
+    /// # Returns
+    ///
+    /// Returns `(group name, CPU numbers)` pairs in group order, e.g.
+    /// `("Socket 0", [0, 1, 2, 3])`. Empty when sysfs doesn't expose the dimension,
+    /// or for core types on chips with a single core type.
+    fn cpu_groups(group_by: GroupBy) -> Vec<(String, Vec<u32>)> {
+        use std::collections::BTreeMap;
+
+        match group_by {
+            GroupBy::Socket => {
+                let mut sockets: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+                for entry in fs::read_dir("/sys/devices/system/cpu").into_iter().flatten().flatten() {
+                    let Some(cpu) = entry.file_name().to_str()
+                        .and_then(|name| name.strip_prefix("cpu"))
+                        .and_then(|n| n.parse::<u32>().ok()) else {
+                        continue;
+                    };
+                    if let Some(socket) = fs::read_to_string(entry.path().join("topology/physical_package_id")).ok()
+                        .and_then(|id| id.trim().parse::<u32>().ok()) {
+                        sockets.entry(socket).or_default().push(cpu);
+                    }
+                }
+                sockets.into_iter().map(|(socket, cpus)| (format!("Socket {}", socket), cpus)).collect()
+            }
+            GroupBy::CoreType => {
+                let mut kinds: BTreeMap<CoreKind, Vec<u32>> = BTreeMap::new();
+                for (cpu, kind) in Self::get_core_kinds() {
+                    kinds.entry(kind).or_default().push(cpu);
+                }
+                kinds.into_iter().map(|(kind, cpus)| (format!("{}s", kind.name()), cpus)).collect()
+            }
+            GroupBy::Numa => {
+                let mut nodes: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+                for entry in fs::read_dir("/sys/devices/system/node").into_iter().flatten().flatten() {
+                    let Some(node) = entry.file_name().to_str()
+                        .and_then(|name| name.strip_prefix("node"))
+                        .and_then(|n| n.parse::<u32>().ok()) else {
+                        continue;
+                    };
+                    // Memory-only nodes have no CPUs to report
+                    if let Some(cpus) = fs::read_to_string(entry.path().join("cpulist")).ok()
+                        .and_then(|list| Self::parse_cpu_list(&list)) {
+                        nodes.insert(node, cpus);
+                    }
+                }
+                nodes.into_iter().map(|(node, cpus)| (format!("Node {}", node), cpus)).collect()
+            }
+        }
+    }
+
+    /// Build the `--group-by` section: the cores, caches, and (with `--freq`) current
+    /// frequencies of each group.
+    ///
+    /// Each group starts with a header such as "Socket 0: CPUs 0-7,16-23 (8 cores)",
+    /// followed by indented lines. Caches are counted per distinct instance among the
+    /// group's CPUs, e.g. "L2 8× 2MB".
+    ///
+    /// # Arguments
+    ///
+    /// * `group_by` - Dimension to group by
+    /// * `opts` - Display options (`--freq`)
+    fn group_lines(group_by: GroupBy, opts: &DisplayOptions) -> Vec<String> {
+        use std::collections::{BTreeMap, HashSet};
+
+        let groups = Self::cpu_groups(group_by);
+        if groups.is_empty() {
+            return vec![match group_by {
+                GroupBy::CoreType => "Groups: single core type (not a hybrid CPU)".to_string(),
+                _ => format!("Groups: no {} information", group_by.name()),
+            }];
+        }
+        let cache_dirs = Self::cache_index_dirs();
+        let frequencies = if opts.freq { Self::get_core_frequencies() } else { Vec::new() };
+
+        let mut lines = Vec::new();
+        for (name, cpus) in groups {
+            let members: HashSet<u32> = cpus.iter().copied().collect();
+            let read = |path: std::path::PathBuf| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+
+            let cores: HashSet<(String, String)> = cpus.iter()
+                .filter_map(|cpu| {
+                    let topology = std::path::PathBuf::from(format!("/sys/devices/system/cpu/cpu{}/topology", cpu));
+                    Some((read(topology.join("physical_package_id"))?, read(topology.join("core_id"))?))
+                })
+                .collect();
+            let mut header = format!("{}: CPU{} {}", name, if cpus.len() == 1 { "" } else { "s" }, display::format_cpu_list(&cpus));
+            if !cores.is_empty() {
+                header.push_str(&format!(" ({} core{})", cores.len(), if cores.len() == 1 { "" } else { "s" }));
+            }
+            lines.push(header);
+
+            // (level, sysfs type) -> (size of one instance in KB, distinct cache identities)
+            let mut caches: BTreeMap<(u32, String), (u32, HashSet<String>)> = BTreeMap::new();
+            for (cpu, dir) in cache_dirs.iter().filter(|(cpu, _)| members.contains(cpu)) {
+                if let (Some((level, cache_type, identity)), Some(size)) = (
+                    Self::cache_signature(*cpu, dir),
+                    read(dir.join("size")).and_then(|s| Self::parse_cache_size(&s)),
+                ) {
+                    caches.entry((level, cache_type)).or_insert((size, HashSet::new())).1.insert(identity);
+                }
+            }
+            let cache_parts: Vec<String> = caches.iter()
+                .map(|((level, cache_type), (size, instances))| {
+                    let suffix = match cache_type.as_str() {
+                        "Data" => "d",
+                        "Instruction" => "i",
+                        _ => "",
+                    };
+                    format!("L{}{} {}× {}", level, suffix, instances.len(), display::format_cache_size(*size))
+                })
+                .collect();
+            if !cache_parts.is_empty() {
+                lines.push(format!("{}Caches: {}", display::FLAG_INDENT, cache_parts.join(", ")));
+            }
+
+            let group_frequencies: Vec<(u32, f32)> = frequencies.iter().copied()
+                .filter(|(cpu, _)| members.contains(cpu))
+                .collect();
+            if !group_frequencies.is_empty() {
+                lines.push(format!(
+                    "{}Core Frequencies: {}",
+                    display::FLAG_INDENT, display::format_core_frequencies(&group_frequencies),
+                ));
+            }
+        }
+        lines
+    }
+
+    /// Correlate NUMA nodes with physical packages (sockets).
+    ///
+    /// Reads each node's CPU list from `/sys/devices/system/node/node*/cpulist` and
+    /// looks up the `physical_package_id` of its CPUs. With sub-NUMA clustering
+    /// (Intel SNC, AMD NPS) a socket is split into several nodes, so there are more
+    /// nodes than sockets and every node still lies within one socket. Nodes without
+    /// CPUs (CXL or other memory-only nodes) are counted separately.
+    ///
+    /// # Returns
+    ///
+    /// Returns the topology, or `None` if sysfs doesn't expose NUMA nodes.
+    fn get_numa_topology() -> Option<NumaTopology> {
+        use std::collections::HashSet;
+
+        let mut topology = NumaTopology { nodes: 0, sockets: 0, memory_only: 0, nodes_within_socket: true };
+        let mut sockets: HashSet<u32> = HashSet::new();
+        for entry in fs::read_dir("/sys/devices/system/node").ok()?.flatten() {
+            let is_node = entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix("node"))
+                .is_some_and(|n| n.parse::<u32>().is_ok());
+            if !is_node {
+                continue;
+            }
+
+            let cpus = fs::read_to_string(entry.path().join("cpulist")).ok()
+                .and_then(|list| Self::parse_cpu_list(&list));
+            let Some(cpus) = cpus else {
+                topology.memory_only += 1;
+                continue;
+            };
+
+            let node_sockets: HashSet<u32> = cpus.iter()
+                .filter_map(|cpu| {
+                    fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/topology/physical_package_id", cpu)).ok()?
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+                .collect();
+            topology.nodes += 1;
+            topology.nodes_within_socket &= node_sockets.len() <= 1;
+            sockets.extend(node_sockets);
+        }
+
+        topology.sockets = sockets.len() as u32;
+        (topology.nodes > 0 && topology.sockets > 0).then_some(topology)
+    }
+
+    /// Format the NUMA line, e.g. "NUMA: 4 nodes across 2 sockets (SNC enabled)".
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options; single-node systems are only shown with `--verbose`
+    fn format_numa(&self, opts: &DisplayOptions) -> Option<String> {
+        let numa = self.numa.as_ref()?;
+        if numa.nodes == 1 && numa.memory_only == 0 && !opts.verbose {
+            return None;
+        }
+
+        let plural = |count: u32, word: &str| format!("{} {}{}", count, word, if count == 1 { "" } else { "s" });
+        let mut line = if numa.nodes == numa.sockets && numa.nodes_within_socket {
+            if numa.sockets > 1 {
+                format!("NUMA: {} (1 per socket)", plural(numa.nodes, "node"))
+            } else {
+                format!("NUMA: {}", plural(numa.nodes, "node"))
+            }
+        } else if numa.nodes > numa.sockets && numa.nodes_within_socket {
+            let clustering = match self.vendor_id() {
+                "GenuineIntel" => "SNC enabled".to_string(),
+                "AuthenticAMD" | "HygonGenuine" => format!("NPS{}", numa.nodes / numa.sockets),
+                _ => "sub-NUMA clustering".to_string(),
+            };
+            format!("NUMA: {} across {} ({})", plural(numa.nodes, "node"), plural(numa.sockets, "socket"), clustering)
+        } else {
+            format!("NUMA: {} across {}", plural(numa.nodes, "node"), plural(numa.sockets, "socket"))
+        };
+        if numa.memory_only > 0 {
+            line.push_str(&format!(", plus {} memory-only", numa.memory_only));
+        }
+        Some(line)
+    }
+
+    /// Describe how the processor package is split into dies or tiles.
+    ///
+    /// On AMD chiplet designs every CCX has its own L3, so the number of distinct L3
+    /// instances per socket, divided by the CCXs per CCD of the microarchitecture,
+    /// gives the CCD count. Intel tiles are fixed per microarchitecture and come
+    /// straight from the table. The CCD count is skipped under a hypervisor, whose
+    /// cache topology rarely matches the host, and whenever the numbers don't divide
+    /// evenly.
+    ///
+    /// # Returns
+    ///
+    /// Returns a line such as "Dies: 2 CCDs" or "Tiles: 4 (Compute/SoC/GPU/IO)", or
+    /// `None` when the layout is unknown.
+    fn format_dies(&self) -> Option<String> {
+        match uarch::package_layout(self.uarch?)? {
+            PackageLayout::Tiles(tiles) => Some(format!("Tiles: {} ({})", tiles.len(), tiles.join("/"))),
+            PackageLayout::Chiplets { ccx_per_ccd } => {
+                if self.hypervisor.is_some() {
+                    return None;
+                }
+                let l3_instances = *Self::count_cache_instances().get(&(3, "Unified".to_string()))?;
+                let sockets = Self::count_packages()?;
+                let per_ccd = sockets * ccx_per_ccd;
+                if l3_instances == 0 || !l3_instances.is_multiple_of(per_ccd) {
+                    return None;
+                }
+                let ccds = l3_instances / per_ccd;
+                Some(format!(
+                    "Dies: {} CCD{}{}",
+                    ccds, if ccds == 1 { "" } else { "s" }, if sockets > 1 { " per socket" } else { "" },
+                ))
+            }
+        }
+    }
+
+    /// Count the physical packages (sockets) from the sysfs topology.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of distinct `physical_package_id` values, or `None` if the
+    /// topology is unavailable.
+    fn count_packages() -> Option<u32> {
+        let packages: std::collections::HashSet<String> = fs::read_dir("/sys/devices/system/cpu").ok()?
+            .flatten()
+            .filter(|entry| entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|n| n.parse::<u32>().is_ok()))
+            .filter_map(|entry| fs::read_to_string(entry.path().join("topology/physical_package_id")).ok())
+            .map(|id| id.trim().to_string())
+            .collect();
+        (!packages.is_empty()).then_some(packages.len() as u32)
+    }
+
+    /// Expand a sysfs CPU list such as "0-3,8-11" into CPU numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `list` - CPU list in the kernel's list format
+    ///
+    /// # Returns
+    ///
+    /// Returns the CPU numbers, or `None` if the list is malformed or empty.
+    fn parse_cpu_list(list: &str) -> Option<Vec<u32>> {
+        let mut cpus = Vec::new();
+        for range in list.trim().split(',').filter(|r| !r.is_empty()) {
+            match range.split_once('-') {
+                Some((start, end)) => cpus.extend(start.parse::<u32>().ok()?..=end.parse::<u32>().ok()?),
+                None => cpus.push(range.parse::<u32>().ok()?),
+            }
+        }
+        (!cpus.is_empty()).then_some(cpus)
+    }
+
+    /// Count the CPUs in a sysfs CPU list such as "0-3,8-11".
+    ///
+    /// # Arguments
+    ///
+    /// * `list` - CPU list in the kernel's list format
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of CPUs, or `None` if the list is malformed.
+    fn count_cpu_list(list: &str) -> Option<u32> {
+        let mut count = 0;
+        for range in list.trim().split(',').filter(|r| !r.is_empty()) {
+            count += match range.split_once('-') {
+                Some((start, end)) => end.parse::<u32>().ok()?.checked_sub(start.parse::<u32>().ok()?)? + 1,
+                None => {
+                    range.parse::<u32>().ok()?;
+                    1
+                }
+            };
+        }
+        Some(count)
+    }
+
+    /// Parse cache size string from sysfs.
+    ///
+    /// This helper function parses cache size strings from sysfs files,
+    /// which can be in formats like "32K", "1024K", "32768K", etc.
+    ///
+    /// # Arguments
+    ///
+    /// * `size_str` - Cache size string from sysfs (e.g., "32K")
+    ///
+    /// # Returns
+    ///
+    /// Returns the cache size in kilobytes, or `None` if parsing fails. Empty and
+    /// zero sizes ("", "0K"), which some hypervisors expose, are also `None` so they
+    /// are treated as unknown rather than shown as "0KB".
+    fn parse_cache_size(size_str: &str) -> Option<u32> {
+        let size_kb = if let Some(kb) = size_str.strip_suffix('K') {
+            kb.parse::<u32>().ok()
+        } else if let Some(kb) = size_str.strip_suffix("KB") {
+            kb.parse::<u32>().ok()
+        } else {
+            // Try parsing as plain number (assume KB)
+            size_str.parse::<u32>().ok()
+        };
+        size_kb.filter(|&kb| kb > 0)
+    }
+
+    /// Get the logo for this CPU, or for `logo_override` if given.
+    ///
+    /// Falls back to the ARM logo on ARM32/ARM64 architectures when the vendor has no
+    /// logo of its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Display options (colors, logo palette)
+    fn get_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions) -> Option<Logo> {
+        let vendor_to_use = logo_override.unwrap_or(self.vendor_id());
+        let palette = opts.logo_colors.as_deref();
+        get_logo_for_vendor(vendor_to_use, opts.use_color(), palette, opts.background)
+            .or_else(|| {
+                // Check if architecture is ARM-based and fallback to ARM logo
+                if self.architecture.contains("arm") || self.architecture.contains("aarch64") {
+                    get_logo_for_vendor("ARM", opts.use_color(), palette, opts.background)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Get the formatted information lines for display.
+    ///
+    /// This helper function generates the formatted CPU information lines
+    /// that are used by both logo and no-logo display methods.
+    fn get_info_lines(&self, opts: &DisplayOptions) -> Vec<String> {
+        let mut lines = vec![
+            format!("Name: {}", self.format_model()),
+            format!("Architecture: {}", self.architecture),
+            format!("Byte Order: {}", self.byte_order),
+            format!("Vendor: {}", self.format_vendor()),
+            self.format_frequency(opts.freq_mode.unwrap_or(FreqMode::Max)),
+            format!("Cores: {} cores ({})", self.physical_cores, self.format_threads()),
+        ];
+
+        lines.extend(self.format_threads_per_core());
+        lines.extend(self.format_numa(opts));
+        lines.extend(self.format_dies());
+
+        if opts.compact_cache {
+            lines.extend(self.compact_cache_line());
+        } else {
+            lines.extend(self.cache_lines());
+        }
+
+        if let Some(group_by) = opts.group_by {
+            lines.extend(Self::group_lines(group_by, opts));
+        } else if opts.freq {
+            let frequencies = Self::get_core_frequencies();
+            if !frequencies.is_empty() {
+                lines.push(format!("Core Frequencies: {}", display::format_core_frequencies(&frequencies)));
+            }
+        }
+
+        if opts.msr
+            && let Some(line) = Self::effective_frequency() {
+            lines.push(format!("Effective Frequency: {}", line));
+        }
+
+        if opts.power {
+            if let Some(driver) = self.scaling_driver() {
+                lines.push(format!("Scaling Driver: {}", driver));
+            }
+            if let Some(bias) = Self::energy_perf_bias() {
+                lines.push(format!("Energy/Performance Bias: {}", bias));
+            }
+            lines.extend(Self::cppc_lines());
+            lines.extend(Self::idle_state_lines(&Self::get_idle_states()));
+        }
+
+        if opts.temp {
+            if let Some(celsius) = Self::get_temperature() {
+                lines.push(format!("Temperature: {:.0}°C", celsius));
+            }
+            if let Some(celsius) = Self::get_critical_temperature() {
+                lines.push(format!("Tjmax: {:.0}°C", celsius));
+            }
+        }
+
+        if let Some(status) = self.avx512_status() {
+            lines.push(format!("AVX-512: {}", status));
+        }
+
+        if let Some(status) = self.virtualization_status() {
+            lines.push(format!("Virtualization: {}", status));
+        }
+
+        if opts.show_security() {
+            let protection = flags::control_flow_protection(&self.flags);
+            if !protection.is_empty() {
+                lines.push(format!("Control-flow protection: {}", protection.join(", ")));
+            }
+            if let Some(risk) = Self::smt_risk() {
+                lines.push(format!("SMT Risk: {}", risk));
+            }
+        }
+
+        if let Some(hypervisor) = &self.hypervisor {
+            lines.push(format!("Hypervisor: {}", hypervisor));
+        }
+
+        if let Some(container) = &self.container {
+            lines.push(format!("Environment: {}", container));
+        }
+
+        if let Some(quota) = self.format_cpu_quota() {
+            lines.push(format!("CPU Quota: {}", quota));
+        }
+
+        if let Some(layer) = &self.translation_layer {
+            lines.push(format!("Translation Layer: {}", layer));
+        }
+
+        if let Some((achievable, rated)) = self.below_spec {
+            lines.push(format!(
+                "Warning: CPU may be running below rated specifications (max {:.3} GHz, rated base {:.3} GHz)",
+                achievable, rated,
+            ));
+        }
+
+        // The kernel may mask flags to the common subset on hybrid designs
+        if self.heterogeneous_isa {
+            lines.push("Heterogeneous ISA: cores expose different features".to_string());
+        }
+
+        if opts.verbose && let Some(socket) = &self.socket {
+            lines.push(format!("Socket: {}", socket));
+        }
+
+        // The serial uniquely identifies the device, so it is only shown on request
+        if opts.verbose && let Some(serial) = &self.serial {
+            lines.push(format!("Serial: {} (unique device ID, avoid sharing publicly)", serial));
+        }
+
+        if opts.verbose && let Some(bogomips) = self.bogomips {
+            lines.push(format!("BogoMIPS: {:.2}", bogomips));
+        }
+
+        if opts.verbose && let Some(clock) = self.base_clock() {
+            lines.push(format!("Base Clock: {}", clock));
+        }
+
+        if opts.verbose && let Some(xsave) = Self::xsave() {
+            lines.push(format!("XSAVE: {}", xsave));
+        }
+
+        if opts.verbose && let Some(frequency) = self.timer_frequency() {
+            lines.push(format!("Timer Frequency: {}", frequency));
+        }
+
+        if opts.verbose && let Some(cpus) = Self::isolated_cpus() {
+            lines.push(format!("Isolated CPUs: {}", cpus));
+        }
+
+        if opts.verbose && let Some(features) = Self::resource_control() {
+            lines.push(format!("Resource Control: {}", features));
+        }
+
+        if opts.verbose {
+            let avx512_subsets = flags::avx512_subsets(&self.flags);
+            if !avx512_subsets.is_empty() {
+                lines.push(format!("AVX-512 Subsets: {}", avx512_subsets.join(", ")));
+            }
+        }
+
+        if opts.verbose && let Some(status) = self.amx_status() {
+            lines.push(format!("AMX: {}", status));
+        }
+
+        if opts.show_memory() && let Some(sizes) = self.huge_page_sizes() {
+            lines.push(format!("Huge Pages: {}", sizes));
+        }
+
+        if opts.show_memory() && let Some(population) = Self::memory_population() {
+            lines.push(format!("Memory: {}", population));
+        }
+
+        if opts.show_memory() && let Some(addressing) = self.virtual_addressing() {
+            lines.push(format!("Virtual Addressing: {}", addressing));
+        }
+
+        if opts.show_cache_details() {
+            lines.extend(display::cache_detail_lines(&self.caches()));
+            if let Some(clflush) = self.format_clflush_size() {
+                lines.push(format!("CLFLUSH Size: {}", clflush));
+            }
+            if let Some(latency) = self.uarch.and_then(uarch::cache_latency) {
+                lines.push(format!(
+                    "Cache Latency: L1: ~{} cycles, L2: ~{} cycles, L3: ~{} cycles (typical, not measured)",
+                    latency.l1, latency.l2, latency.l3,
+                ));
+            }
+        }
+
+        if opts.verbose && let Some(uarch) = self.uarch {
+            lines.push(format!("Microarchitecture: {} ({})", uarch.name, uarch.codename));
+            if let Some(max_memory_gb) = uarch.max_memory_gb {
+                lines.push(format!("Max Memory: {} (manufacturer spec)", uarch::format_memory_gb(max_memory_gb)));
+            }
+        }
+
+        if opts.show_source {
+            self.annotate_sources(&mut lines);
+        }
+        display::relabel(&mut lines, opts);
+        lines
+    }
+
+    /// Format the frequency line for the selected `--freq-mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Which frequency to show; the label changes to match
+    fn format_frequency(&self, mode: FreqMode) -> String {
+        let clusters = self.frequency_clusters();
+        if mode == FreqMode::Max && clusters.len() > 1 {
+            let parts: Vec<String> = clusters.iter().enumerate()
+                .map(|(i, &(khz, cpus))| format!(
+                    "Cluster {}: {:.3} GHz ({} CPU{})",
+                    i, khz as f32 / 1_000_000.0, cpus, if cpus == 1 { "" } else { "s" },
+                ))
+                .collect();
+            return format!("Max Frequency: {}", parts.join(", "));
+        }
+
+        let (label, ghz) = match mode {
+            FreqMode::Base => ("Base Frequency", Self::get_base_frequency()),
+            FreqMode::Current => ("Current Frequency", Self::get_current_frequency()),
+            FreqMode::Max => ("Max Frequency", self.max_mhz),
+        };
+        match ghz {
+            // Per-cluster maximums are listed above; this catches per-core variation
+            // (preferred cores) hidden by a uniform scaling_max_freq
+            Some(ghz) if mode == FreqMode::Max && Self::max_frequency_varies() => {
+                format!("{}: {:.3} GHz (varies by core, see --freq)", label, ghz)
+            }
+            Some(ghz) => format!("{}: {:.3} GHz", label, ghz),
+            None => format!("{}: Unknown", label),
+        }
+    }
+
+    /// Check whether the hardware maximum frequency differs between CPUs.
+    ///
+    /// On CPUs with preferred cores (Intel Turbo Boost Max 3.0, AMD preferred cores
+    /// with amd-pstate) a few cores boost higher than the rest, which shows up as
+    /// different `cpuinfo_max_freq` values even when every CPU has the same
+    /// `scaling_max_freq`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if at least two CPUs report different maximums.
+    fn max_frequency_varies() -> bool {
+        let mut maximums = fs::read_dir("/sys/devices/system/cpu").into_iter().flatten().flatten()
+            .filter(|entry| entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|n| n.parse::<u32>().is_ok()))
+            .filter_map(|entry| fs::read_to_string(entry.path().join("cpufreq/cpuinfo_max_freq")).ok())
+            .filter_map(|khz| khz.trim().parse::<u64>().ok());
+        maximums.next().is_some_and(|first| maximums.any(|khz| khz != first))
+    }
+
+    /// Get the per-level cache lines (L1i, L1d, L1, L2, L3).
+    fn cache_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("L1i Size: {}", match self.l1i_size { 
+                Some((_, total)) => display::format_cache_size(total), 
+                None => "Unknown".to_string() 
+            }),
+            format!("L1d Size: {}", match self.l1d_size { 
+                Some((_, total)) => display::format_cache_size(total), 
+                None => "Unknown".to_string() 
+            }),
+            format!("L1 Size: {}", match (self.l1i_size, self.l1d_size) {
+                (Some((_, l1i_total)), Some((_, l1d_total))) => display::format_cache_size(l1i_total + l1d_total),
+                (Some((_, l1i_total)), None) => display::format_cache_size(l1i_total),
+                (None, Some((_, l1d_total))) => display::format_cache_size(l1d_total),
+                (None, None) => "Unknown".to_string()
+            }),
+            format!("L2 Size: {}", match self.l2_size { 
+                Some((_, total)) => display::format_cache_size(total), 
+                None => "Unknown".to_string() 
+            }),
+        ];
+        if let Some(hybrid_l2) = &self.hybrid_l2 {
+            lines.push(format!("L2 Per Core Type: {}", hybrid_l2));
+        }
+        lines.push(format!("L3 Size: {}", match self.l3_size { 
+            Some((_, total)) => display::format_cache_size(total), 
+            None => "Unknown".to_string() 
+        }));
+        lines
+    }
+
+    /// Get the `--compact-cache` summary line, dropping unknown levels.
+    fn compact_cache_line(&self) -> Option<String> {
+        let l1 = match (self.l1i_size, self.l1d_size) {
+            (None, None) => None,
+            (l1i, l1d) => Some(l1i.map_or(0, |(_, total)| total) + l1d.map_or(0, |(_, total)| total)),
+        };
+        display::compact_cache_line(&[
+            ("L1", l1),
+            ("L2", self.l2_size.map(|(_, total)| total)),
+            ("L3", self.l3_size.map(|(_, total)| total)),
+        ])
+    }
+
+    /// Append the source of each value to its line (`--show-source`).
+    ///
+    /// Produces lines such as "Max Frequency: 5.200 GHz [cpufreq scaling_max_freq]".
+    /// Lines whose value wasn't detected, and lines without a tracked source, are
+    /// left unchanged. The base and current frequencies (`--freq-mode`) are read
+    /// when the line is built, so their source is worked out here the same way.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - Information lines from `get_info_lines`
+    fn annotate_sources(&self, lines: &mut [String]) {
+        let sources = &self.sources;
+        for line in lines.iter_mut() {
+            let Some((label, value)) = line.split_once(": ") else { continue };
+            let source = match label {
+                "Name" => sources.model,
+                "Vendor" => sources.vendor,
+                "Architecture" => sources.architecture,
+                "Byte Order" => "compile-time target",
+                "Cores" => sources.cores,
+                "Threads per core" => sources.threads_per_core,
+                "Max Frequency" => sources.max_frequency,
+                "Base Frequency" if cpuid::base_frequency_mhz().is_some() => "CPUID leaf 0x16",
+                "Base Frequency" => "cpufreq base_frequency",
+                "Current Frequency" if Self::has_cpufreq_current() => "cpufreq scaling_cur_freq",
+                "Current Frequency" => "/proc/cpuinfo cpu MHz",
+                "L1i Size" | "L1d Size" | "L1 Size" | "L2 Size" | "L3 Size" | "Cache" => sources.caches,
+                "Socket" => sources.socket,
+                "Hypervisor" => sources.hypervisor,
+                _ => "",
+            };
+            if !source.is_empty() && value != "Unknown" {
+                line.push_str(&format!(" [{}]", source));
+            }
+        }
+    }
+
+    /// Format the model name, marking models emulated by the hypervisor.
+    ///
+    /// Under QEMU/KVM the guest sees a generic or named QEMU model (e.g. "QEMU Virtual
+    /// CPU") unless the VM uses `-cpu host`. Such models are annotated so they aren't
+    /// mistaken for the host CPU.
+    fn format_model(&self) -> String {
+        if self.hypervisor.is_some() && virt::is_emulated_model(&self.model) {
+            format!("{} (emulated CPU model)", self.model)
+        } else {
+            self.model.clone()
+        }
+    }
+
+    /// Describe AMX (Advanced Matrix Extensions) support.
+    ///
+    /// Lists the AMX subsets advertised in the flags and checks XCR0 bits 17-18
+    /// (XTILECFG/XTILEDATA), which the kernel sets once it supports the tile register
+    /// state. Without them AMX instructions fault even though the CPU has them.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some` status string such as "supported (tile, int8, bf16)", or `None`
+    /// when no AMX flags are present or the architecture is not x86.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn amx_status(&self) -> Option<String> {
+        const AMX_TILE_STATE: u64 = (1 << 17) | (1 << 18);
+
+        let subsets: Vec<&str> = self.flags.split_whitespace()
+            .filter_map(|flag| flag.strip_prefix("amx_"))
+            .collect();
+        if subsets.is_empty() {
+            return None;
+        }
+
+        let enabled = cpuid::xcr0().is_some_and(|xcr0| xcr0 & AMX_TILE_STATE == AMX_TILE_STATE);
+        if enabled {
+            Some(format!("supported ({})", subsets.join(", ")))
+        } else {
+            Some(format!("supported ({}), but tile state not enabled by OS", subsets.join(", ")))
+        }
+    }
+
+    /// Describe AMX support (not applicable off x86).
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    fn amx_status(&self) -> Option<String> {
+        None
+    }
+
+    /// Format the CLFLUSH line size, noting when it differs from the cache line size.
+    ///
+    /// CLFLUSH flushes one cache line, so the two sizes should always match; a
+    /// mismatch points at a hypervisor or firmware reporting inconsistent values,
+    /// which matters to code that aligns or flushes by cache line.
+    ///
+    /// # Returns
+    ///
+    /// Returns e.g. "64 B" or "64 B (mismatch: cache lines are 128 B)", or `None` when
+    /// /proc/cpuinfo doesn't report it.
+    fn format_clflush_size(&self) -> Option<String> {
+        let clflush = self.clflush_size?;
+        let line_size = self.caches().iter()
+            .filter(|cache| cache.level == 1)
+            .find_map(|cache| cache.line_size);
+        Some(match line_size {
+            Some(line_size) if line_size != clflush => format!("{} B (mismatch: cache lines are {} B)", clflush, line_size),
+            _ => format!("{} B", clflush),
+        })
+    }
+
+    /// Describe the XSAVE state components and the size of the XSAVE area.
+    ///
+    /// The area holds the register state saved on a context switch, so its size is a
+    /// direct measure of that cost. When the OS has enabled fewer components in XCR0
+    /// than the CPU supports (e.g. an older kernel without AMX support), the smaller
+    /// enabled size is shown too.
+    ///
+    /// # Returns
+    ///
+    /// Returns e.g. "2696 bytes (x87, SSE, AVX, AVX-512, PKRU)", or `None` off x86 or
+    /// without XSAVE.
+    fn xsave() -> Option<String> {
+        let info = cpuid::xsave_info()?;
+        let mut line = format!("{} bytes ({})", info.max_size, info.components().join(", "));
+        if info.enabled_size != 0 && info.enabled_size != info.max_size {
+            line.push_str(&format!(", {} bytes enabled", info.enabled_size));
+        }
+        Some(line)
+    }
+
+    /// Describe whether SMT exposes the system to L1TF or MDS.
+    ///
+    /// Both attacks leak data between the hardware threads of a core, so the kernel's
+    /// status in `/sys/devices/system/cpu/vulnerabilities/{l1tf,mds}` states the SMT
+    /// situation explicitly ("SMT vulnerable", "SMT disabled", or "SMT Host state
+    /// unknown" inside a VM) unless the CPU is not affected.
+    ///
+    /// # Returns
+    ///
+    /// Returns a status such as "live risk (L1TF, MDS)", or `None` when the files are
+    /// missing or don't mention SMT.
+    fn smt_risk() -> Option<String> {
+        let statuses: Vec<(&str, SmtStatus)> = [("L1TF", "l1tf"), ("MDS", "mds")].into_iter()
+            .filter_map(|(name, file)| {
+                let text = fs::read_to_string(format!("/sys/devices/system/cpu/vulnerabilities/{}", file)).ok()?;
+                Some((name, Self::parse_smt_status(&text)?))
+            })
+            .collect();
+        let worst = statuses.iter().map(|&(_, status)| status).max()?;
+        let names: Vec<&str> = statuses.iter()
+            .filter(|&&(_, status)| status == worst)
+            .map(|&(name, _)| name)
+            .collect();
+
+        Some(match worst {
+            SmtStatus::Vulnerable => format!("live risk ({} can leak data between sibling threads)", names.join(", ")),
+            SmtStatus::HostUnknown => format!("unknown ({} depend on the host's SMT state)", names.join(", ")),
+            SmtStatus::Disabled => "none (SMT disabled)".to_string(),
+            SmtStatus::NotAffected => "none (not affected by L1TF or MDS)".to_string(),
+        })
+    }
+
+    /// Interpret the SMT part of a kernel vulnerability status line.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - Contents of a `vulnerabilities/*` file, e.g.
+    ///   "Mitigation: Clear CPU buffers; SMT vulnerable"
+    fn parse_smt_status(status: &str) -> Option<SmtStatus> {
+        let status = status.trim();
+        if status.starts_with("Not affected") {
+            Some(SmtStatus::NotAffected)
+        } else if status.contains("SMT vulnerable") {
+            Some(SmtStatus::Vulnerable)
+        } else if status.contains("SMT Host state unknown") {
+            Some(SmtStatus::HostUnknown)
+        } else if status.contains("SMT disabled") {
+            Some(SmtStatus::Disabled)
+        } else {
+            None
+        }
+    }
+
+    /// Describe the reference clock (BCLK) the core frequency is a multiple of.
+    ///
+    /// Read from CPUID leaf 0x16 when the CPU reports it. Many CPUs (all AMD ones and
+    /// most hypervisors) leave the leaf empty; every Intel and AMD microarchitecture in
+    /// rcpufetch's table uses a 100 MHz reference clock, so for those it is shown as
+    /// assumed.
+    ///
+    /// # Returns
+    ///
+    /// Returns a string such as "100 MHz" or "100 MHz (assumed)", or `None` when the
+    /// clock is unknown or the architecture is not x86.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn base_clock(&self) -> Option<String> {
+        if let Some(mhz) = cpuid::bus_frequency_mhz() {
+            return Some(format!("{} MHz", mhz));
+        }
+        let modern = matches!(self.vendor_id(), "GenuineIntel" | "AuthenticAMD") && self.uarch.is_some();
+        modern.then(|| "100 MHz (assumed)".to_string())
+    }
+
+    /// Describe the reference clock (not applicable off x86).
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    fn base_clock(&self) -> Option<String> {
+        None
+    }
+
+    /// Read the vendor ID directly from CPUID leaf 0.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(String)` with the 12-character vendor ID (e.g. "GenuineIntel").
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn cpuid_vendor() -> Option<String> {
+        cpuid::vendor()
+    }
+
+    /// Read the vendor ID directly from CPUID leaf 0 (not applicable off x86).
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    fn cpuid_vendor() -> Option<String> {
+        None
+    }
+
+    /// Get the authoritative vendor ID: CPUID when available, otherwise /proc/cpuinfo.
+    fn vendor_id(&self) -> &str {
+        self.cpuid_vendor.as_deref().unwrap_or(&self.vendor)
+    }
+
+    /// Format the vendor, noting when /proc/cpuinfo disagrees with CPUID.
+    fn format_vendor(&self) -> String {
+        match &self.cpuid_vendor {
+            Some(cpuid_vendor) if *cpuid_vendor != self.vendor => {
+                format!("{} (cpuinfo reports {})", cpuid_vendor, self.vendor)
+            }
+            _ => self.vendor.clone(),
+        }
+    }
+
+    /// Describe the frequency of the ARM generic timer.
+    ///
+    /// The generic timer counts at a fixed rate unrelated to the CPU clock. The rate
+    /// is read from the `CNTFRQ_EL0` register, which Linux lets user space read on
+    /// AArch64. Firmware that leaves the register unset supplies the rate in the
+    /// `clock-frequency` property of the device tree's timer node instead.
+    ///
+    /// # Returns
+    ///
+    /// Returns a frequency such as "24 MHz" or "19.2 MHz", or `None` off ARM or when
+    /// neither source is readable.
+    fn timer_frequency(&self) -> Option<String> {
+        if !(self.architecture.starts_with("aarch64") || self.architecture.starts_with("arm")) {
+            return None;
+        }
+        let hz = Self::read_cntfrq().or_else(Self::read_device_tree_timer_frequency)?;
+        Some(format!("{} MHz", hz as f64 / 1_000_000.0))
+    }
+
+    /// Format the cgroup CPU quota against the number of logical CPUs.
+    ///
+    /// Quotas are CPU time on logical CPUs, so a quota of 16 uses every thread of an
+    /// 8-core SMT machine; comparing against physical cores would overstate the share.
+    ///
+    /// # Returns
+    ///
+    /// Returns e.g. "2.5 cores (of 16 logical CPUs)", or `None` when no quota is set.
+    fn format_cpu_quota(&self) -> Option<String> {
+        let quota = self.cpu_quota?;
+        let cores = format!("{:.2}", quota);
+        let cores = cores.trim_end_matches('0').trim_end_matches('.');
+        Some(format!(
+            "{} core{} (of {} logical CPUs)",
+            cores, if cores == "1" { "" } else { "s" }, self.logical_cores,
+        ))
+    }
+
+    /// Describe the cache and memory bandwidth controls exposed through resctrl.
+    ///
+    /// When the resctrl filesystem is mounted at `/sys/fs/resctrl`, its `info`
+    /// directory has one entry per resource the hardware can partition or monitor:
+    /// `L3`/`L2` for Cache Allocation Technology (split into `*CODE` and `*DATA` with
+    /// Code and Data Prioritization), `MB` for Memory Bandwidth Allocation, `SMBA` for
+    /// AMD's slow memory bandwidth allocation, and `L3_MON` for cache occupancy (CMT)
+    /// and memory bandwidth (MBM) monitoring.
+    ///
+    /// # Returns
+    ///
+    /// Returns e.g. "L3 CAT (CDP), MBA, CMT, MBM", or `None` when resctrl isn't
+    /// mounted or exposes nothing.
+    fn resource_control() -> Option<String> {
+        let info = std::path::Path::new("/sys/fs/resctrl/info");
+        let names: std::collections::HashSet<String> = fs::read_dir(info).ok()?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        let has = |name: &str| names.contains(name);
+
+        let mut features = Vec::new();
+        for level in ["L3", "L2"] {
+            let cdp = has(&format!("{}CODE", level));
+            if cdp {
+                features.push(format!("{} CAT (CDP)", level));
+            } else if has(level) {
+                features.push(format!("{} CAT", level));
+            }
+        }
+        if has("MB") {
+            features.push("MBA".to_string());
+        }
+        if has("SMBA") {
+            features.push("SMBA".to_string());
+        }
+        if has("L3_MON") {
+            let monitors = fs::read_to_string(info.join("L3_MON/mon_features")).unwrap_or_default();
+            if monitors.lines().any(|line| line.trim() == "llc_occupancy") {
+                features.push("CMT".to_string());
+            }
+            if monitors.lines().any(|line| line.trim().starts_with("mbm_")) {
+                features.push("MBM".to_string());
+            }
+        }
+
+        (!features.is_empty()).then(|| features.join(", "))
+    }
+
+    /// Describe the CPUs isolated from the scheduler on the kernel command line.
+    ///
+    /// Real-time and latency-sensitive setups reserve CPUs with `isolcpus=` (taken
+    /// out of the general scheduler) and `nohz_full=` (no scheduler tick while a
+    /// single task runs). Both are read from `/proc/cmdline`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a description such as "4-7 (isolcpus, nohz_full)", or `None` when no
+    /// isolation is configured.
+    fn isolated_cpus() -> Option<String> {
+        Self::parse_isolated_cpus(&fs::read_to_string("/proc/cmdline").ok()?)
+    }
+
+    /// Parse the `isolcpus=` and `nohz_full=` parameters of a kernel command line.
+    ///
+    /// `isolcpus` may start with flags (`isolcpus=domain,managed_irq,4-7`), which are
+    /// dropped so only the CPU list remains. Arguments after `--` belong to init and
+    /// are ignored. Identical lists are shown once with both parameter names.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmdline` - Contents of `/proc/cmdline`
+    ///
+    /// # Returns
+    ///
+    /// Returns a description such as "2-3 (isolcpus), 2-7 (nohz_full)", or `None`
+    /// when neither parameter lists any CPUs.
+    fn parse_isolated_cpus(cmdline: &str) -> Option<String> {
+        const ISOLCPUS_FLAGS: &[&str] = &["nohz", "domain", "managed_irq"];
+
+        let mut lists: Vec<(String, Vec<&str>)> = Vec::new();
+        for param in cmdline.split_whitespace().take_while(|&arg| arg != "--") {
+            let (name, cpus) = match param.split_once('=') {
+                Some(("isolcpus", value)) => ("isolcpus", value.split(',')
+                    .filter(|part| !ISOLCPUS_FLAGS.contains(part))
+                    .collect::<Vec<_>>()
+                    .join(",")),
+                Some(("nohz_full", value)) => ("nohz_full", value.to_string()),
+                _ => continue,
+            };
+            if cpus.is_empty() {
+                continue;
+            }
+            match lists.iter_mut().find(|(list, _)| *list == cpus) {
+                Some((_, names)) => names.push(name),
+                None => lists.push((cpus, vec![name])),
+            }
+        }
+
+        let parts: Vec<String> = lists.iter()
+            .map(|(cpus, names)| format!("{} ({})", cpus, names.join(", ")))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Read the generic timer frequency from `CNTFRQ_EL0`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the frequency in Hz, or `None` if the register reads as zero.
+    #[cfg(target_arch = "aarch64")]
+    fn read_cntfrq() -> Option<u64> {
+        let hz: u64;
+        // SAFETY: CNTFRQ_EL0 is readable at EL0 on Linux and reading it has no side effects.
+        unsafe {
+            std::arch::asm!("mrs {}, cntfrq_el0", out(reg) hz, options(nomem, nostack, preserves_flags));
+        }
+        (hz > 0).then_some(hz)
+    }
+
+    /// Read the generic timer frequency (not applicable off AArch64).
+    #[cfg(not(target_arch = "aarch64"))]
+    fn read_cntfrq() -> Option<u64> {
+        None
+    }
+
+    /// Read the generic timer frequency from the device tree.
+    ///
+    /// Looks for a `timer` node compatible with the ARM architected timer and reads
+    /// its `clock-frequency` property (a big-endian 32-bit value).
+    ///
+    /// # Returns
+    ///
+    /// Returns the frequency in Hz, or `None` if there is no such property.
+    fn read_device_tree_timer_frequency() -> Option<u64> {
+        fs::read_dir("/proc/device-tree").ok()?
+            .flatten()
+            .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.starts_with("timer")))
+            .filter(|entry| {
+                fs::read(entry.path().join("compatible"))
+                    .is_ok_and(|compatible| String::from_utf8_lossy(&compatible).contains("arm,armv"))
+            })
+            .find_map(|entry| {
+                let bytes = fs::read(entry.path().join("clock-frequency")).ok()?;
+                let hz = u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?);
+                (hz > 0).then_some(hz as u64)
+            })
+    }
+
+    /// Describe hardware virtualization support (VT-x or AMD-V).
+    ///
+    /// The `vmx`/`svm` flag shows the CPU supports it; `/dev/kvm` only exists once KVM
+    /// has initialized, which requires the extension to be enabled in firmware.
+    ///
+    /// # Returns
+    ///
+    /// Returns a status such as "VT-x (enabled)" or "AMD-V (disabled in BIOS or KVM
+    /// not loaded)", or `None` if the CPU doesn't advertise either extension (for
+    /// example inside a VM without nested virtualization).
+    fn virtualization_status(&self) -> Option<String> {
+        let has_flag = |name: &str| self.flags.split_whitespace().any(|flag| flag == name);
+        let extension = if has_flag("vmx") {
+            "VT-x"
+        } else if has_flag("svm") {
+            "AMD-V"
+        } else {
+            return None;
+        };
+
+        if std::path::Path::new("/dev/kvm").exists() {
+            Some(format!("{} (enabled)", extension))
+        } else {
+            Some(format!("{} (disabled in BIOS or KVM not loaded)", extension))
+        }
+    }
+
+    /// Describe whether AVX-512 is actually usable.
+    ///
+    /// On some CPUs (notably hybrid Intel parts) AVX-512 is advertised by CPUID or
+    /// listed in /proc/cpuinfo but the OS has not enabled the AVX-512 register state,
+    /// so programs cannot use it. This compares the advertised support against
+    /// `is_x86_feature_detected!`, which also checks OS enablement.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some` status string when AVX-512 is advertised anywhere, or `None`
+    /// when it is absent or the architecture is not x86.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn avx512_status(&self) -> Option<String> {
+        let in_cpuinfo = self.flags.split_whitespace().any(|f| f == "avx512f");
+        let in_cpuid = cpuid::has_avx512f();
+        let usable = std::arch::is_x86_feature_detected!("avx512f");
+
+        match (in_cpuinfo || in_cpuid, usable) {
+            (_, true) => Some("enabled".to_string()),
+            (true, false) => Some("present in CPUID but disabled by OS".to_string()),
+            (false, false) => None,
+        }
+    }
+
+    /// Describe whether AVX-512 is actually usable (not applicable off x86).
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    fn avx512_status(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Fraction of the rated base frequency below which the cpufreq cap is reported as
+/// running below spec. Kept low so ordinary power-saving limits don't trigger it.
+const BELOW_SPEC_RATIO: f32 = 0.75;
+
+/// Address of the IA32_MPERF MSR (counts at the base frequency while in C0).
+const MSR_IA32_MPERF: u64 = 0xE7;
+
+/// Address of the IA32_APERF MSR (counts at the actual frequency while in C0).
+const MSR_IA32_APERF: u64 = 0xE8;
+
+/// How long APERF/MPERF are sampled for `--msr`.
+const MSR_SAMPLE_WINDOW: Duration = Duration::from_millis(100);
+
+/// hwmon driver names of CPU temperature sensors.
+const CPU_TEMP_SENSORS: &[&str] = &["coretemp", "k10temp", "zenpower", "cpu_thermal"];
+
+/// SMT exposure reported by an L1TF or MDS vulnerability status, least severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SmtStatus {
+    NotAffected,
+    Disabled,
+    HostUnknown,
+    Vulnerable,
+}
+
+/// A CPU idle state reported by cpuidle (`/sys/devices/system/cpu/cpuN/cpuidle/stateN`).
+struct IdleState {
+    /// State name (e.g. "C6")
+    name: String,
+    /// Driver description (e.g. "MWAIT 0x20")
+    desc: String,
+    /// Exit latency in microseconds
+    latency_us: Option<u32>,
+    /// Whether the state has been disabled by the user or kernel
+    disabled: bool,
+}
+
+/// A cpufreq policy: CPUs sharing one clock (`/sys/devices/system/cpu/cpufreq/policyN`).
+struct FrequencyDomain {
+    /// CPUs in the policy's `related_cpus`
+    cpus: Vec<u32>,
+    /// Maximum frequency the policy allows, in kHz
+    max_khz: u64,
+}
+
+/// NUMA nodes correlated with physical packages (`/sys/devices/system/node`).
+struct NumaTopology {
+    /// Number of nodes with CPUs
+    nodes: u32,
+    /// Number of distinct physical packages the nodes' CPUs belong to
+    sockets: u32,
+    /// Number of nodes without CPUs (e.g. CXL memory expanders)
+    memory_only: u32,
+    /// Whether every node's CPUs lie within a single socket
+    nodes_within_socket: bool,
+}
+
+/// Intermediate struct for holding parsed CPU information from /proc/cpuinfo.
+///
+/// This struct is used internally during the parsing process to collect
+/// information before creating the final `LinuxCpuInfo` struct.
 struct ParsedCpuInfo {
     /// CPU model name
     model: String,
     /// CPU vendor ID
     vendor: String,
-    /// CPU flags string
+    /// CPU flags string (union across all processor blocks)
     flags: String,
     /// Number of physical CPU cores
     physical_cores: u32,
@@ -647,11 +2900,363 @@ struct ParsedCpuInfo {
     /// Maximum CPU frequency in GHz
     max_mhz: Option<f32>,
     /// L1 data cache information
-    l1d_size: Option<(u32, u32)>,
+    l1d_size: Option<(u32, u64)>,
     /// L1 instruction cache information
-    l1i_size: Option<(u32, u32)>,
+    l1i_size: Option<(u32, u64)>,
     /// L2 cache information
-    l2_size: Option<(u32, u32)>,
+    l2_size: Option<(u32, u64)>,
     /// L3 cache information
-    l3_size: Option<(u32, u32)>,
+    l3_size: Option<(u32, u64)>,
+    /// CPUID display family ("cpu family")
+    family: Option<u32>,
+    /// CPUID display model ("model")
+    cpu_model: Option<u32>,
+    /// SMBIOS socket designation (only read when SMBIOS was used as a fallback)
+    socket: Option<String>,
+    /// Board/SoC serial number ("Serial", e.g. on Raspberry Pi)
+    serial: Option<String>,
+    /// Whether processor blocks report different "flags"/"Features" sets
+    heterogeneous_isa: bool,
+    /// BogoMIPS of the first processor ("bogomips"/"BogoMIPS")
+    bogomips: Option<f32>,
+    /// Virtual address width from "address sizes" (x86 only)
+    virtual_address_bits: Option<u32>,
+    /// CLFLUSH line size in bytes from "clflush size" (x86 only)
+    clflush_size: Option<u32>,
+    /// Where the model, vendor, core counts, frequency, and caches came from
+    sources: FieldSources,
+}
+
+/// Where each displayed value was read from, for `--show-source`.
+///
+/// Empty strings mean the value wasn't detected.
+#[derive(Default)]
+struct FieldSources {
+    model: &'static str,
+    vendor: &'static str,
+    architecture: &'static str,
+    cores: &'static str,
+    threads_per_core: &'static str,
+    max_frequency: &'static str,
+    caches: &'static str,
+    socket: &'static str,
+    hypervisor: &'static str,
+}
+
+impl CpuInfo for LinuxCpuInfo {
+    /// Read the cache hierarchy of cpu0 from sysfs.
+    ///
+    /// Level, type, size, associativity, line size, and sharing come from
+    /// `/sys/devices/system/cpu/cpu0/cache/index*/`. Inclusivity isn't exported by
+    /// sysfs, so it's taken from CPUID on x86.
+    fn caches(&self) -> Vec<Cache> {
+        let parameters = cpuid::cache_parameters();
+        let instances = Self::count_cache_instances();
+        let read = |path: &std::path::Path, name: &str| {
+            fs::read_to_string(path.join(name)).ok().map(|s| s.trim().to_string())
+        };
+
+        let mut caches: Vec<Cache> = fs::read_dir("/sys/devices/system/cpu/cpu0/cache")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("index")))
+            .filter_map(|path| {
+                let level = read(&path, "level")?.parse::<u32>().ok()?;
+                let type_name = read(&path, "type")?;
+                let cache_type = match type_name.as_str() {
+                    "Data" => CacheType::Data,
+                    "Instruction" => CacheType::Instruction,
+                    _ => CacheType::Unified,
+                };
+                let size_kb = Self::parse_cache_size(&read(&path, "size")?)?;
+                let inclusive = parameters.iter()
+                    .find(|p| p.level == level && p.cache_type == cache_type)
+                    .map(|p| p.inclusive);
+                Some(Cache {
+                    level,
+                    cache_type,
+                    size_kb,
+                    associativity: read(&path, "ways_of_associativity").and_then(|s| s.parse().ok()).filter(|&w| w > 0),
+                    line_size: read(&path, "coherency_line_size").and_then(|s| s.parse().ok()).filter(|&l| l > 0),
+                    shared_cpus: read(&path, "shared_cpu_list").and_then(|s| Self::count_cpu_list(&s)),
+                    instances: instances.get(&(level, type_name)).copied(),
+                    inclusive,
+                })
+            })
+            .collect();
+
+        // The same cache may be listed under several index directories
+        caches.sort_by_key(|cache| (cache.level, cache.name()));
+        caches.dedup_by_key(|cache| (cache.level, cache.cache_type));
+        caches
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn force_architecture(&mut self, architecture: &str) {
+        self.architecture = architecture.to_string();
+        self.sources.architecture = "--force-arch";
+    }
+
+    fn flags(&self) -> &str {
+        &self.flags
+    }
+
+    fn max_frequency_ghz(&self) -> Option<f32> {
+        self.max_mhz
+    }
+
+    fn fingerprint_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("vendor", self.vendor_id().to_string()),
+            ("model", self.model.clone()),
+            ("architecture", self.architecture.clone()),
+            ("physical_cores", self.physical_cores.to_string()),
+            ("logical_cores", self.logical_cores.to_string()),
+            ("l1d_kb", cpu::canonical_size(self.l1d_size.map(|(_, total)| total))),
+            ("l1i_kb", cpu::canonical_size(self.l1i_size.map(|(_, total)| total))),
+            ("l2_kb", cpu::canonical_size(self.l2_size.map(|(_, total)| total))),
+            ("l3_kb", cpu::canonical_size(self.l3_size.map(|(_, total)| total))),
+            ("flags", cpu::canonical_flags(&self.flags)),
+        ]
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let threads_per_core = self.threads_per_core.iter()
+            .map(|&(threads, cores)| JsonValue::Object(vec![
+                ("threads".to_string(), threads.into()),
+                ("cores".to_string(), cores.into()),
+            ]))
+            .collect();
+        let frequency_domains = self.frequency_domains.iter()
+            .map(|domain| JsonValue::Object(vec![
+                ("cpus".to_string(), JsonValue::Array(domain.cpus.iter().map(|&cpu| cpu.into()).collect())),
+                ("max_frequency_ghz".to_string(), JsonValue::from_f32(domain.max_khz as f32 / 1_000_000.0)),
+            ]))
+            .collect();
+        let microarchitecture = self.uarch.map_or(JsonValue::Null, |uarch| JsonValue::Object(vec![
+            ("name".to_string(), uarch.name.into()),
+            ("codename".to_string(), uarch.codename.into()),
+            ("max_memory_gb".to_string(), JsonValue::optional(uarch.max_memory_gb)),
+        ]));
+
+        json::CommonFields {
+            model: &self.model,
+            vendor: self.vendor_id(),
+            architecture: Some(&self.architecture),
+            byte_order: Some(&self.byte_order),
+            physical_cores: Some(self.physical_cores),
+            logical_cores: self.logical_cores,
+            max_frequency_ghz: self.max_frequency_ghz(),
+            l1d_kb: self.l1d_size.map(|(_, total)| total),
+            l1i_kb: self.l1i_size.map(|(_, total)| total),
+            l2_kb: self.l2_size.map(|(_, total)| total),
+            l3_kb: self.l3_size.map(|(_, total)| total),
+            caches: &self.caches(),
+            flags: &self.flags,
+        }.into_json(vec![
+            ("threads_per_core".to_string(), JsonValue::Array(threads_per_core)),
+            ("frequency_domains".to_string(), JsonValue::Array(frequency_domains)),
+            ("heterogeneous_isa".to_string(), self.heterogeneous_isa.into()),
+            ("below_rated_spec".to_string(), self.below_spec.is_some().into()),
+            ("bogomips".to_string(), self.bogomips.map_or(JsonValue::Null, JsonValue::from_f32)),
+            ("virtual_address_bits".to_string(), JsonValue::optional(cpuid::linear_address_bits().or(self.virtual_address_bits))),
+            ("clflush_size".to_string(), JsonValue::optional(self.clflush_size)),
+            ("microarchitecture".to_string(), microarchitecture),
+            ("hypervisor".to_string(), JsonValue::optional(self.hypervisor.as_ref())),
+            ("container".to_string(), JsonValue::optional(self.container.as_ref())),
+            ("cpu_quota".to_string(), self.cpu_quota.map_or(JsonValue::Null, JsonValue::Number)),
+            ("translation_layer".to_string(), JsonValue::optional(self.translation_layer.as_ref())),
+        ])
+    }
+
+    /// Print the CPU information with an optional logo override.
+    ///
+    /// This function displays comprehensive CPU information in a formatted layout
+    /// alongside the specified vendor logo or the detected vendor logo if none is specified.
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Layout options (gap between logo and info)
+    fn display_info_with_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let (logo_lines, logo_colors) = self.get_logo(logo_override, opts)
+            .map_or_else(Default::default, |l| (l.lines, l.colors));
+        
+        let mut info_lines = self.get_info_lines(opts);
+
+        let left_margin = display::info_column_offset(&logo_lines, opts);
+        let total_width = opts.resolve_width(display::DEFAULT_WIDTH);
+        let wrap_width = total_width.saturating_sub(left_margin);
+        info_lines.extend(display::flag_lines(&self.flags, " ", wrap_width, opts));
+        info_lines.extend(Self::core_map_lines(wrap_width, opts));
+
+        display::print_side_by_side(&logo_lines, &logo_colors, &info_lines, opts);
+    }
+
+    /// Print the CPU information without any logo.
+    ///
+    /// This function displays comprehensive CPU information in a simple list format
+    /// without any vendor logo or side-by-side alignment.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options (verbose output, grouped flags)
+    fn display_info_no_logo(&self, opts: &DisplayOptions) {
+        let mut lines = self.get_info_lines(opts);
+        let wrap_width = opts.resolve_width(display::DEFAULT_NO_LOGO_WIDTH);
+        lines.extend(display::flag_lines(&self.flags, " ", wrap_width, opts));
+        lines.extend(Self::core_map_lines(wrap_width, opts));
+
+        // Print CPU information without logo
+        display::print_lines(&lines, opts);
+    }
+
+    /// Display CPU information as a self-contained HTML snippet (`--format html`).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Display options (colors, logo, verbose output)
+    fn display_info_html(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let logo_lines = if opts.no_logo {
+            Vec::new()
+        } else {
+            self.get_logo(logo_override, opts).map(|l| l.lines).unwrap_or_default()
+        };
+
+        let mut info_lines = self.get_info_lines(opts);
+        if !self.flags.is_empty() {
+            info_lines.extend(display::flag_lines(&self.flags, " ", usize::MAX, opts));
+        }
+
+        display::print_html(&logo_lines, &info_lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cpuinfo_unions_flags_of_differing_blocks() {
+        let content = "processor\t: 0\nvendor_id\t: GenuineIntel\nflags\t\t: fpu sse avx512f\n\n\
+                       processor\t: 1\nvendor_id\t: GenuineIntel\nflags\t\t: fpu sse avx_vnni\n";
+        let parsed = LinuxCpuInfo::parse_cpuinfo(content).unwrap();
+        assert_eq!(parsed.flags, "fpu sse avx512f avx_vnni");
+        assert!(parsed.heterogeneous_isa);
+        assert_eq!(parsed.logical_cores, 2);
+    }
+
+    #[test]
+    fn parse_cache_size_treats_empty_and_zero_as_unknown() {
+        assert_eq!(LinuxCpuInfo::parse_cache_size(""), None);
+        assert_eq!(LinuxCpuInfo::parse_cache_size("0K"), None);
+        assert_eq!(LinuxCpuInfo::parse_cache_size("0"), None);
+    }
+
+    #[test]
+    fn parse_cache_size_reads_kilobytes() {
+        assert_eq!(LinuxCpuInfo::parse_cache_size("32K"), Some(32));
+        assert_eq!(LinuxCpuInfo::parse_cache_size("1024KB"), Some(1024));
+    }
+
+    #[test]
+    fn sum_cache_sizes_skips_zero_sizes() {
+        // Some hypervisors expose an L2 index with a size of 0
+        let entries = vec![
+            cache_entry(0, 1, "Data", Some("0"), None, "32K"),
+            cache_entry(0, 2, "Unified", Some("0"), None, "0K"),
+        ];
+        let (l1d, _, l2, _) = LinuxCpuInfo::sum_cache_sizes(entries).unwrap();
+        assert_eq!(l1d, Some((0, 32)));
+        assert_eq!(l2, None);
+
+        let entries = vec![cache_entry(0, 2, "Unified", Some("0"), None, "0K")];
+        assert_eq!(LinuxCpuInfo::sum_cache_sizes(entries), None);
+    }
+
+    #[test]
+    fn virtual_addressing_shows_usable_width() {
+        assert_eq!(LinuxCpuInfo::format_virtual_addressing(57, true, true), "57-bit (5-level paging)");
+        assert_eq!(LinuxCpuInfo::format_virtual_addressing(57, true, false), "48-bit (4-level paging; CPU supports 57-bit)");
+        assert_eq!(LinuxCpuInfo::format_virtual_addressing(48, true, false), "48-bit (4-level paging)");
+        assert_eq!(LinuxCpuInfo::format_virtual_addressing(32, false, false), "32-bit");
+    }
+
+    /// A `(signature, size)` entry for `sum_cache_sizes`.
+    fn cache_entry(cpu: u32, level: u32, cache_type: &str, id: Option<&str>, shared_cpu_list: Option<&str>, size: &str) -> ((u32, String, String), String) {
+        let identity = LinuxCpuInfo::cache_identity(cpu, id.map(str::to_string), shared_cpu_list.map(str::to_string));
+        ((level, cache_type.to_string(), identity), size.to_string())
+    }
+
+    #[test]
+    fn sum_cache_sizes_counts_duplicate_ids_once() {
+        // Two SMT siblings share an L2 (id 0); the second core has its own (id 1),
+        // and all three entries share one L3 (id 0, a different level)
+        let entries = vec![
+            cache_entry(0, 2, "Unified", Some("0"), Some("0-1"), "1024K"),
+            cache_entry(1, 2, "Unified", Some("0"), Some("0-1"), "1024K"),
+            cache_entry(2, 2, "Unified", Some("1"), Some("2-3"), "1024K"),
+            cache_entry(0, 3, "Unified", Some("0"), Some("0-3"), "8192K"),
+            cache_entry(2, 3, "Unified", Some("0"), Some("0-3"), "8192K"),
+        ];
+        let (_, _, l2, l3) = LinuxCpuInfo::sum_cache_sizes(entries).unwrap();
+        assert_eq!(l2, Some((0, 2048)));
+        assert_eq!(l3, Some((0, 8192)));
+    }
+
+    #[test]
+    fn sum_cache_sizes_counts_duplicate_shared_cpu_lists_once() {
+        // Kernels without `id` identify caches by their sharing list
+        let entries = vec![
+            cache_entry(0, 1, "Data", None, Some("0-1"), "48K"),
+            cache_entry(1, 1, "Data", None, Some("0-1"), "48K"),
+            cache_entry(2, 1, "Data", None, Some("2-3"), "48K"),
+            cache_entry(3, 1, "Data", None, Some("2-3"), "48K"),
+            cache_entry(0, 1, "Instruction", None, None, "32K"),
+            cache_entry(1, 1, "Instruction", None, None, "32K"),
+        ];
+        let (l1d, l1i, l2, l3) = LinuxCpuInfo::sum_cache_sizes(entries).unwrap();
+        assert_eq!(l1d, Some((0, 96)));
+        // Without an id or sharing list each CPU's cache is counted
+        assert_eq!(l1i, Some((0, 64)));
+        assert_eq!((l2, l3), (None, None));
+    }
+
+    #[test]
+    fn format_l2_groups_counts_shared_instances_once() {
+        let l2 = |id: &str, size_kb, shared| (id.to_string(), size_kb, shared);
+        let e_cores = [l2("id 8", 2048, true), l2("id 8", 2048, true), l2("id 9", 2048, true), l2("id 9", 2048, true)];
+        assert_eq!(LinuxCpuInfo::format_l2_groups("E", &e_cores), ["2× 2MB shared (E-cluster)"]);
+
+        let p_cores = [l2("id 0", 1280, false), l2("id 1", 1280, false)];
+        assert_eq!(LinuxCpuInfo::format_l2_groups("P", &p_cores), ["2× 1.3MB (P)"]);
+    }
+
+    #[test]
+    fn format_l2_groups_splits_low_power_e_cores_by_size() {
+        let l2 = |id: &str, size_kb, shared| (id.to_string(), size_kb, shared);
+        let e_cores = [l2("id 8", 2048, true), l2("id 8", 2048, true), l2("id 9", 2048, true), l2("id 10", 1024, true)];
+        assert_eq!(LinuxCpuInfo::format_l2_groups("E", &e_cores), ["2× 2MB shared (E-cluster)", "1× 1MB shared (E-cluster)"]);
+    }
+
+    #[test]
+    fn detect_below_spec_flags_a_ceiling_under_three_quarters_of_base() {
+        assert_eq!(LinuxCpuInfo::detect_below_spec(Some(0.8), Some(3.0), false), Some((0.8, 3.0)));
+        assert_eq!(LinuxCpuInfo::detect_below_spec(Some(2.2), Some(3.0), false), Some((2.2, 3.0)));
+        assert_eq!(LinuxCpuInfo::detect_below_spec(Some(2.25), Some(3.0), false), None);
+        assert_eq!(LinuxCpuInfo::detect_below_spec(Some(4.7), Some(3.0), false), None);
+    }
+
+    #[test]
+    fn detect_below_spec_needs_both_values_and_bare_metal() {
+        assert_eq!(LinuxCpuInfo::detect_below_spec(None, Some(3.0), false), None);
+        assert_eq!(LinuxCpuInfo::detect_below_spec(Some(0.8), None, false), None);
+        assert_eq!(LinuxCpuInfo::detect_below_spec(Some(0.8), Some(3.0), true), None);
+    }
 }
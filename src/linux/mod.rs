@@ -1 +1,3 @@
-pub mod linux;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod linux;
+pub mod smbios;
@@ -0,0 +1,255 @@
+//! SMBIOS processor and memory information for rcpufetch.
+//!
+//! Some systems (notably ARM servers) expose a /proc/cpuinfo without a useful model
+//! name. The firmware's SMBIOS table usually still describes the processor, so this
+//! module decodes SMBIOS Type 4 (Processor Information) from the raw table exported
+//! by the kernel at `/sys/firmware/dmi/tables/DMI`. Type 17 (Memory Device) is
+//! decoded as well, to show how the memory slots and channels are populated.
+//!
+//! The table is normally readable by root only; without permission every lookup
+//! simply returns `None`.
+
+use std::fs;
+
+/// Raw SMBIOS structure table exported by the kernel.
+const DMI_TABLE_PATH: &str = "/sys/firmware/dmi/tables/DMI";
+
+/// SMBIOS structure type for Processor Information.
+const TYPE_PROCESSOR: u8 = 4;
+
+/// SMBIOS structure type for Memory Device.
+const TYPE_MEMORY_DEVICE: u8 = 17;
+
+/// SMBIOS structure type marking the end of the table.
+const TYPE_END_OF_TABLE: u8 = 127;
+
+/// Placeholder strings firmware vendors leave in unused fields.
+const PLACEHOLDERS: &[&str] = &[
+    "Not Specified",
+    "Unknown",
+    "To Be Filled By O.E.M.",
+    "Default string",
+    "None",
+];
+
+/// Processor information decoded from an SMBIOS Type 4 structure.
+#[derive(Debug, Default, Clone)]
+pub struct ProcessorInfo {
+    /// Socket designation (e.g. "CPU 0", "AM5")
+    pub socket: Option<String>,
+    /// Processor manufacturer (e.g. "Ampere(R)")
+    pub manufacturer: Option<String>,
+    /// Processor version, i.e. the model name (e.g. "Ampere(R) Altra(R) Processor")
+    pub version: Option<String>,
+}
+
+/// A memory slot decoded from an SMBIOS Type 17 structure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryDevice {
+    /// Installed size in MB: `Some(0)` for an empty slot, `None` if populated but
+    /// the size is unknown
+    pub size_mb: Option<u64>,
+    /// Key identifying the memory channel (e.g. "DIMM_A" or "P0 A"), if the slot's
+    /// locators name one; only meaningful for comparing slots of one system
+    pub channel: Option<String>,
+}
+
+/// One structure of a raw SMBIOS table.
+struct Structure<'a> {
+    /// Structure type (e.g. 4 for Processor Information)
+    structure_type: u8,
+    /// Formatted area, starting with the type, length, and handle
+    formatted: &'a [u8],
+    /// Strings of the structure's string set
+    strings: Vec<String>,
+}
+
+/// Read processor information for the first processor from the SMBIOS table.
+///
+/// # Returns
+///
+/// Returns `Some(ProcessorInfo)` if the table could be read and contains a Type 4
+/// structure, or `None` otherwise.
+pub fn read_processor_info() -> Option<ProcessorInfo> {
+    let table = fs::read(DMI_TABLE_PATH).ok()?;
+    parse_processor_info(&table)
+}
+
+/// Find and decode the first Type 4 structure in a raw SMBIOS table.
+///
+/// # Arguments
+///
+/// * `table` - Raw SMBIOS structure table
+///
+/// # Returns
+///
+/// Returns `Some(ProcessorInfo)` for the first processor, or `None` if there is none
+/// or the table is malformed.
+fn parse_processor_info(table: &[u8]) -> Option<ProcessorInfo> {
+    let processor = structures(table).into_iter().find(|s| s.structure_type == TYPE_PROCESSOR)?;
+    Some(ProcessorInfo {
+        socket: processor.string_at(0x04),
+        manufacturer: processor.string_at(0x07),
+        version: processor.string_at(0x10),
+    })
+}
+
+/// Read every memory slot from the SMBIOS table.
+///
+/// # Returns
+///
+/// Returns one `MemoryDevice` per RAM slot, empty ones included, or an empty `Vec`
+/// if the table can't be read.
+pub fn read_memory_devices() -> Vec<MemoryDevice> {
+    fs::read(DMI_TABLE_PATH).map_or_else(|_| Vec::new(), |table| parse_memory_devices(&table))
+}
+
+/// Decode the Type 17 structures of a raw SMBIOS table.
+///
+/// The size is a word at offset 0x0C: 0 means the slot is empty, 0xFFFF unknown,
+/// bit 15 selects KB instead of MB, and 0x7FFF defers to the Extended Size dword at
+/// offset 0x1C. ROM and flash devices (memory types 0x09-0x0D) are skipped.
+///
+/// # Arguments
+///
+/// * `table` - Raw SMBIOS structure table
+fn parse_memory_devices(table: &[u8]) -> Vec<MemoryDevice> {
+    structures(table).into_iter()
+        .filter(|s| s.structure_type == TYPE_MEMORY_DEVICE)
+        .filter(|s| !matches!(s.formatted.get(0x12), Some(0x09..=0x0D)))
+        .filter_map(|device| {
+            let word = |offset: usize| device.formatted.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+            let size_mb = match word(0x0C)? {
+                0xFFFF => None,
+                0x7FFF => device.formatted.get(0x1C..0x20)
+                    .map(|b| (u32::from_le_bytes([b[0], b[1], b[2], b[3]]) & 0x7FFF_FFFF) as u64),
+                size if size & 0x8000 != 0 => Some(((size & 0x7FFF) as u64).div_ceil(1024)),
+                size => Some(size as u64),
+            };
+            let locators = [device.string_at(0x10), device.string_at(0x11)];
+            Some(MemoryDevice { size_mb, channel: channel_from_locators(&locators) })
+        })
+        .collect()
+}
+
+/// Derive a channel key from a memory slot's device and bank locators.
+///
+/// Locators are free-form firmware strings. Two common conventions are recognized:
+/// an explicit channel ("P0 CHANNEL A", "ChannelA-DIMM0"), and a channel letter
+/// followed by the slot number in the device locator ("DIMM_A1", "CPU1_DIMM B2").
+/// The text before the channel is kept so that e.g. channel A of two sockets differ.
+///
+/// # Arguments
+///
+/// * `locators` - Device locator and bank locator, in that order
+fn channel_from_locators(locators: &[Option<String>]) -> Option<String> {
+    for locator in locators.iter().flatten() {
+        let upper = locator.to_uppercase();
+        if let Some(pos) = upper.find("CHANNEL") {
+            let id: String = upper[pos + "CHANNEL".len()..]
+                .trim_start_matches([' ', '_', '-'])
+                .chars()
+                .take_while(char::is_ascii_alphanumeric)
+                .collect();
+            if !id.is_empty() {
+                return Some(format!("{}{}", &upper[..pos], id));
+            }
+        }
+    }
+
+    let device = locators.first()?.as_ref()?.to_uppercase();
+    let token = device.rsplit([' ', '_', '-']).next()?;
+    let letter = token.chars().next().filter(char::is_ascii_alphabetic)?;
+    let slot = &token[1..];
+    (!slot.is_empty() && slot.chars().all(|c| c.is_ascii_digit()))
+        .then(|| format!("{}{}", &device[..device.len() - token.len()], letter))
+}
+
+/// Split a raw SMBIOS table into its structures.
+///
+/// Each structure is a formatted area (type, length, handle, fields) followed by a
+/// string set: null-terminated strings ending with an extra null byte. String fields
+/// in the formatted area are 1-based indexes into that set, with 0 meaning "none".
+/// Walking stops at the end-of-table structure or at the first malformed structure.
+///
+/// # Arguments
+///
+/// * `table` - Raw SMBIOS structure table
+fn structures(table: &[u8]) -> Vec<Structure<'_>> {
+    let mut structures = Vec::new();
+    let mut offset = 0;
+
+    while offset + 4 <= table.len() {
+        let structure_type = table[offset];
+        let length = table[offset + 1] as usize;
+        if length < 4 || offset + length > table.len() || structure_type == TYPE_END_OF_TABLE {
+            break;
+        }
+
+        let strings_start = offset + length;
+        let Some(strings_end) = find_string_set_end(table, strings_start) else {
+            break;
+        };
+        structures.push(Structure {
+            structure_type,
+            formatted: &table[offset..offset + length],
+            strings: parse_string_set(&table[strings_start..strings_end]),
+        });
+
+        // Skip the string set and its double-null terminator
+        offset = strings_end + 2;
+    }
+
+    structures
+}
+
+impl Structure<'_> {
+    /// Resolve the string referenced by the byte at `field` in the formatted area.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - Offset of the string index within the formatted area
+    fn string_at(&self, field: usize) -> Option<String> {
+        self.formatted.get(field).and_then(|&index| lookup_string(&self.strings, index))
+    }
+}
+
+/// Find the end of a structure's string set (the position of its double-null terminator).
+///
+/// # Arguments
+///
+/// * `table` - Raw SMBIOS structure table
+/// * `start` - Offset just past the structure's formatted area
+fn find_string_set_end(table: &[u8], start: usize) -> Option<usize> {
+    (start..table.len().saturating_sub(1))
+        .find(|&i| table[i] == 0 && table[i + 1] == 0)
+}
+
+/// Split a string set into its individual strings.
+///
+/// # Arguments
+///
+/// * `bytes` - String set bytes, without the final double-null terminator
+fn parse_string_set(bytes: &[u8]) -> Vec<String> {
+    if bytes.is_empty() {
+        return Vec::new();
+    }
+    bytes.split(|&b| b == 0)
+        .map(|s| String::from_utf8_lossy(s).trim().to_string())
+        .collect()
+}
+
+/// Resolve a 1-based string index, skipping empty and placeholder values.
+///
+/// # Arguments
+///
+/// * `strings` - Strings of the structure's string set
+/// * `index` - 1-based index from the formatted area (0 means no string)
+fn lookup_string(strings: &[String], index: u8) -> Option<String> {
+    let value = strings.get((index as usize).checked_sub(1)?)?;
+    if value.is_empty() || PLACEHOLDERS.iter().any(|p| p.eq_ignore_ascii_case(value)) {
+        None
+    } else {
+        Some(value.clone())
+    }
+}
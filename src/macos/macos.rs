@@ -6,9 +6,17 @@
 //! and CPU feature flags. All public items are documented following the standards
 //! outlined in CONTRIBUTING.md and the linux.rs example.
 
-use crate::art::logos::get_logo_lines_for_vendor;
+use crate::art::logos::get_logo_for_vendor;
+use crate::display::{self, DisplayOptions};
+use crate::cla::FreqMode;
+use crate::cpu::{self, Cache, CacheType, CpuInfo};
+use crate::cpuid;
+use crate::json::{self, JsonValue};
 use std::process::Command;
 
+/// Cache levels as `(L1, L2, L3)`, each `Option<(size_kb, count)>`.
+type CacheLevels = (Option<(u32, u32)>, Option<(u32, u32)>, Option<(u32, u32)>);
+
 /// Struct representing parsed macOS CPU information.
 ///
 /// Contains comprehensive CPU information gathered from sysctl and system commands,
@@ -34,6 +42,10 @@ pub struct MacOSCpuInfo {
     l2_size: Option<(u32, u32)>,
     /// L3 cache (size in KB, count)
     l3_size: Option<(u32, u32)>,
+    /// Cache line size in bytes (`hw.cachelinesize`)
+    cache_line_size: Option<u32>,
+    /// Maximum P-core and E-core cluster frequencies in GHz (Apple Silicon only)
+    cluster_max_ghz: (Option<f32>, Option<f32>), // (P-cores, E-cores)
     /// CPU feature flags and capabilities
     flags: String,
 }
@@ -90,6 +102,14 @@ impl MacOSCpuInfo {
         
         // Parse cache information - prefer detailed perflevel cache info for Apple Silicon
         let (l1_size, l2_size, l3_size) = Self::get_cache_info();
+        let cache_line_size = Self::get_sysctl_u32("hw.cachelinesize").ok();
+        
+        // Get per-cluster maximum frequencies (Apple Silicon only)
+        let cluster_max_ghz = if vendor == "Apple" {
+            Self::get_cluster_max_frequencies()
+        } else {
+            (None, None)
+        };
         
         // Get CPU flags
         let flags = Self::get_cpu_flags();
@@ -105,29 +125,12 @@ impl MacOSCpuInfo {
             l1_size,
             l2_size,
             l3_size,
+            cache_line_size,
+            cluster_max_ghz,
             flags,
         })
     }
     
-    /// Helper function to format cache size with appropriate units (KB or MB).
-    ///
-    /// Converts cache sizes above 1000KB to megabytes with decimal precision.
-    ///
-    /// # Arguments
-    ///
-    /// * `size_kb` - Cache size in kilobytes
-    ///
-    /// # Returns
-    ///
-    /// Returns a formatted string with appropriate units (e.g., "288KB" or "6.0MB")
-    fn format_cache_size(size_kb: u32) -> String {
-        if size_kb >= 1000 {
-            format!("{:.1}MB", size_kb as f32 / 1024.0)
-        } else {
-            format!("{}KB", size_kb)
-        }
-    }
-
     /// Helper function to get comprehensive cache information.
     ///
     /// Returns L1, L2, and L3 cache sizes and counts, using sysctl keys and
@@ -136,7 +139,7 @@ impl MacOSCpuInfo {
     /// # Returns
     ///
     /// Tuple of (L1, L2, L3) cache info as Option<(size_kb, count)>
-    fn get_cache_info() -> (Option<(u32, u32)>, Option<(u32, u32)>, Option<(u32, u32)>) {
+    fn get_cache_info() -> CacheLevels {
         // First try the traditional hw.cachesize approach
         let cache_sizes = Self::get_sysctl_string("hw.cachesize").unwrap_or_default();
         let cache_config = Self::get_sysctl_string("hw.cacheconfig").unwrap_or_default();
@@ -187,6 +190,43 @@ impl MacOSCpuInfo {
         (l1_size, l2_size, l3_size)
     }
 
+    /// Get the maximum P-core and E-core cluster frequencies on Apple Silicon.
+    ///
+    /// The power manager (`pmgr`) node in the IORegistry lists each cluster's DVFS
+    /// states as `voltage-states*-sram` data: pairs of little-endian u32 values
+    /// (frequency in Hz, voltage). `voltage-states5-sram` describes the P-cluster and
+    /// `voltage-states1-sram` the E-cluster.
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (P-core, E-core) maximum frequencies in GHz; either is `None` if the
+    /// property isn't readable.
+    fn get_cluster_max_frequencies() -> (Option<f32>, Option<f32>) {
+        let output = Command::new("ioreg")
+            .args(["-r", "-d", "1", "-n", "pmgr"])
+            .output();
+        let ioreg = match output {
+            Ok(result) if result.status.success() => String::from_utf8_lossy(&result.stdout).to_string(),
+            _ => return (None, None),
+        };
+        
+        let max_ghz = |property: &str| {
+            let key = format!("\"{}\" = <", property);
+            let line = ioreg.lines().find(|line| line.contains(&key))?;
+            let hex = line.split_once(&key)?.1.split('>').next()?;
+            let bytes: Vec<u8> = (0..hex.len() / 2)
+                .filter_map(|i| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok())
+                .collect();
+            bytes.chunks_exact(8)
+                .map(|state| u32::from_le_bytes([state[0], state[1], state[2], state[3]]))
+                .max()
+                .filter(|&hz| hz > 0)
+                .map(|hz| hz as f32 / 1_000_000_000.0)
+        };
+        
+        (max_ghz("voltage-states5-sram"), max_ghz("voltage-states1-sram"))
+    }
+
     /// Helper function to get a string value from sysctl.
     ///
     /// # Arguments
@@ -227,6 +267,24 @@ impl MacOSCpuInfo {
             .map_err(|e| format!("Failed to parse '{}' as u32: {}", value_str, e))
     }
 
+    /// Helper function to read a frequency sysctl reported in Hz.
+    ///
+    /// `hw.cpufrequency*` keys are only provided on Intel Macs.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sysctl key to query (e.g. "hw.cpufrequency_max")
+    ///
+    /// # Returns
+    ///
+    /// `Some(f32)` with the frequency in MHz, or `None` if the key is unavailable.
+    fn get_sysctl_mhz(key: &str) -> Option<f32> {
+        Self::get_sysctl_string(key)
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(|hz| hz as f32 / 1_000_000.0)
+    }
+
     /// Get system architecture using uname -m.
     ///
     /// # Returns
@@ -285,115 +343,6 @@ impl MacOSCpuInfo {
         }
     }
 
-    /// Display CPU information with logo (side-by-side layout).
-    ///
-    /// Displays comprehensive CPU information alongside a vendor logo in a side-by-side layout.
-    /// The logo can be overridden to display a different vendor's logo regardless of the actual CPU vendor.
-    ///
-    /// # Arguments
-    ///
-    /// * `logo_override` - Optional vendor ID to override the detected logo
-    pub fn display_info_with_logo(&self, logo_override: Option<&str>) {
-        let vendor_to_use = logo_override.unwrap_or(&self.vendor);
-        let logo_lines = get_logo_lines_for_vendor(vendor_to_use).unwrap_or_else(|| vec![]);
-        
-        let mut info_lines = self.get_info_lines();
-        
-        // Handle flags wrapping
-        if !self.flags.is_empty() {
-            let logo_width = logo_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
-            let sep = "   ";
-            let left_margin = logo_width + sep.len();
-            let total_width = 100; // Terminal width
-            let wrap_width = total_width - left_margin;
-            
-            // Wrap flags text
-            let flag_label = "Flags: ";
-            let indent = "       "; // 7 spaces to align with "Flags: "
-            let mut flag_lines = Vec::new();
-            let mut current_line = String::from(flag_label);
-            
-            for word in self.flags.split(',') {
-                let word = word.trim();
-                if current_line.len() + word.len() + 2 > wrap_width { // +2 for ", "
-                    flag_lines.push(current_line);
-                    current_line = format!("{}{}", indent, word);
-                } else {
-                    if current_line.trim_end().ends_with(":") {
-                        current_line.push_str(word);
-                    } else {
-                        current_line.push_str(", ");
-                        current_line.push_str(word);
-                    }
-                }
-            }
-            if !current_line.trim().is_empty() {
-                flag_lines.push(current_line);
-            }
-            
-            // Add flag lines to info_lines
-            info_lines.extend(flag_lines);
-        }
-        
-        let logo_width = logo_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
-        let sep = "   ";
-        let max_lines = std::cmp::max(logo_lines.len(), info_lines.len());
-
-        // Print logo and info side by side
-        for i in 0..max_lines {
-            let logo = logo_lines.get(i).map(|s| s.as_str()).unwrap_or("");
-            let mut info = info_lines.get(i).map(|s| s.as_str()).unwrap_or("").to_string();
-            
-            // If there's no logo content on this line, remove the indent from flag lines
-            let indent = "       "; // 7 spaces to align with "Flags: "
-            if logo.is_empty() && info.starts_with(indent) {
-                info = info[indent.len()..].to_string();
-            }
-            
-            println!("{:<width$}{}{}", logo, sep, info, width=logo_width);
-        }
-    }
-
-    /// Display CPU information without any logo.
-    ///
-    /// Displays comprehensive CPU information in a simple list format without any vendor logo
-    /// or side-by-side alignment. Flags are wrapped for readability.
-    pub fn display_info_no_logo(&self) {
-        let info_lines = self.get_info_lines();
-        
-        // Print CPU information without logo
-        for line in info_lines {
-            println!("{}", line);
-        }
-        
-        // Print flags with wrapping
-        if !self.flags.is_empty() {
-            print!("Flags: ");
-            let wrap_width = 80; // Standard terminal width
-            let mut current_line_len = 7; // "Flags: " length
-            let mut first_flag = true;
-            
-            for word in self.flags.split(',') {
-                let word = word.trim();
-                if !first_flag && current_line_len + word.len() + 2 > wrap_width { // +2 for ", "
-                    println!();
-                    print!("       {}", word); // 7 spaces to align with "Flags: "
-                    current_line_len = 7 + word.len();
-                } else {
-                    if first_flag {
-                        print!("{}", word);
-                        current_line_len += word.len();
-                        first_flag = false;
-                    } else {
-                        print!(", {}", word);
-                        current_line_len += word.len() + 2; // +2 for ", "
-                    }
-                }
-            }
-            println!(); // Final newline
-        }
-    }
-
     /// Get the formatted information lines for display.
     ///
     /// Generates the formatted CPU information lines that are used by both logo and no-logo
@@ -402,7 +351,7 @@ impl MacOSCpuInfo {
     /// # Returns
     ///
     /// Vector of formatted information lines as strings.
-    fn get_info_lines(&self) -> Vec<String> {
+    fn get_info_lines(&self, opts: &DisplayOptions) -> Vec<String> {
         let mut lines = vec![
             format!("Name: {}", self.model),
             format!("Architecture: {}", self.architecture),
@@ -411,55 +360,241 @@ impl MacOSCpuInfo {
             format!("Cores: {} cores ({} threads)", self.physical_cores, self.logical_cores),
         ];
         
-        if let Some(mhz) = self.base_mhz {
-            lines.push(format!("Base Frequency: {:.2} MHz", mhz));
+        match opts.freq_mode {
+            None | Some(FreqMode::Base) => {
+                if let Some(mhz) = self.base_mhz {
+                    lines.push(format!("Base Frequency: {:.2} MHz", mhz));
+                }
+            }
+            Some(FreqMode::Current) => {
+                if let Some(mhz) = Self::get_sysctl_mhz("hw.cpufrequency") {
+                    lines.push(format!("Current Frequency: {:.2} MHz", mhz));
+                }
+            }
+            Some(FreqMode::Max) => {
+                if let Some(mhz) = Self::get_sysctl_mhz("hw.cpufrequency_max") {
+                    lines.push(format!("Max Frequency: {:.2} MHz", mhz));
+                }
+            }
+        }
+        
+        match self.cluster_max_ghz {
+            (Some(p_ghz), Some(e_ghz)) => lines.push(format!("P-Core Max: {:.1} GHz, E-Core Max: {:.1} GHz", p_ghz, e_ghz)),
+            (Some(p_ghz), None) => lines.push(format!("P-Core Max: {:.1} GHz", p_ghz)),
+            (None, Some(e_ghz)) => lines.push(format!("E-Core Max: {:.1} GHz", e_ghz)),
+            (None, None) => {}
         }
         
-        // For Apple Silicon, provide more detailed cache information
-        if self.vendor == "Apple" {
+        if opts.compact_cache {
+            lines.extend(display::compact_cache_line(&[
+                ("L1", self.l1_size.map(|(size, _)| size.into())),
+                ("L2", self.l2_size.map(|(size, _)| size.into())),
+                ("L3", self.l3_size.map(|(size, _)| size.into())),
+            ]));
+        } else if self.vendor == "Apple" {
+            // For Apple Silicon, provide more detailed cache information
             // Try to get performance level specific cache info
-            if let Ok(perf0_l1i) = Self::get_sysctl_u32("hw.perflevel0.l1icachesize") {
-                if let Ok(perf0_l1d) = Self::get_sysctl_u32("hw.perflevel0.l1dcachesize") {
-                    let l1i_formatted = Self::format_cache_size(perf0_l1i / 1024);
-                    let l1d_formatted = Self::format_cache_size(perf0_l1d / 1024);
-                    lines.push(format!("P-Core L1 Cache: {} I + {} D", l1i_formatted, l1d_formatted));
-                }
+            if let Ok(perf0_l1i) = Self::get_sysctl_u32("hw.perflevel0.l1icachesize")
+                && let Ok(perf0_l1d) = Self::get_sysctl_u32("hw.perflevel0.l1dcachesize") {
+                let l1i_formatted = display::format_cache_size(perf0_l1i / 1024);
+                let l1d_formatted = display::format_cache_size(perf0_l1d / 1024);
+                lines.push(format!("P-Core L1 Cache: {} I + {} D", l1i_formatted, l1d_formatted));
             }
-            if let Ok(perf1_l1i) = Self::get_sysctl_u32("hw.perflevel1.l1icachesize") {
-                if let Ok(perf1_l1d) = Self::get_sysctl_u32("hw.perflevel1.l1dcachesize") {
-                    let l1i_formatted = Self::format_cache_size(perf1_l1i / 1024);
-                    let l1d_formatted = Self::format_cache_size(perf1_l1d / 1024);
-                    lines.push(format!("E-Core L1 Cache: {} I + {} D", l1i_formatted, l1d_formatted));
-                }
+            if let Ok(perf1_l1i) = Self::get_sysctl_u32("hw.perflevel1.l1icachesize")
+                && let Ok(perf1_l1d) = Self::get_sysctl_u32("hw.perflevel1.l1dcachesize") {
+                let l1i_formatted = display::format_cache_size(perf1_l1i / 1024);
+                let l1d_formatted = display::format_cache_size(perf1_l1d / 1024);
+                lines.push(format!("E-Core L1 Cache: {} I + {} D", l1i_formatted, l1d_formatted));
             }
             if let Ok(perf0_l2) = Self::get_sysctl_u32("hw.perflevel0.l2cachesize") {
-                let l2_formatted = Self::format_cache_size(perf0_l2 / 1024);
+                let l2_formatted = display::format_cache_size(perf0_l2 / 1024);
                 lines.push(format!("P-Core L2 Cache: {}", l2_formatted));
             }
             if let Ok(perf1_l2) = Self::get_sysctl_u32("hw.perflevel1.l2cachesize") {
-                let l2_formatted = Self::format_cache_size(perf1_l2 / 1024);
+                let l2_formatted = display::format_cache_size(perf1_l2 / 1024);
                 lines.push(format!("E-Core L2 Cache: {}", l2_formatted));
             }
         } else {
             // For non-Apple systems, use traditional cache display
             if let Some((l1, l1_count)) = self.l1_size {
-                let l1_formatted = Self::format_cache_size(l1);
+                let l1_formatted = display::format_cache_size(l1);
                 lines.push(format!("L1 Cache Size: {} ({} cores)", l1_formatted, l1_count));
             }
             
             if let Some((l2, l2_count)) = self.l2_size {
-                let l2_formatted = Self::format_cache_size(l2);
+                let l2_formatted = display::format_cache_size(l2);
                 lines.push(format!("L2 Cache Size: {} ({} cores)", l2_formatted, l2_count));
             }
             
             if let Some((l3, l3_count)) = self.l3_size {
-                let l3_formatted = Self::format_cache_size(l3);
+                let l3_formatted = display::format_cache_size(l3);
                 lines.push(format!("L3 Cache Size: {} ({} cores)", l3_formatted, l3_count));
             }
         }
         
+        if let Some(line_size) = self.cache_line_size {
+            lines.push(format!("Cache line: {} B", line_size));
+        }
+        
+        if opts.show_cache_details() {
+            lines.extend(display::cache_detail_lines(&self.caches()));
+        }
+        
         // Don't add flags here - they will be handled separately with wrapping
         
+        display::relabel(&mut lines, opts);
         lines
     }
-}
\ No newline at end of file
+}
+
+impl CpuInfo for MacOSCpuInfo {
+    /// Build the cache hierarchy from `hw.cachesize` and `hw.cacheconfig`.
+    ///
+    /// `hw.cachesize` reports the L1 data cache for level 1, `hw.cacheconfig` reports
+    /// how many logical CPUs share each level, and `hw.cachelinesize` gives the line
+    /// size. Associativity and inclusivity are not available from sysctl; on Intel
+    /// Macs they come from the CPUID deterministic cache parameters leaf, which Apple
+    /// Silicon (including Rosetta 2) doesn't provide.
+    fn caches(&self) -> Vec<Cache> {
+        let parameters = if self.vendor == "Apple" { Vec::new() } else { cpuid::cache_parameters() };
+        [
+            (1, CacheType::Data, self.l1_size),
+            (2, CacheType::Unified, self.l2_size),
+            (3, CacheType::Unified, self.l3_size),
+        ]
+        .into_iter()
+        .filter_map(|(level, cache_type, size)| {
+            let geometry = parameters.iter().find(|p| p.level == level && p.cache_type == cache_type);
+            size.map(|(size_kb, shared_cpus)| Cache {
+                level,
+                cache_type,
+                size_kb,
+                associativity: geometry.map(|p| p.ways),
+                line_size: self.cache_line_size.or(geometry.map(|p| p.line_size)),
+                shared_cpus: Some(shared_cpus),
+                instances: None,
+                inclusive: geometry.map(|p| p.inclusive),
+            })
+        })
+        .collect()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn force_architecture(&mut self, architecture: &str) {
+        self.architecture = architecture.to_string();
+    }
+
+    fn flags(&self) -> &str {
+        &self.flags
+    }
+
+    fn max_frequency_ghz(&self) -> Option<f32> {
+        self.cluster_max_ghz.0.or(self.base_mhz.map(|mhz| mhz / 1000.0))
+    }
+
+    fn fingerprint_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("vendor", self.vendor.clone()),
+            ("model", self.model.clone()),
+            ("architecture", self.architecture.clone()),
+            ("physical_cores", self.physical_cores.to_string()),
+            ("logical_cores", self.logical_cores.to_string()),
+            ("l1_kb", cpu::canonical_size(self.l1_size.map(|(size, _)| size))),
+            ("l2_kb", cpu::canonical_size(self.l2_size.map(|(size, _)| size))),
+            ("l3_kb", cpu::canonical_size(self.l3_size.map(|(size, _)| size))),
+            ("flags", cpu::canonical_flags(&self.flags)),
+        ]
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let caches = self.caches();
+        json::CommonFields {
+            model: &self.model,
+            vendor: &self.vendor,
+            architecture: Some(&self.architecture),
+            byte_order: Some(&self.byte_order),
+            physical_cores: Some(self.physical_cores),
+            logical_cores: self.logical_cores,
+            max_frequency_ghz: self.max_frequency_ghz(),
+            l1d_kb: json::cache_total_kb(&caches, "L1d"),
+            l1i_kb: json::cache_total_kb(&caches, "L1i"),
+            l2_kb: json::cache_total_kb(&caches, "L2"),
+            l3_kb: json::cache_total_kb(&caches, "L3"),
+            caches: &caches,
+            flags: &self.flags,
+        }.into_json(vec![
+            ("base_frequency_mhz".to_string(), self.base_mhz.map_or(JsonValue::Null, JsonValue::from_f32)),
+            ("e_core_max_ghz".to_string(), self.cluster_max_ghz.1.map_or(JsonValue::Null, JsonValue::from_f32)),
+        ])
+    }
+
+    /// Display CPU information with logo (side-by-side layout).
+    ///
+    /// Displays comprehensive CPU information alongside a vendor logo in a side-by-side layout.
+    /// The logo can be overridden to display a different vendor's logo regardless of the actual CPU vendor.
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected logo
+    /// * `opts` - Layout options (gap between logo and info)
+    fn display_info_with_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let vendor_to_use = logo_override.unwrap_or(&self.vendor);
+        let (logo_lines, logo_colors) = get_logo_for_vendor(vendor_to_use, opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+            .map_or_else(Default::default, |l| (l.lines, l.colors));
+        
+        let mut info_lines = self.get_info_lines(opts);
+        
+        // Handle flags wrapping
+        if !self.flags.is_empty() {
+            let wrap_width = opts.resolve_width(display::DEFAULT_WIDTH).saturating_sub(display::info_column_offset(&logo_lines, opts));
+            info_lines.extend(display::flag_lines(&self.flags, ", ", wrap_width, opts));
+        }
+        
+        display::print_side_by_side(&logo_lines, &logo_colors, &info_lines, opts);
+    }
+
+    /// Display CPU information without any logo.
+    ///
+    /// Displays comprehensive CPU information in a simple list format without any vendor logo
+    /// or side-by-side alignment. Flags are wrapped for readability.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options (grouped flags)
+    fn display_info_no_logo(&self, opts: &DisplayOptions) {
+        let mut lines = self.get_info_lines(opts);
+        if !self.flags.is_empty() {
+            let wrap_width = opts.resolve_width(display::DEFAULT_NO_LOGO_WIDTH);
+            lines.extend(display::flag_lines(&self.flags, ", ", wrap_width, opts));
+        }
+
+        // Print CPU information without logo
+        display::print_lines(&lines, opts);
+    }
+
+    /// Display CPU information as a self-contained HTML snippet (`--format html`).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Display options (colors, logo)
+    fn display_info_html(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let logo_lines = if opts.no_logo {
+            Vec::new()
+        } else {
+            get_logo_for_vendor(logo_override.unwrap_or(&self.vendor), opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+                .map(|l| l.lines)
+                .unwrap_or_default()
+        };
+
+        let mut info_lines = self.get_info_lines(opts);
+        if !self.flags.is_empty() {
+            info_lines.extend(display::flag_lines(&self.flags, ", ", usize::MAX, opts));
+        }
+
+        display::print_html(&logo_lines, &info_lines);
+    }
+}
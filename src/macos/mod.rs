@@ -1 +1,2 @@
-pub mod macos;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod macos;
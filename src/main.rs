@@ -2,7 +2,19 @@ mod linux; // Declares the linux module (src/linux/mod.rs)
 mod art; // Declares the art module (src/art.rs)
 mod windows; // Declares the windows module (src/windows/mod.rs)
 mod macos; // Declares the macos module (src/macos/mod.rs)
+mod generic; // Declares the best-effort fallback module (src/generic/mod.rs)
 mod cla; // Declares the command line arguments module (src/cla.rs)
+mod display; // Declares the shared display module (src/display.rs)
+mod cpu; // Declares the shared CPU types module (src/cpu.rs)
+mod cpuid; // Declares the CPUID helper module (src/cpuid.rs)
+mod uarch; // Declares the microarchitecture table module (src/uarch.rs)
+mod virt; // Declares the virtualization detection module (src/virt.rs)
+mod flags; // Declares the CPU flag classification module (src/flags.rs)
+mod bench; // Declares the cache and memory benchmark module (src/bench.rs)
+mod json; // Declares the JSON serializer module (src/json.rs)
+mod snapshot; // Declares the JSON snapshot loader module (src/snapshot.rs)
+use cla::OutputFormat; // Output format selected with --format
+use cpu::CpuInfo; // Shared CPU interface (model, caches, flags, fingerprint, validation, JSON)
 use std::env; // Declares the standard library's env module for environment variable access
 
 fn main() {
@@ -39,21 +51,34 @@ fn main() {
         return;
     }
 
-    // Convert logo argument to vendor ID format if provided
-    let logo_override = args.logo.as_ref().and_then(|logo| {
-        match logo.to_lowercase().as_str() {
-            "nvidia" => Some("NVIDIA"),
-            "powerpc" => Some("PowerPC"),
-            "arm" => Some("ARM"),
-            "amd" => Some("AuthenticAMD"),
-            "intel" => Some("GenuineIntel"),
-            "apple" => Some("Apple"),
-            _ => {
-                eprintln!("Warning: Unknown logo vendor '{}'. Valid options: nvidia, powerpc, arm, amd, intel, apple", logo);
-                None
-            }
+    // Handle cache benchmark flag
+    if args.bench_cache {
+        bench::print_cache_benchmark();
+        return;
+    }
+
+    // Handle memory bandwidth benchmark flag
+    if args.bench_bandwidth {
+        bench::print_bandwidth_benchmark();
+        return;
+    }
+
+    // Layout options shared by all display functions
+    let mut display_options = display::DisplayOptions::from_args(&args);
+    if args.auto_colors && args.format == OutputFormat::Text && display_options.use_color()
+        && display_options.logo_colors.is_none() && !display_options.no_logo {
+        display_options.background = display::terminal_background();
+    }
+
+    // Display a saved snapshot instead of detecting the running CPU
+    if let Some(path) = &args.from_json {
+        use crate::snapshot::SnapshotCpuInfo;
+        match SnapshotCpuInfo::load(path) {
+            Ok(mut cpu_info) => render(&mut cpu_info, &args, &display_options),
+            Err(e) => report_error(&args, "Error loading snapshot", &e, "SnapshotUnreadable", true),
         }
-    });
+        return;
+    }
 
     // Detect OS and use appropriate module
     let os = env::consts::OS;
@@ -62,50 +87,213 @@ fn main() {
         "linux" => {
             use crate::linux::linux::LinuxCpuInfo;
             match LinuxCpuInfo::new() {
-                Ok(cpu_info) => {
-                    if args.no_logo {
-                        cpu_info.display_info_no_logo();
-                    } else {
-                        cpu_info.display_info_with_logo(logo_override);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Error fetching CPU info: {}", e);
-                }
+                Ok(mut cpu_info) => render(&mut cpu_info, &args, &display_options),
+                Err(e) => report_error(&args, "Error fetching CPU info", &e, "DetectionFailed", args.strict || args.get.is_some()),
             }
         }
         "windows" => {
             use crate::windows::windows::WindowsCpuInfo;
+            // Legacy consoles print ANSI sequences literally; fall back to no color
+            if !display::enable_ansi_support() {
+                display_options.no_color = true;
+            }
             match WindowsCpuInfo::new() {
-                Ok(cpu_info) => {
-                    if args.no_logo {
-                        cpu_info.display_info_no_logo();
-                    } else {
-                        cpu_info.display_info_with_logo(logo_override);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Error fetching CPU info: {}", e);
-                }
+                Ok(mut cpu_info) => render(&mut cpu_info, &args, &display_options),
+                Err(e) => report_error(&args, "Error fetching CPU info", &e, "DetectionFailed", args.strict || args.get.is_some()),
             }
         }
         "macos" => {
             use crate::macos::macos::MacOSCpuInfo;
             match MacOSCpuInfo::new() {
-                Ok(cpu_info) => {
-                    if args.no_logo {
-                        cpu_info.display_info_no_logo();
-                    } else {
-                        cpu_info.display_info_with_logo(logo_override);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Error fetching CPU info: {}", e);
-                }
+                Ok(mut cpu_info) => render(&mut cpu_info, &args, &display_options),
+                Err(e) => report_error(&args, "Error fetching CPU info", &e, "DetectionFailed", args.strict || args.get.is_some()),
             }
         }
         _ => {
-            eprintln!("Unsupported operating system: {}", os);
+            // No dedicated module yet (BSDs, illumos, ...): show what can be found portably
+            use crate::generic::generic::GenericCpuInfo;
+            match GenericCpuInfo::new(os) {
+                Ok(mut cpu_info) => render(&mut cpu_info, &args, &display_options),
+                Err(e) => report_error(&args, "Error fetching CPU info", &e, "DetectionFailed", args.strict || args.get.is_some()),
+            }
+        }
+    }
+}
+
+/// Print the detected CPU information in the form selected on the command line.
+///
+/// Shared by every OS module and `--from-json`: applies `--force-arch`, then prints
+/// a single field (`--get`), the fingerprint, the unknown flags, or the information
+/// as JSON, shell assignments, HTML, or text, and finally runs the `--strict` check.
+///
+/// # Arguments
+///
+/// * `cpu_info` - Detected CPU information
+/// * `args` - Parsed command line arguments
+/// * `display_options` - Layout options for the HTML and text output
+fn render(cpu_info: &mut impl CpuInfo, args: &cla::Args, display_options: &display::DisplayOptions) {
+    if let Some(architecture) = &args.force_arch {
+        cpu_info.force_architecture(architecture);
+    }
+    let logo_override = args.logo.as_deref().and_then(logo_vendor);
+    if let Some(key) = &args.get {
+        print_field(cpu_info, key);
+    } else if args.fingerprint {
+        println!("{}", cpu_info.fingerprint());
+    } else if args.unknown_flags {
+        print_unknown_flags(cpu_info.flags());
+    } else if args.format == OutputFormat::Json {
+        println!("{}", json::to_string(&cpu_info.to_json(), args.pretty));
+    } else if args.format == OutputFormat::Shell {
+        print!("{}", cpu_info.shell_assignments());
+    } else if args.format == OutputFormat::Html {
+        cpu_info.display_info_html(logo_override, display_options);
+    } else {
+        print_share_header(cpu_info, args.share);
+        if args.no_logo {
+            cpu_info.display_info_no_logo(display_options);
+        } else {
+            cpu_info.display_info_with_logo(logo_override, display_options);
+        }
+        print_share_footer(args.share);
+    }
+    check_strict(cpu_info, args.strict);
+}
+
+/// Convert the `--logo` argument to the vendor ID the logo table uses.
+///
+/// Prints a warning and returns `None` for unknown vendors, so the detected logo
+/// is shown instead.
+///
+/// # Arguments
+///
+/// * `logo` - Vendor name given to `--logo`, e.g. "intel"
+fn logo_vendor(logo: &str) -> Option<&'static str> {
+    match logo.to_lowercase().as_str() {
+        "nvidia" => Some("NVIDIA"),
+        "powerpc" => Some("PowerPC"),
+        "arm" => Some("ARM"),
+        "amd" => Some("AuthenticAMD"),
+        "intel" => Some("GenuineIntel"),
+        "apple" => Some("Apple"),
+        _ => {
+            eprintln!("Warning: Unknown logo vendor '{}'. Valid options: nvidia, powerpc, arm, amd, intel, apple", logo);
+            None
         }
     }
-}
\ No newline at end of file
+}
+
+/// Report that CPU information couldn't be detected or loaded.
+///
+/// With `--format json` the error goes to stdout as a JSON object, e.g.
+/// `{"error":"...","kind":"DetectionFailed"}`, so scripted consumers never have to
+/// parse human text, and rcpufetch always exits with status 1. Otherwise the message
+/// goes to stderr and rcpufetch only exits when `fatal` is set.
+///
+/// # Arguments
+///
+/// * `args` - Parsed command line arguments (output format, `--pretty`)
+/// * `context` - Human-readable prefix for stderr, e.g. "Error fetching CPU info"
+/// * `error` - The error message
+/// * `kind` - Machine-readable error kind, e.g. "DetectionFailed"
+/// * `fatal` - Whether to exit with status 1 in text and HTML output
+fn report_error(args: &cla::Args, context: &str, error: &str, kind: &str, fatal: bool) {
+    if args.format == OutputFormat::Json {
+        let object = json::JsonValue::Object(vec![
+            ("error".to_string(), error.into()),
+            ("kind".to_string(), kind.into()),
+        ]);
+        println!("{}", json::to_string(&object, args.pretty));
+        std::process::exit(1);
+    }
+    eprintln!("{}: {}", context, error);
+    if fatal {
+        std::process::exit(1);
+    }
+}
+
+/// Print a single field's value for `--get`.
+///
+/// Unknown values print an empty line and exit with status 1, so scripts can
+/// tell them apart; an invalid key prints the valid keys and exits with status 1.
+///
+/// # Arguments
+///
+/// * `cpu_info` - Detected CPU information
+/// * `key` - Canonical field key
+fn print_field(cpu_info: &impl CpuInfo, key: &str) {
+    match cpu_info.field(key) {
+        Ok(Some(value)) => println!("{}", value),
+        Ok(None) => {
+            println!();
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Post-detection validation pass for `--strict`.
+///
+/// Output has already been printed; if any critical field is unknown, this reports
+/// the missing fields and exits with a nonzero status.
+///
+/// # Arguments
+///
+/// * `cpu_info` - Detected CPU information
+/// * `strict` - Whether `--strict` was given
+fn check_strict(cpu_info: &impl CpuInfo, strict: bool) {
+    if !strict {
+        return;
+    }
+    if let Err(e) = cpu_info.validate() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Print the CPU flags missing from the classification table (`--unknown-flags`).
+///
+/// Flags go to stdout one per line so the list is easy to diff or paste into an
+/// issue; the summary goes to stderr.
+///
+/// # Arguments
+///
+/// * `cpu_flags` - Raw flag string in the platform's format
+fn print_unknown_flags(cpu_flags: &str) {
+    let unknown = flags::unknown_flags(cpu_flags);
+    for flag in &unknown {
+        println!("{}", flag);
+    }
+    eprintln!(
+        "{} of {} flags are not in rcpufetch's classification table",
+        unknown.len(),
+        flags::split_flags(cpu_flags).len(),
+    );
+}
+
+/// Print the `--share` header: a summary line and the opening code fence.
+///
+/// # Arguments
+///
+/// * `cpu_info` - Detected CPU information
+/// * `share` - Whether `--share` was given
+fn print_share_header(cpu_info: &impl CpuInfo, share: bool) {
+    if share {
+        println!("rcpufetch v{} — {}", env!("CARGO_PKG_VERSION"), cpu_info.model());
+        println!("```");
+    }
+}
+
+/// Print the `--share` footer: the closing code fence.
+///
+/// # Arguments
+///
+/// * `share` - Whether `--share` was given
+fn print_share_footer(share: bool) {
+    if share {
+        println!("```");
+    }
+}
@@ -0,0 +1,306 @@
+//! CPU information loaded from a JSON snapshot (`--from-json <FILE>`).
+//!
+//! A snapshot is the output of `rcpufetch --format json` captured on any platform.
+//! Loading it back lets rcpufetch re-render archived data in any output format, for
+//! example to view snapshots collected centrally from a fleet of machines. Fields
+//! that differ between platforms (frequencies, cache layout) are read from whichever
+//! form the snapshot contains.
+
+use crate::art::logos::get_logo_for_vendor;
+use crate::cpu::{self, Cache, CacheType, CpuInfo};
+use crate::display::{self, DisplayOptions};
+use crate::json::{self, JsonValue};
+use std::fs;
+
+/// CPU information read from a JSON snapshot.
+pub struct SnapshotCpuInfo {
+    /// Path the snapshot was loaded from, shown in the output
+    path: String,
+    model: String,
+    vendor: String,
+    architecture: Option<String>,
+    byte_order: Option<String>,
+    physical_cores: u32,
+    logical_cores: u32,
+    /// Maximum frequency in GHz (or the base frequency, if that's all the snapshot has)
+    max_ghz: Option<f32>,
+    caches: Vec<Cache>,
+    /// Flags joined with spaces, in Linux format
+    flags: String,
+    hypervisor: Option<String>,
+    container: Option<String>,
+}
+
+impl SnapshotCpuInfo {
+    /// Load a snapshot written by `--format json`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the JSON file
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(SnapshotCpuInfo)` on success, or `Err(String)` if the file can't be
+    /// read, isn't valid JSON, isn't an rcpufetch snapshot, or was written with a
+    /// newer `schema_version` than this build understands. Snapshots from before
+    /// `schema_version` was added are read as version 1.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+        let root = json::parse(&text).map_err(|e| format!("{}: {}", path, e))?;
+
+        if !matches!(root, JsonValue::Object(_)) || root.get("model").is_none() {
+            return Err(format!("{}: not an rcpufetch JSON snapshot", path));
+        }
+        let schema_version = match root.get("schema_version") {
+            Some(version) => version.as_u32().ok_or_else(|| format!("{}: invalid schema_version", path))?,
+            None => 1,
+        };
+        if schema_version > json::SCHEMA_VERSION {
+            return Err(format!(
+                "{}: schema_version {} is newer than supported ({}); upgrade rcpufetch",
+                path, schema_version, json::SCHEMA_VERSION,
+            ));
+        }
+
+        let string = |key: &str| root.get(key).and_then(JsonValue::as_str).map(str::to_string);
+        let ghz = |key: &str| root.get(key).and_then(JsonValue::as_f64).map(|ghz| ghz as f32);
+        let max_ghz = ghz("max_frequency_ghz")
+            .or_else(|| ghz("p_core_max_ghz"))
+            .or_else(|| ghz("base_frequency_mhz").map(|mhz| mhz / 1000.0));
+        let flags: Vec<&str> = root.get("flags").map_or(&[][..], JsonValue::as_array)
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .collect();
+
+        Ok(SnapshotCpuInfo {
+            path: path.to_string(),
+            model: string("model").unwrap_or_else(|| "Unknown".to_string()),
+            vendor: string("vendor").unwrap_or_else(|| "Unknown".to_string()),
+            architecture: string("architecture"),
+            byte_order: string("byte_order"),
+            physical_cores: root.get("physical_cores").and_then(JsonValue::as_u32).unwrap_or(0),
+            logical_cores: root.get("logical_cores").and_then(JsonValue::as_u32).unwrap_or(0),
+            max_ghz,
+            caches: Self::read_caches(&root),
+            flags: flags.join(" "),
+            hypervisor: string("hypervisor"),
+            container: string("container"),
+        })
+    }
+
+    /// Read the cache hierarchy from a snapshot.
+    ///
+    /// Snapshots from every platform have a `caches` array; older Windows snapshots
+    /// only have `l1_kb`, `l2_kb`, and `l3_kb`, which are read as unified caches.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Snapshot object
+    fn read_caches(root: &JsonValue) -> Vec<Cache> {
+        let u32_field = |cache: &JsonValue, key: &str| cache.get(key).and_then(JsonValue::as_u32);
+
+        if let Some(JsonValue::Array(caches)) = root.get("caches") {
+            return caches.iter()
+                .filter_map(|cache| {
+                    Some(Cache {
+                        level: u32_field(cache, "level")?,
+                        cache_type: match cache.get("type").and_then(JsonValue::as_str)? {
+                            "data" => CacheType::Data,
+                            "instruction" => CacheType::Instruction,
+                            _ => CacheType::Unified,
+                        },
+                        size_kb: u32_field(cache, "size_kb")?,
+                        associativity: u32_field(cache, "associativity"),
+                        line_size: u32_field(cache, "line_size"),
+                        shared_cpus: u32_field(cache, "shared_cpus"),
+                        instances: u32_field(cache, "instances"),
+                        inclusive: cache.get("inclusive").and_then(JsonValue::as_bool),
+                    })
+                })
+                .collect();
+        }
+
+        (1..=3)
+            .filter_map(|level| {
+                Some(Cache {
+                    level,
+                    cache_type: CacheType::Unified,
+                    size_kb: u32_field(root, &format!("l{}_kb", level))?,
+                    associativity: None,
+                    line_size: None,
+                    shared_cpus: None,
+                    instances: None,
+                    inclusive: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the formatted information lines, excluding flags.
+    fn get_info_lines(&self, opts: &DisplayOptions) -> Vec<String> {
+        let mut lines = vec![
+            format!("Name: {}", self.model),
+            format!("Architecture: {}", self.architecture.as_deref().unwrap_or("Unknown")),
+        ];
+        if let Some(byte_order) = &self.byte_order {
+            lines.push(format!("Byte Order: {}", byte_order));
+        }
+        lines.push(format!("Vendor: {}", self.vendor));
+        lines.push(match self.max_ghz {
+            Some(ghz) => format!("Max Frequency: {:.3} GHz", ghz),
+            None => "Max Frequency: Unknown".to_string(),
+        });
+        lines.push(format!("Cores: {} cores ({} threads)", self.physical_cores, self.logical_cores));
+
+        // A total is only known when the snapshot recorded the number of instances
+        if opts.compact_cache {
+            let level_total = |level: u32| {
+                let sizes: Vec<u64> = self.caches.iter().filter(|c| c.level == level).map(Cache::total_kb).collect();
+                (!sizes.is_empty()).then(|| sizes.iter().sum())
+            };
+            lines.extend(display::compact_cache_line(&[
+                ("L1", level_total(1)),
+                ("L2", level_total(2)),
+                ("L3", level_total(3)),
+            ]));
+        } else {
+            for cache in &self.caches {
+                lines.push(format!("{} Size: {}", cache.name(), display::format_cache_size(cache.total_kb())));
+            }
+        }
+
+        if opts.show_cache_details() {
+            lines.extend(display::cache_detail_lines(&self.caches));
+        }
+        if let Some(hypervisor) = &self.hypervisor {
+            lines.push(format!("Hypervisor: {}", hypervisor));
+        }
+        if let Some(container) = &self.container {
+            lines.push(format!("Environment: {}", container));
+        }
+        lines.push(format!("Snapshot: {}", self.path));
+        display::relabel(&mut lines, opts);
+        lines
+    }
+
+    /// Get the wrapped flag lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options (grouped flags, flag limit)
+    /// * `wrap_width` - Width available to the info column
+    fn flag_lines(&self, opts: &DisplayOptions, wrap_width: usize) -> Vec<String> {
+        if self.flags.is_empty() {
+            return Vec::new();
+        }
+        display::flag_lines(&self.flags, " ", wrap_width, opts)
+    }
+}
+
+impl CpuInfo for SnapshotCpuInfo {
+    fn caches(&self) -> Vec<Cache> {
+        self.caches.clone()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn force_architecture(&mut self, architecture: &str) {
+        self.architecture = Some(architecture.to_string());
+    }
+
+    fn flags(&self) -> &str {
+        &self.flags
+    }
+
+    fn max_frequency_ghz(&self) -> Option<f32> {
+        self.max_ghz
+    }
+
+    fn fingerprint_fields(&self) -> Vec<(&'static str, String)> {
+        // Totals across all instances, like the Linux module reports them
+        let cache_kb = |name: &str| cpu::canonical_size(self.caches.iter().find(|c| c.name() == name).map(Cache::total_kb));
+        vec![
+            ("vendor", self.vendor.clone()),
+            ("model", self.model.clone()),
+            ("architecture", self.architecture.clone().unwrap_or_else(|| "unknown".to_string())),
+            ("physical_cores", self.physical_cores.to_string()),
+            ("logical_cores", self.logical_cores.to_string()),
+            ("l1d_kb", cache_kb("L1d")),
+            ("l1i_kb", cache_kb("L1i")),
+            ("l2_kb", cache_kb("L2")),
+            ("l3_kb", cache_kb("L3")),
+            ("flags", cpu::canonical_flags(&self.flags)),
+        ]
+    }
+
+    fn to_json(&self) -> JsonValue {
+        json::CommonFields {
+            model: &self.model,
+            vendor: &self.vendor,
+            architecture: self.architecture.as_deref(),
+            byte_order: self.byte_order.as_deref(),
+            physical_cores: Some(self.physical_cores),
+            logical_cores: self.logical_cores,
+            max_frequency_ghz: self.max_frequency_ghz(),
+            l1d_kb: json::cache_total_kb(&self.caches, "L1d"),
+            l1i_kb: json::cache_total_kb(&self.caches, "L1i"),
+            l2_kb: json::cache_total_kb(&self.caches, "L2"),
+            l3_kb: json::cache_total_kb(&self.caches, "L3"),
+            caches: &self.caches,
+            flags: &self.flags,
+        }.into_json(vec![
+            ("hypervisor".to_string(), JsonValue::optional(self.hypervisor.as_ref())),
+            ("container".to_string(), JsonValue::optional(self.container.as_ref())),
+        ])
+    }
+
+    /// Print the snapshot with a logo (side-by-side layout).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the snapshot's vendor logo
+    /// * `opts` - Layout options
+    fn display_info_with_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let (logo_lines, logo_colors) = get_logo_for_vendor(logo_override.unwrap_or(&self.vendor), opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+            .map_or_else(Default::default, |l| (l.lines, l.colors));
+
+        let wrap_width = opts.resolve_width(display::DEFAULT_WIDTH).saturating_sub(display::info_column_offset(&logo_lines, opts));
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, wrap_width));
+
+        display::print_side_by_side(&logo_lines, &logo_colors, &info_lines, opts);
+    }
+
+    /// Print the snapshot without a logo.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Layout options
+    fn display_info_no_logo(&self, opts: &DisplayOptions) {
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, opts.resolve_width(display::DEFAULT_NO_LOGO_WIDTH)));
+        display::print_lines(&info_lines, opts);
+    }
+
+    /// Print the snapshot as a self-contained HTML snippet (`--format html`).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the snapshot's vendor logo
+    /// * `opts` - Display options (colors, logo)
+    fn display_info_html(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let logo_lines = if opts.no_logo {
+            Vec::new()
+        } else {
+            get_logo_for_vendor(logo_override.unwrap_or(&self.vendor), opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+                .map(|l| l.lines)
+                .unwrap_or_default()
+        };
+
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, usize::MAX));
+        display::print_html(&logo_lines, &info_lines);
+    }
+}
@@ -0,0 +1,253 @@
+//! Microarchitecture detection for rcpufetch.
+//!
+//! Maps a CPU's vendor and CPUID family/model signature to a known microarchitecture
+//! and its manufacturer specifications. All data lives in the `UARCH_TABLE` below so
+//! new CPUs can be added without touching any detection logic.
+
+/// A known microarchitecture and its manufacturer specifications.
+#[derive(Debug)]
+pub struct Uarch {
+    /// Vendor ID as reported by CPUID ("AuthenticAMD", "GenuineIntel")
+    pub vendor: &'static str,
+    /// CPUID display family
+    pub family: u32,
+    /// CPUID display models belonging to this microarchitecture
+    pub models: &'static [u32],
+    /// Microarchitecture name (e.g. "Zen 4")
+    pub name: &'static str,
+    /// Product codename (e.g. "Raphael")
+    pub codename: &'static str,
+    /// Maximum supported memory per socket in GB, according to the manufacturer
+    pub max_memory_gb: Option<u32>,
+    /// Lowest rated base frequency of any SKU in MHz, according to the manufacturer
+    pub min_base_mhz: Option<u32>,
+}
+
+/// Table of known microarchitectures, keyed by vendor, family, and model.
+///
+/// Memory limits are manufacturer specifications for the platform (per socket), not
+/// measured values. Entries without a single well-defined limit use `None`. That
+/// includes Rome, Milan and Genoa: Threadripper (Castle Peak, Chagall, Storm Peak)
+/// reports the same family/model, and its limits differ from EPYC's.
+///
+/// Base frequencies are per SKU, so `min_base_mhz` is the lowest one across every
+/// part sharing the signature (Threadripper included). It is only filled in for AMD
+/// families, where CPUID leaf 0x16 doesn't report the base frequency, and left out
+/// where the signature also covers mobile parts (Raphael and Dragon Range, for one).
+const UARCH_TABLE: &[Uarch] = &[
+    // AMD
+    Uarch { vendor: "AuthenticAMD", family: 0x17, models: &[0x01], name: "Zen", codename: "Summit Ridge / Naples", max_memory_gb: None, min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x17, models: &[0x08, 0x11, 0x18], name: "Zen / Zen+", codename: "Pinnacle Ridge / Raven Ridge", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x17, models: &[0x31], name: "Zen 2", codename: "Rome", max_memory_gb: None, min_base_mhz: Some(2000) },
+    Uarch { vendor: "AuthenticAMD", family: 0x17, models: &[0x71], name: "Zen 2", codename: "Matisse", max_memory_gb: Some(128), min_base_mhz: Some(3100) },
+    Uarch { vendor: "AuthenticAMD", family: 0x19, models: &[0x01], name: "Zen 3", codename: "Milan", max_memory_gb: None, min_base_mhz: Some(2000) },
+    Uarch { vendor: "AuthenticAMD", family: 0x19, models: &[0x21], name: "Zen 3", codename: "Vermeer", max_memory_gb: Some(128), min_base_mhz: Some(3000) },
+    Uarch { vendor: "AuthenticAMD", family: 0x19, models: &[0x50], name: "Zen 3", codename: "Cezanne", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x19, models: &[0x11], name: "Zen 4", codename: "Genoa", max_memory_gb: None, min_base_mhz: Some(2250) },
+    Uarch { vendor: "AuthenticAMD", family: 0x19, models: &[0x61], name: "Zen 4", codename: "Raphael", max_memory_gb: Some(192), min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x19, models: &[0x74, 0x75], name: "Zen 4", codename: "Phoenix", max_memory_gb: None, min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x1A, models: &[0x44], name: "Zen 5", codename: "Granite Ridge", max_memory_gb: Some(192), min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x1A, models: &[0x24], name: "Zen 5", codename: "Strix Point", max_memory_gb: None, min_base_mhz: None },
+    Uarch { vendor: "AuthenticAMD", family: 0x1A, models: &[0x02, 0x11], name: "Zen 5", codename: "Turin", max_memory_gb: Some(6144), min_base_mhz: None },
+    // Intel
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x55], name: "Skylake", codename: "Skylake-SP / Cascade Lake", max_memory_gb: None, min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x5E], name: "Skylake", codename: "Skylake-S", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x9E], name: "Skylake", codename: "Coffee Lake", max_memory_gb: None, min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xA5], name: "Skylake", codename: "Comet Lake", max_memory_gb: Some(128), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xA7], name: "Cypress Cove", codename: "Rocket Lake", max_memory_gb: Some(128), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x6A, 0x6C], name: "Sunny Cove", codename: "Ice Lake-SP", max_memory_gb: Some(6144), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x7E], name: "Sunny Cove", codename: "Ice Lake", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x8C, 0x8D], name: "Willow Cove", codename: "Tiger Lake", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x97], name: "Golden Cove", codename: "Alder Lake-S", max_memory_gb: Some(128), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x9A], name: "Golden Cove", codename: "Alder Lake-P", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xB7, 0xBF], name: "Raptor Cove", codename: "Raptor Lake-S", max_memory_gb: Some(192), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xBA], name: "Raptor Cove", codename: "Raptor Lake-P", max_memory_gb: Some(64), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xAA, 0xAC], name: "Redwood Cove", codename: "Meteor Lake", max_memory_gb: Some(96), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xC6], name: "Lion Cove", codename: "Arrow Lake", max_memory_gb: Some(192), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xBD], name: "Lion Cove", codename: "Lunar Lake", max_memory_gb: Some(32), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0x8F], name: "Golden Cove", codename: "Sapphire Rapids", max_memory_gb: Some(4096), min_base_mhz: None },
+    Uarch { vendor: "GenuineIntel", family: 6, models: &[0xCF], name: "Raptor Cove", codename: "Emerald Rapids", max_memory_gb: Some(4096), min_base_mhz: None },
+];
+
+/// Typical load-to-use cache latencies in core clock cycles.
+#[derive(Debug)]
+pub struct CacheLatency {
+    /// Key matched against a `Uarch` codename first, then its name
+    pub key: &'static str,
+    /// L1 data cache latency
+    pub l1: u32,
+    /// L2 cache latency
+    pub l2: u32,
+    /// L3 cache latency
+    pub l3: u32,
+}
+
+/// Typical cache latencies, keyed by codename (for parts that differ from their
+/// core's other products, such as server dies with a larger mesh) or by name.
+///
+/// These are approximate figures from public microbenchmark results, not measured
+/// on the running system; real latency varies with clock speed, load, and memory
+/// placement.
+const CACHE_LATENCY_TABLE: &[CacheLatency] = &[
+    // Server parts: the mesh/fabric makes L3 much slower than on client parts
+    CacheLatency { key: "Skylake-SP / Cascade Lake", l1: 4, l2: 14, l3: 70 },
+    CacheLatency { key: "Ice Lake-SP", l1: 5, l2: 14, l3: 75 },
+    CacheLatency { key: "Sapphire Rapids", l1: 5, l2: 16, l3: 110 },
+    CacheLatency { key: "Emerald Rapids", l1: 5, l2: 16, l3: 100 },
+    // AMD
+    CacheLatency { key: "Zen", l1: 4, l2: 12, l3: 35 },
+    CacheLatency { key: "Zen / Zen+", l1: 4, l2: 12, l3: 38 },
+    CacheLatency { key: "Zen 2", l1: 4, l2: 12, l3: 39 },
+    CacheLatency { key: "Zen 3", l1: 4, l2: 12, l3: 46 },
+    CacheLatency { key: "Zen 4", l1: 4, l2: 14, l3: 50 },
+    CacheLatency { key: "Zen 5", l1: 4, l2: 14, l3: 50 },
+    // Intel
+    CacheLatency { key: "Skylake", l1: 4, l2: 12, l3: 42 },
+    CacheLatency { key: "Cypress Cove", l1: 5, l2: 13, l3: 45 },
+    CacheLatency { key: "Sunny Cove", l1: 5, l2: 13, l3: 42 },
+    CacheLatency { key: "Willow Cove", l1: 5, l2: 14, l3: 50 },
+    CacheLatency { key: "Golden Cove", l1: 5, l2: 15, l3: 65 },
+    CacheLatency { key: "Raptor Cove", l1: 5, l2: 16, l3: 65 },
+    CacheLatency { key: "Redwood Cove", l1: 5, l2: 16, l3: 75 },
+    CacheLatency { key: "Lion Cove", l1: 5, l2: 17, l3: 80 },
+];
+
+/// Look up typical cache latencies for a microarchitecture.
+///
+/// # Arguments
+///
+/// * `uarch` - Detected microarchitecture
+///
+/// # Returns
+///
+/// Returns `Some(&CacheLatency)` for microarchitectures in the table, or `None`.
+pub fn cache_latency(uarch: &Uarch) -> Option<&'static CacheLatency> {
+    CACHE_LATENCY_TABLE.iter()
+        .find(|latency| latency.key == uarch.codename)
+        .or_else(|| CACHE_LATENCY_TABLE.iter().find(|latency| latency.key == uarch.name))
+}
+
+/// How a processor package is split into dies or tiles.
+#[derive(Debug)]
+pub enum PackageLayout {
+    /// AMD chiplets: core complex dies (CCDs), each holding this many core complexes
+    /// (CCXs) with their own L3
+    Chiplets { ccx_per_ccd: u32 },
+    /// Intel disaggregated designs: the tiles in every package, by function
+    Tiles(&'static [&'static str]),
+}
+
+/// Package layout of a microarchitecture.
+#[derive(Debug)]
+pub struct Package {
+    /// Codename matched against a `Uarch`
+    pub key: &'static str,
+    /// How the package is split
+    pub layout: PackageLayout,
+}
+
+/// Known package layouts, keyed by codename.
+///
+/// Monolithic parts (AMD APUs, most Intel client chips) and codenames that cover
+/// packages with different layouts (e.g. Turin with both Zen 5 and Zen 5c CCDs,
+/// or Sapphire Rapids with tiled XCC and monolithic MCC dies) are left out, so the
+/// count is only shown when it is certain.
+const PACKAGE_TABLE: &[Package] = &[
+    // AMD: Zen 2 CCDs hold two 4-core CCXs; from Zen 3 on a CCD is a single CCX
+    Package { key: "Rome", layout: PackageLayout::Chiplets { ccx_per_ccd: 2 } },
+    Package { key: "Matisse", layout: PackageLayout::Chiplets { ccx_per_ccd: 2 } },
+    Package { key: "Milan", layout: PackageLayout::Chiplets { ccx_per_ccd: 1 } },
+    Package { key: "Vermeer", layout: PackageLayout::Chiplets { ccx_per_ccd: 1 } },
+    Package { key: "Genoa", layout: PackageLayout::Chiplets { ccx_per_ccd: 1 } },
+    Package { key: "Raphael", layout: PackageLayout::Chiplets { ccx_per_ccd: 1 } },
+    Package { key: "Granite Ridge", layout: PackageLayout::Chiplets { ccx_per_ccd: 1 } },
+    // Intel
+    Package { key: "Meteor Lake", layout: PackageLayout::Tiles(&["Compute", "SoC", "GPU", "IO"]) },
+    Package { key: "Arrow Lake", layout: PackageLayout::Tiles(&["Compute", "SoC", "GPU", "IO"]) },
+    Package { key: "Lunar Lake", layout: PackageLayout::Tiles(&["Compute", "Platform Controller"]) },
+];
+
+/// Look up how a microarchitecture's package is split into dies or tiles.
+///
+/// # Arguments
+///
+/// * `uarch` - Detected microarchitecture
+///
+/// # Returns
+///
+/// Returns `Some(&PackageLayout)` for codenames in the table, or `None`.
+pub fn package_layout(uarch: &Uarch) -> Option<&'static PackageLayout> {
+    PACKAGE_TABLE.iter()
+        .find(|package| package.key == uarch.codename)
+        .map(|package| &package.layout)
+}
+
+/// Look up the microarchitecture for a CPU signature.
+///
+/// # Arguments
+///
+/// * `vendor` - CPUID vendor string (e.g. "AuthenticAMD")
+/// * `family` - CPUID display family
+/// * `model` - CPUID display model
+///
+/// # Returns
+///
+/// Returns `Some(&Uarch)` for known CPUs, or `None` if the signature is not in the table.
+pub fn detect(vendor: &str, family: u32, model: u32) -> Option<&'static Uarch> {
+    UARCH_TABLE.iter()
+        .find(|u| u.vendor == vendor && u.family == family && u.models.contains(&model))
+}
+
+/// Format a memory capacity given in GB, using TB for whole terabytes.
+///
+/// # Arguments
+///
+/// * `gb` - Capacity in gigabytes
+///
+/// # Returns
+///
+/// Returns a string such as "192 GB" or "6 TB".
+pub fn format_memory_gb(gb: u32) -> String {
+    if gb >= 1024 && gb.is_multiple_of(1024) {
+        format!("{} TB", gb / 1024)
+    } else {
+        format!("{} GB", gb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_known_models_and_formats_their_memory_limit() {
+        let raphael = detect("AuthenticAMD", 0x19, 0x61).unwrap();
+        assert_eq!(raphael.codename, "Raphael");
+        assert_eq!(raphael.max_memory_gb.map(format_memory_gb).as_deref(), Some("192 GB"));
+
+        let turin = detect("AuthenticAMD", 0x1A, 0x11).unwrap();
+        assert_eq!(turin.max_memory_gb.map(format_memory_gb).as_deref(), Some("6 TB"));
+    }
+
+    #[test]
+    fn unknown_models_are_not_detected() {
+        assert!(detect("AuthenticAMD", 0x19, 0xFF).is_none());
+        assert!(detect("GenuineIntel", 0x19, 0x61).is_none());
+    }
+
+    #[test]
+    fn signatures_shared_with_threadripper_have_no_memory_limit() {
+        for (family, model) in [(0x17, 0x31), (0x19, 0x01), (0x19, 0x11)] {
+            let uarch = detect("AuthenticAMD", family, model).unwrap();
+            assert_eq!(uarch.max_memory_gb, None, "{}", uarch.codename);
+        }
+    }
+
+    #[test]
+    fn format_memory_gb_uses_tb_only_for_whole_terabytes() {
+        assert_eq!(format_memory_gb(192), "192 GB");
+        assert_eq!(format_memory_gb(6144), "6 TB");
+        assert_eq!(format_memory_gb(1536), "1536 GB");
+        assert_eq!(format_memory_gb(512), "512 GB");
+    }
+}
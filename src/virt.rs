@@ -0,0 +1,294 @@
+//! Virtualization detection for rcpufetch.
+//!
+//! Inside a virtual machine the guest sees whatever CPU model the hypervisor chooses
+//! to expose. QEMU/KVM in particular reports generic or named models ("QEMU Virtual
+//! CPU", "Intel Core Processor (Skylake)") unless the VM is started with `-cpu host`,
+//! which confuses users comparing the output against the host. This module detects
+//! the hypervisor and recognizes those emulated model strings, and detects when
+//! rcpufetch runs inside a container, where cache and topology data may be limited.
+//! It also detects x86 translation layers such as FEX-Emu and box64, under which an
+//! x86 build of rcpufetch reports emulated flags on a non-x86 machine.
+
+use std::fs;
+
+/// Known hypervisor CPUID signatures and their display names.
+const HYPERVISORS: &[(&str, &str)] = &[
+    ("KVMKVMKVM", "KVM"),
+    ("TCGTCGTCGTCG", "QEMU (TCG)"),
+    ("Microsoft Hv", "Hyper-V"),
+    ("VMwareVMware", "VMware"),
+    ("XenVMMXenVMM", "Xen"),
+    ("VBoxVBoxVBox", "VirtualBox"),
+    ("prl hyperv", "Parallels"),
+    ("lrpepyh  vr", "Parallels"),
+    ("bhyve bhyve", "bhyve"),
+    ("ACRNACRNACRN", "ACRN"),
+    ("QNXQVMBSQG", "QNX Hypervisor"),
+    ("Apple VZ", "Apple Virtualization"),
+];
+
+/// Model name prefixes used by QEMU's built-in CPU models.
+///
+/// These come from the `model-id` of QEMU's generic models (`qemu64`, `kvm64`) and its
+/// named models (`Skylake-Client`, `EPYC-Rome`, ...). With `-cpu host` the guest sees
+/// the real model string instead, which matches none of these.
+const EMULATED_MODEL_PREFIXES: &[&str] = &[
+    "QEMU Virtual CPU",
+    "Common KVM processor",
+    "Common 32-bit KVM processor",
+    "Intel Core Processor (",
+    "Intel Xeon Processor (",
+    "Intel Atom Processor (",
+    "Intel Core i7 9xx (Nehalem",
+    "Intel Xeon E312xx (Sandy Bridge",
+    "Intel Xeon E3-12xx v2 (Ivy Bridge",
+    "Intel Celeron_4x0 (Conroe",
+    "Intel Core 2 Duo P9xxx (Penryn",
+    "Westmere E56xx/L56xx/X56xx",
+    "AMD EPYC Processor",
+    "AMD EPYC-",
+    "AMD Opteron 23xx (Gen 3",
+    "AMD Opteron 62xx class",
+    "AMD Opteron 63xx class",
+    "Hygon Dhyana Processor",
+];
+
+/// Detect the hypervisor the system is running under.
+///
+/// Uses the CPUID hypervisor signature on x86 and falls back to the `hypervisor`
+/// CPU flag when the signature is unknown or unavailable.
+///
+/// # Arguments
+///
+/// * `flags` - Space-separated CPU flags (e.g. from /proc/cpuinfo)
+///
+/// # Returns
+///
+/// Returns `Some(String)` with the hypervisor name, or `None` on bare metal.
+pub fn detect_hypervisor(flags: &str) -> Option<String> {
+    if let Some(signature) = crate::cpuid::hypervisor_vendor() {
+        let name = HYPERVISORS.iter()
+            .find(|(sig, _)| *sig == signature)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or(signature);
+        return Some(name);
+    }
+
+    if flags.split_whitespace().any(|flag| flag == "hypervisor") {
+        return Some("Unknown hypervisor".to_string());
+    }
+
+    None
+}
+
+/// Check whether a CPU model string is one of QEMU's emulated CPU models.
+///
+/// # Arguments
+///
+/// * `model` - CPU model name as reported to the guest
+///
+/// # Returns
+///
+/// Returns `true` if the model matches a known QEMU model name.
+pub fn is_emulated_model(model: &str) -> bool {
+    EMULATED_MODEL_PREFIXES.iter().any(|prefix| model.starts_with(prefix))
+}
+
+/// Detect whether rcpufetch is running inside a container.
+///
+/// Checks the marker files created by Docker (`/.dockerenv`) and Podman
+/// (`/run/.containerenv`), then the cgroup paths of PID 1, then the `container`
+/// environment variable set by systemd-nspawn, LXC, and others.
+///
+/// # Returns
+///
+/// Returns `Some(String)` describing the container (e.g. "Docker container"), or
+/// `None` when no container indicators are found.
+pub fn detect_container() -> Option<String> {
+    if fs::metadata("/.dockerenv").is_ok() {
+        return Some("Docker container".to_string());
+    }
+    if fs::metadata("/run/.containerenv").is_ok() {
+        return Some("Podman container".to_string());
+    }
+
+    let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    if cgroup.contains("kubepods") {
+        return Some("Kubernetes pod".to_string());
+    }
+    if cgroup.contains("docker") {
+        return Some("Docker container".to_string());
+    }
+    if cgroup.contains("lxc") {
+        return Some("LXC container".to_string());
+    }
+
+    match std::env::var("container").ok()?.as_str() {
+        "" => None,
+        "docker" => Some("Docker container".to_string()),
+        "podman" => Some("Podman container".to_string()),
+        "lxc" | "lxc-libvirt" => Some("LXC container".to_string()),
+        "systemd-nspawn" => Some("systemd-nspawn container".to_string()),
+        other => Some(format!("{} container", other)),
+    }
+}
+
+/// Read the CPU quota of the cgroup rcpufetch runs in.
+///
+/// Container runtimes and Kubernetes limit CPU time with a quota per period: cgroup
+/// v2's `cpu.max` ("250000 100000" is 2.5 CPUs' worth of time, "max" means no limit)
+/// or cgroup v1's `cpu.cfs_quota_us` and `cpu.cfs_period_us` (-1 means no limit).
+/// The cgroup of this process is found in `/proc/self/cgroup`; it and its ancestors
+/// are checked, since a limit on a parent also applies, and the tightest wins.
+///
+/// # Returns
+///
+/// Returns the quota in CPUs (e.g. 2.5), or `None` when no quota is set.
+pub fn cpu_quota() -> Option<f64> {
+    let cgroups = fs::read_to_string("/proc/self/cgroup").ok()?;
+    let mut quotas = Vec::new();
+    for line in cgroups.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        if controllers.is_empty() {
+            // cgroup v2: a single unified hierarchy
+            quotas.extend(cgroup_ancestors("/sys/fs/cgroup", path).filter_map(|dir| {
+                parse_cpu_max(&fs::read_to_string(dir.join("cpu.max")).ok()?)
+            }));
+        } else if controllers.split(',').any(|controller| controller == "cpu") {
+            // cgroup v1: the cpu controller is mounted as "cpu,cpuacct" or "cpu"
+            for mount in ["/sys/fs/cgroup/cpu,cpuacct", "/sys/fs/cgroup/cpu"] {
+                quotas.extend(cgroup_ancestors(mount, path).filter_map(|dir| {
+                    let read = |name: &str| fs::read_to_string(dir.join(name)).ok()?.trim().parse::<i64>().ok();
+                    cfs_quota(read("cpu.cfs_quota_us")?, read("cpu.cfs_period_us")?)
+                }));
+            }
+        }
+    }
+    quotas.into_iter().reduce(f64::min)
+}
+
+/// List a cgroup's directory and those of its ancestors, innermost first.
+///
+/// Inside a container the cgroup path from `/proc/self/cgroup` may refer to the
+/// host's hierarchy while the container only sees its own cgroup at the mount
+/// point, so the mount point itself is always included.
+///
+/// # Arguments
+///
+/// * `mount` - Mount point of the hierarchy, e.g. "/sys/fs/cgroup"
+/// * `path` - Cgroup path from `/proc/self/cgroup`, e.g. "/kubepods/pod1/abc"
+fn cgroup_ancestors(mount: &str, path: &str) -> impl Iterator<Item = std::path::PathBuf> {
+    let mount = std::path::PathBuf::from(mount);
+    let dirs: Vec<std::path::PathBuf> = std::path::Path::new(path.trim_start_matches('/'))
+        .ancestors()
+        .map(|ancestor| mount.join(ancestor))
+        .collect();
+    dirs.into_iter()
+}
+
+/// Parse a cgroup v2 `cpu.max` value such as "250000 100000" or "max 100000".
+///
+/// # Returns
+///
+/// Returns the quota in CPUs, or `None` when it is unlimited or malformed.
+fn parse_cpu_max(value: &str) -> Option<f64> {
+    let mut fields = value.split_whitespace();
+    let quota = fields.next()?.parse::<i64>().ok()?;
+    let period = fields.next().map_or(Some(100_000), |period| period.parse::<i64>().ok())?;
+    cfs_quota(quota, period)
+}
+
+/// Convert a quota and period in microseconds into CPUs.
+///
+/// # Returns
+///
+/// Returns `quota / period`, or `None` when either is not positive (a v1 quota of
+/// -1 means unlimited).
+fn cfs_quota(quota: i64, period: i64) -> Option<f64> {
+    (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+}
+
+/// Environment variable prefixes of x86 translation layers and their display names.
+///
+/// FEX-Emu and box86/box64 are configured through these variables, which are set in
+/// the environment of the translated process.
+const TRANSLATION_LAYER_ENV_PREFIXES: &[(&str, &str)] = &[
+    ("FEX_", "FEX-Emu"),
+    ("BOX64_", "box64"),
+    ("BOX86_", "box86"),
+];
+
+/// Detect an x86 translation layer (FEX-Emu, box64, box86) running rcpufetch.
+///
+/// Only x86 builds can run under these layers, and the architecture they report
+/// (`uname -m`, /proc/cpuinfo) is the emulated one. The native architecture comes
+/// from the kernel's `/proc/sys/kernel/arch`, which the layers don't intercept, or
+/// from the ARM64-only `midr_el1` register file in sysfs. A translation layer is only
+/// reported when its environment variables are set and the native architecture
+/// isn't x86, so stray variables on an x86 machine are ignored.
+///
+/// # Returns
+///
+/// Returns a description such as "FEX-Emu (native: aarch64)", or `None` when no
+/// translation layer is detected.
+pub fn detect_translation_layer() -> Option<String> {
+    if !matches!(std::env::consts::ARCH, "x86" | "x86_64") {
+        return None;
+    }
+
+    let layer = std::env::vars_os()
+        .filter_map(|(key, _)| key.into_string().ok())
+        .find_map(|key| {
+            TRANSLATION_LAYER_ENV_PREFIXES.iter()
+                .find(|(prefix, _)| key.starts_with(prefix))
+                .map(|(_, name)| *name)
+        })?;
+
+    let native = fs::read_to_string("/proc/sys/kernel/arch")
+        .ok()
+        .map(|arch| arch.trim().to_string())
+        .filter(|arch| !arch.is_empty())
+        .or_else(|| {
+            fs::metadata("/sys/devices/system/cpu/cpu0/regs/identification/midr_el1")
+                .is_ok()
+                .then(|| "aarch64".to_string())
+        });
+
+    match native {
+        Some(arch) if matches!(arch.as_str(), "x86_64" | "i386" | "i486" | "i586" | "i686") => None,
+        Some(arch) => Some(format!("{} (native: {})", layer, arch)),
+        None => Some(layer.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cpu_max_reads_quota_and_period() {
+        assert_eq!(parse_cpu_max("250000 100000\n"), Some(2.5));
+        assert_eq!(parse_cpu_max("50000 200000"), Some(0.25));
+    }
+
+    #[test]
+    fn parse_cpu_max_unlimited_is_none() {
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cpu_max(""), None);
+    }
+
+    #[test]
+    fn parse_cpu_max_defaults_the_period() {
+        assert_eq!(parse_cpu_max("200000"), Some(2.0));
+    }
+
+    #[test]
+    fn cfs_quota_unlimited_is_none() {
+        assert_eq!(cfs_quota(-1, 100_000), None);
+        assert_eq!(cfs_quota(150_000, 0), None);
+        assert_eq!(cfs_quota(150_000, 100_000), Some(1.5));
+    }
+}
@@ -1,94 +1,644 @@
-use crate::art::logos::get_logo_lines_for_vendor;
+use crate::art::logos::get_logo_for_vendor;
+use crate::cpu::{self, Cache, CacheType, CpuInfo};
+use crate::cpuid;
+use crate::json::{self, JsonValue};
+use crate::display::{self, DisplayOptions};
+use std::process::Command;
 
 pub struct WindowsCpuInfo {
     model: String,
     vendor: String,
+    /// Architecture rcpufetch was built for (e.g. "x86_64")
+    architecture: String,
     physical_cores: u32,
     logical_cores: u32,
+    /// Physical cores per efficiency class, highest class (P-cores) first
+    core_classes: Vec<(u8, u32)>,
     base_mhz: Option<f32>,
     l1_size: Option<(u32, u32)>,
     l2_size: Option<(u32, u32)>,
     l3_size: Option<(u32, u32)>,
+    caches: Vec<Cache>,
+    /// Flags joined with spaces, in Linux format
+    flags: String,
+}
+
+/// Processor description from `HKLM\HARDWARE\DESCRIPTION\System\CentralProcessor\0`.
+#[derive(Debug)]
+struct RegistryProcessor {
+    /// `ProcessorNameString`, the model string
+    name: String,
+    /// `VendorIdentifier`, the vendor ID (e.g. "GenuineIntel")
+    vendor: String,
+    /// `~MHz`, the rated clock speed
+    mhz: Option<u32>,
+}
+
+/// Core and cache topology from `GetLogicalProcessorInformationEx`.
+#[derive(Debug, Default)]
+struct ProcessorTopology {
+    physical_cores: u32,
+    logical_cores: u32,
+    /// Physical cores per efficiency class, highest class first
+    core_classes: Vec<(u8, u32)>,
+    /// One entry per distinct cache, with `instances` counting its copies
+    caches: Vec<Cache>,
+}
+
+/// Processor information reported by the WMI `Win32_Processor` class.
+///
+/// Multi-socket systems report one instance per package; core counts are summed
+/// across instances, everything else is taken from the first one.
+#[derive(Debug, Default)]
+struct WmiProcessor {
+    /// `Name`, the model string
+    name: Option<String>,
+    /// `Manufacturer`, the vendor ID (e.g. "GenuineIntel")
+    manufacturer: Option<String>,
+    /// `NumberOfCores`, summed across packages
+    physical_cores: Option<u32>,
+    /// `NumberOfLogicalProcessors`, summed across packages
+    logical_cores: Option<u32>,
+    /// `MaxClockSpeed` in MHz
+    max_clock_mhz: Option<u32>,
+    /// `L2CacheSize` in KB, for one package
+    l2_kb: Option<u32>,
+    /// `L3CacheSize` in KB, for one package
+    l3_kb: Option<u32>,
+    /// `NumberOfCores` of the first package, i.e. the cores sharing its caches
+    package_cores: Option<u32>,
 }
 
 impl WindowsCpuInfo {
+    /// Detect the CPU through the registry and `GetLogicalProcessorInformationEx`.
+    ///
+    /// Falls back to WMI for whatever the native APIs couldn't provide.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(WindowsCpuInfo)` on success, or `Err(String)` describing the
+    /// native failure when neither the native APIs nor WMI reported the processor.
     pub fn new() -> Result<Self, String> {
-       // TODO: Implement this later
-       return Ok(Self {
+        let mut info = Self {
             model: "Unknown".to_string(),
             vendor: "Unknown".to_string(),
+            architecture: std::env::consts::ARCH.to_string(),
             physical_cores: 0,
             logical_cores: 0,
+            core_classes: Vec::new(),
             base_mhz: None,
             l1_size: None,
             l2_size: None,
             l3_size: None,
-        });
+            caches: Vec::new(),
+            flags: cpuid::feature_flags(),
+        };
+
+        let registry = Self::read_registry_processor();
+        if let Ok(processor) = &registry {
+            info.model = processor.name.clone();
+            info.vendor = processor.vendor.clone();
+            info.base_mhz = processor.mhz.map(|mhz| mhz as f32);
+        }
+
+        let topology = Self::read_processor_information()
+            .and_then(|buffer| Self::parse_processor_information(&buffer));
+        if let Ok(topology) = topology.as_ref() {
+            info.apply_topology(topology);
+        }
+
+        if let Err(error) = registry.as_ref().and(topology.as_ref()) {
+            // WMI needs no elevated privileges, so it also covers locked-down machines
+            match Self::query_wmi() {
+                Some(wmi) => info.apply_wmi(wmi),
+                None => return Err(format!("could not detect the processor: {}", error)),
+            }
+        }
+
+        Ok(info)
     }
 
-    /// Display CPU information with logo (side-by-side layout).
+    /// Take core counts and cache sizes from the processor topology.
     ///
-    /// This function displays comprehensive CPU information alongside a vendor logo
-    /// in a side-by-side layout. The logo can be overridden to display a different
-    /// vendor's logo regardless of the actual CPU vendor.
-    pub fn display_info_with_logo(&self, logo_override: Option<&str>) {
-        let vendor_to_use = logo_override.unwrap_or(&self.vendor);
-        let logo_lines = get_logo_lines_for_vendor(vendor_to_use).unwrap_or_else(|| vec![]);
-        
-        let info_lines = self.get_info_lines();
-        
-        let logo_width = logo_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
-        let sep = "   ";
-        let max_lines = std::cmp::max(logo_lines.len(), info_lines.len());
+    /// Cache sizes are totals across all instances of a level, paired with the
+    /// number of physical cores, matching what WMI reports per package.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Topology parsed from `GetLogicalProcessorInformationEx`
+    fn apply_topology(&mut self, topology: &ProcessorTopology) {
+        self.physical_cores = topology.physical_cores;
+        self.logical_cores = topology.logical_cores;
+        self.core_classes = topology.core_classes.clone();
+        self.caches = topology.caches.clone();
 
-        // Print logo and info side by side
-        for i in 0..max_lines {
-            let logo = logo_lines.get(i).map(|s| s.as_str()).unwrap_or("");
-            let info = info_lines.get(i).map(|s| s.as_str()).unwrap_or("");
-            println!("{:<width$}{}{}", logo, sep, info, width=logo_width);
+        let level_total = |level: u32| {
+            let sizes: Vec<u32> = topology.caches.iter()
+                .filter(|c| c.level == level)
+                .map(|c| c.size_kb * c.instances.unwrap_or(1))
+                .collect();
+            (!sizes.is_empty()).then(|| (sizes.iter().sum(), topology.physical_cores))
+        };
+        self.l1_size = level_total(1);
+        self.l2_size = level_total(2);
+        self.l3_size = level_total(3);
+    }
+
+    /// Read the processor description from the registry.
+    ///
+    /// # Returns
+    ///
+    /// Returns the model, vendor, and clock speed of processor 0, or `Err(String)`
+    /// if the key or its string values can't be read.
+    #[cfg(windows)]
+    fn read_registry_processor() -> Result<RegistryProcessor, String> {
+        use std::ffi::c_void;
+
+        #[link(name = "advapi32")]
+        unsafe extern "system" {
+            fn RegGetValueW(
+                key: *mut c_void,
+                sub_key: *const u16,
+                value: *const u16,
+                flags: u32,
+                value_type: *mut u32,
+                data: *mut c_void,
+                data_len: *mut u32,
+            ) -> i32;
+        }
+
+        // Predefined handles are sign-extended 32-bit values
+        let hkey_local_machine = 0x8000_0002_u32 as i32 as isize as *mut c_void;
+        const RRF_RT_REG_SZ: u32 = 0x0000_0002;
+        const RRF_RT_REG_DWORD: u32 = 0x0000_0010;
+
+        let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+        let sub_key = wide("HARDWARE\\DESCRIPTION\\System\\CentralProcessor\\0");
+
+        let read_string = |name: &str| -> Result<String, String> {
+            let mut buffer = [0u16; 256];
+            let mut len = std::mem::size_of_val(&buffer) as u32;
+            // SAFETY: every pointer refers to a live local, and `len` holds the
+            // buffer size in bytes so the call never writes past it.
+            let status = unsafe {
+                RegGetValueW(
+                    hkey_local_machine,
+                    sub_key.as_ptr(),
+                    wide(name).as_ptr(),
+                    RRF_RT_REG_SZ,
+                    std::ptr::null_mut(),
+                    buffer.as_mut_ptr().cast(),
+                    &mut len,
+                )
+            };
+            if status != 0 {
+                return Err(format!("could not read {} from the registry (error {})", name, status));
+            }
+            let chars = (len as usize / 2).min(buffer.len());
+            Ok(String::from_utf16_lossy(&buffer[..chars]).trim_matches(char::from(0)).trim().to_string())
+        };
+
+        let mut mhz = 0u32;
+        let mut len = std::mem::size_of::<u32>() as u32;
+        // SAFETY: as above; the DWORD is written into `mhz`, which is `len` bytes.
+        let status = unsafe {
+            RegGetValueW(
+                hkey_local_machine,
+                sub_key.as_ptr(),
+                wide("~MHz").as_ptr(),
+                RRF_RT_REG_DWORD,
+                std::ptr::null_mut(),
+                (&mut mhz as *mut u32).cast(),
+                &mut len,
+            )
+        };
+
+        Ok(RegistryProcessor {
+            name: read_string("ProcessorNameString")?,
+            vendor: read_string("VendorIdentifier")?,
+            mhz: (status == 0 && mhz > 0).then_some(mhz),
+        })
+    }
+
+    /// Read the processor description from the registry (Windows only).
+    #[cfg(not(windows))]
+    fn read_registry_processor() -> Result<RegistryProcessor, String> {
+        Err("the processor registry key is only available on Windows".to_string())
+    }
+
+    /// Query all logical processor relationships.
+    ///
+    /// # Returns
+    ///
+    /// Returns the raw `SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX` records, or
+    /// `Err(String)` with the OS error if the call fails.
+    #[cfg(windows)]
+    fn read_processor_information() -> Result<Vec<u8>, String> {
+        unsafe extern "system" {
+            fn GetLogicalProcessorInformationEx(relationship: u32, buffer: *mut u8, returned_length: *mut u32) -> i32;
         }
+        const RELATION_ALL: u32 = 0xFFFF;
+
+        let mut len = 0u32;
+        // SAFETY: a null buffer with a zero length only asks for the required size.
+        unsafe { GetLogicalProcessorInformationEx(RELATION_ALL, std::ptr::null_mut(), &mut len) };
+        if len == 0 {
+            return Err(format!("GetLogicalProcessorInformationEx failed: {}", std::io::Error::last_os_error()));
+        }
+
+        let mut buffer = vec![0u8; len as usize];
+        // SAFETY: `buffer` is exactly `len` bytes long.
+        let ok = unsafe { GetLogicalProcessorInformationEx(RELATION_ALL, buffer.as_mut_ptr(), &mut len) };
+        if ok == 0 {
+            return Err(format!("GetLogicalProcessorInformationEx failed: {}", std::io::Error::last_os_error()));
+        }
+        buffer.truncate(len as usize);
+        Ok(buffer)
     }
 
-    /// Display CPU information without any logo.
+    /// Query all logical processor relationships (Windows only).
+    #[cfg(not(windows))]
+    fn read_processor_information() -> Result<Vec<u8>, String> {
+        Err("GetLogicalProcessorInformationEx is only available on Windows".to_string())
+    }
+
+    /// Parse the records returned by `GetLogicalProcessorInformationEx`.
     ///
-    /// This function displays comprehensive CPU information in a simple list format
-    /// without any vendor logo or side-by-side alignment.
-    pub fn display_info_no_logo(&self) {
-        let info_lines = self.get_info_lines();
-        
-        // Print CPU information without logo
-        for line in info_lines {
-            println!("{}", line);
+    /// Each record starts with its `Relationship` and `Size`. Processor-core records
+    /// (0) count physical cores, their group masks count logical processors, and
+    /// cache records (2) are merged into one `Cache` per distinct level, type, and
+    /// geometry. Other relationships are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Raw `SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX` records
+    ///
+    /// # Returns
+    ///
+    /// Returns the topology, or `Err(String)` if a record is truncated or no cores
+    /// were reported.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    fn parse_processor_information(buffer: &[u8]) -> Result<ProcessorTopology, String> {
+        const RELATION_PROCESSOR_CORE: u32 = 0;
+        const RELATION_CACHE: u32 = 2;
+        const MALFORMED: &str = "GetLogicalProcessorInformationEx returned a malformed record";
+
+        let u16_at = |record: &[u8], offset: usize| {
+            record.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or(MALFORMED)
+        };
+        let u32_at = |record: &[u8], offset: usize| {
+            record.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or(MALFORMED)
+        };
+        // GROUP_AFFINITY is a pointer-sized mask followed by the group number
+        let mask_bits = |record: &[u8], offset: usize| {
+            record.get(offset..offset + 8)
+                .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]).count_ones())
+                .ok_or(MALFORMED)
+        };
+
+        let mut topology = ProcessorTopology::default();
+        let mut offset = 0;
+        while offset + 8 <= buffer.len() {
+            let relationship = u32_at(buffer, offset)?;
+            let size = u32_at(buffer, offset + 4)? as usize;
+            let record = buffer.get(offset..offset + size).filter(|_| size >= 8).ok_or(MALFORMED)?;
+            offset += size;
+
+            match relationship {
+                RELATION_PROCESSOR_CORE => {
+                    let efficiency_class = *record.get(9).ok_or(MALFORMED)?;
+                    let group_count = u16_at(record, 30)? as usize;
+                    for group in 0..group_count {
+                        topology.logical_cores += mask_bits(record, 32 + group * 16)?;
+                    }
+                    topology.physical_cores += 1;
+                    match topology.core_classes.iter_mut().find(|(class, _)| *class == efficiency_class) {
+                        Some((_, count)) => *count += 1,
+                        None => topology.core_classes.push((efficiency_class, 1)),
+                    }
+                }
+                RELATION_CACHE => {
+                    let level = *record.get(8).ok_or(MALFORMED)? as u32;
+                    let associativity = *record.get(9).ok_or(MALFORMED)? as u32;
+                    let line_size = u16_at(record, 10)? as u32;
+                    let size_kb = u32_at(record, 12)? / 1024;
+                    let cache_type = match u32_at(record, 16)? {
+                        0 => CacheType::Unified,
+                        1 => CacheType::Instruction,
+                        2 => CacheType::Data,
+                        _ => continue, // trace caches
+                    };
+                    let shared_cpus = mask_bits(record, 40)?;
+
+                    let existing = topology.caches.iter_mut().find(|c| {
+                        c.level == level && c.cache_type == cache_type && c.size_kb == size_kb && c.shared_cpus == Some(shared_cpus)
+                    });
+                    match existing {
+                        Some(cache) => cache.instances = cache.instances.map(|n| n + 1),
+                        None => topology.caches.push(Cache {
+                            level,
+                            cache_type,
+                            size_kb,
+                            // 0xFF means fully associative
+                            associativity: (associativity != 0 && associativity != 0xFF).then_some(associativity),
+                            line_size: (line_size > 0).then_some(line_size),
+                            shared_cpus: Some(shared_cpus),
+                            instances: Some(1),
+                            inclusive: None,
+                        }),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if topology.physical_cores == 0 {
+            return Err("GetLogicalProcessorInformationEx reported no processor cores".to_string());
+        }
+        topology.core_classes.sort_by_key(|&(class, _)| std::cmp::Reverse(class));
+        topology.caches.sort_by_key(|c| (c.level, c.cache_type != CacheType::Data));
+        Ok(topology)
+    }
+
+    /// Fill in fields the native APIs didn't provide from WMI.
+    ///
+    /// Values that were already detected are kept, since the native APIs report
+    /// richer data (per-level cache sharing, L1 sizes) than `Win32_Processor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `wmi` - Processor information queried from WMI
+    fn apply_wmi(&mut self, wmi: WmiProcessor) {
+        if self.model == "Unknown" && let Some(name) = wmi.name {
+            self.model = name;
+        }
+        if self.vendor == "Unknown" && let Some(manufacturer) = wmi.manufacturer {
+            self.vendor = manufacturer;
+        }
+        if self.physical_cores == 0 {
+            self.physical_cores = wmi.physical_cores.unwrap_or(0);
+        }
+        if self.logical_cores == 0 {
+            self.logical_cores = wmi.logical_cores.unwrap_or(0);
+        }
+        self.base_mhz = self.base_mhz.or(wmi.max_clock_mhz.map(|mhz| mhz as f32));
+
+        // WMI cache sizes are per package, shared by all of that package's cores
+        let package_cores = wmi.package_cores.unwrap_or(1);
+        self.l2_size = self.l2_size.or(wmi.l2_kb.map(|kb| (kb, package_cores)));
+        self.l3_size = self.l3_size.or(wmi.l3_kb.map(|kb| (kb, package_cores)));
+    }
+
+    /// Query `Win32_Processor` through PowerShell's `Get-CimInstance`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed processor information, or `None` if PowerShell is not
+    /// available or the query failed.
+    fn query_wmi() -> Option<WmiProcessor> {
+        let output = Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "Get-CimInstance Win32_Processor | Format-List Name,Manufacturer,NumberOfCores,NumberOfLogicalProcessors,MaxClockSpeed,L2CacheSize,L3CacheSize",
+            ])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
         }
+        Some(Self::parse_wmi_list(&String::from_utf8_lossy(&output.stdout)))
     }
 
-    /// Get the formatted information lines for display.
+    /// Parse `Format-List` output of `Win32_Processor` instances.
+    ///
+    /// Each instance is a block of `Property : Value` lines; blocks are separated by
+    /// blank lines. PowerShell's CRLF line endings are handled by `str::lines`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Text printed by `Format-List`
+    fn parse_wmi_list(output: &str) -> WmiProcessor {
+        let mut wmi = WmiProcessor::default();
+        let add = |total: Option<u32>, value: Option<u32>| match (total, value) {
+            (Some(total), Some(value)) => Some(total + value),
+            (total, value) => total.or(value),
+        };
+
+        let mut instance = 0;
+        let mut in_block = false;
+        for line in output.lines().map(str::trim) {
+            if line.is_empty() {
+                if in_block {
+                    instance += 1;
+                    in_block = false;
+                }
+                continue;
+            }
+            in_block = true;
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let first = instance == 0;
+            let value = value.trim();
+            let number = value.parse::<u32>().ok().filter(|&n| n > 0);
+            match key.trim() {
+                "Name" if first && !value.is_empty() => wmi.name = Some(value.to_string()),
+                "Manufacturer" if first && !value.is_empty() => wmi.manufacturer = Some(value.to_string()),
+                "NumberOfCores" => {
+                    wmi.physical_cores = add(wmi.physical_cores, number);
+                    if first {
+                        wmi.package_cores = number;
+                    }
+                }
+                "NumberOfLogicalProcessors" => wmi.logical_cores = add(wmi.logical_cores, number),
+                "MaxClockSpeed" if first => wmi.max_clock_mhz = number,
+                "L2CacheSize" if first => wmi.l2_kb = number,
+                "L3CacheSize" if first => wmi.l3_kb = number,
+                _ => {}
+            }
+        }
+
+        wmi
+    }
+
+    /// Get the formatted information lines for display, excluding flags.
     ///
     /// This helper function generates the formatted CPU information lines
     /// that are used by both logo and no-logo display methods.
-    fn get_info_lines(&self) -> Vec<String> {
+    fn get_info_lines(&self, opts: &DisplayOptions) -> Vec<String> {
         let mut lines = vec![
             format!("Name: {}", self.model),
             format!("Vendor: {}", self.vendor),
             format!("Cores: {} cores ({} threads)", self.physical_cores, self.logical_cores),
         ];
+
+        // Hybrid CPUs report more than one efficiency class
+        if let [(_, p_cores), (_, e_cores)] = self.core_classes[..] {
+            lines.push(format!("Core Types: {} P-cores + {} E-cores", p_cores, e_cores));
+        } else if self.core_classes.len() > 2 {
+            let classes: Vec<String> = self.core_classes.iter()
+                .map(|(class, count)| format!("{} class {}", count, class))
+                .collect();
+            lines.push(format!("Core Types: {}", classes.join(" + ")));
+        }
         
         if let Some(mhz) = self.base_mhz {
             lines.push(format!("Base Frequency: {:.2} MHz", mhz));
         }
         
-        if let Some((l1, l1_count)) = self.l1_size {
-            lines.push(format!("L1 Cache Size: {} KB ({} cores)", l1, l1_count));
+        if opts.compact_cache {
+            lines.extend(display::compact_cache_line(&[
+                ("L1", self.l1_size.map(|(size, _)| size.into())),
+                ("L2", self.l2_size.map(|(size, _)| size.into())),
+                ("L3", self.l3_size.map(|(size, _)| size.into())),
+            ]));
+        } else {
+            if let Some((l1, l1_count)) = self.l1_size {
+                lines.push(format!("L1 Cache Size: {} KB ({} cores)", l1, l1_count));
+            }
+            
+            if let Some((l2, l2_count)) = self.l2_size {
+                lines.push(format!("L2 Cache Size: {} KB ({} cores)", l2, l2_count));
+            }
+            
+            if let Some((l3, l3_count)) = self.l3_size {
+                lines.push(format!("L3 Cache Size: {} KB ({} cores)", l3, l3_count));
+            }
         }
         
-        if let Some((l2, l2_count)) = self.l2_size {
-            lines.push(format!("L2 Cache Size: {} KB ({} cores)", l2, l2_count));
+        if opts.show_cache_details() {
+            lines.extend(display::cache_detail_lines(&self.caches()));
         }
         
-        if let Some((l3, l3_count)) = self.l3_size {
-            lines.push(format!("L3 Cache Size: {} KB ({} cores)", l3, l3_count));
+        display::relabel(&mut lines, opts);
+        lines
+    }
+
+    /// Get the wrapped flag lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options (grouped flags, flag limit)
+    /// * `wrap_width` - Width available to the info column
+    fn flag_lines(&self, opts: &DisplayOptions, wrap_width: usize) -> Vec<String> {
+        if self.flags.is_empty() {
+            return Vec::new();
         }
+        display::flag_lines(&self.flags, " ", wrap_width, opts)
+    }
+}
+
+impl CpuInfo for WindowsCpuInfo {
+    fn caches(&self) -> Vec<Cache> {
+        self.caches.clone()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// The architecture isn't displayed on Windows yet; the override only reaches
+    /// the JSON output.
+    fn force_architecture(&mut self, architecture: &str) {
+        self.architecture = architecture.to_string();
+    }
+
+    /// Flags come from CPUID, since Windows doesn't list them itself.
+    fn flags(&self) -> &str {
+        &self.flags
+    }
+
+    fn max_frequency_ghz(&self) -> Option<f32> {
+        self.base_mhz.map(|mhz| mhz / 1000.0)
+    }
+
+    fn fingerprint_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("vendor", self.vendor.clone()),
+            ("model", self.model.clone()),
+            ("physical_cores", self.physical_cores.to_string()),
+            ("logical_cores", self.logical_cores.to_string()),
+            ("l1_kb", cpu::canonical_size(self.l1_size.map(|(size, _)| size))),
+            ("l2_kb", cpu::canonical_size(self.l2_size.map(|(size, _)| size))),
+            ("l3_kb", cpu::canonical_size(self.l3_size.map(|(size, _)| size))),
+            ("flags", cpu::canonical_flags(&self.flags)),
+        ]
+    }
+
+    fn to_json(&self) -> JsonValue {
+        json::CommonFields {
+            model: &self.model,
+            vendor: &self.vendor,
+            architecture: Some(&self.architecture),
+            byte_order: Some(cpu::byte_order()),
+            physical_cores: Some(self.physical_cores),
+            logical_cores: self.logical_cores,
+            max_frequency_ghz: self.max_frequency_ghz(),
+            l1d_kb: json::cache_total_kb(&self.caches, "L1d"),
+            l1i_kb: json::cache_total_kb(&self.caches, "L1i"),
+            l2_kb: json::cache_total_kb(&self.caches, "L2"),
+            l3_kb: json::cache_total_kb(&self.caches, "L3"),
+            caches: &self.caches,
+            flags: &self.flags,
+        }.into_json(vec![
+            ("base_frequency_mhz".to_string(), self.base_mhz.map_or(JsonValue::Null, JsonValue::from_f32)),
+        ])
+    }
+
+    /// Display CPU information with logo (side-by-side layout).
+    ///
+    /// This function displays comprehensive CPU information alongside a vendor logo
+    /// in a side-by-side layout. The logo can be overridden to display a different
+    /// vendor's logo regardless of the actual CPU vendor.
+    fn display_info_with_logo(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let vendor_to_use = logo_override.unwrap_or(&self.vendor);
+        let (logo_lines, logo_colors) = get_logo_for_vendor(vendor_to_use, opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+            .map_or_else(Default::default, |l| (l.lines, l.colors));
         
-        lines
+        let wrap_width = opts.resolve_width(display::DEFAULT_WIDTH).saturating_sub(display::info_column_offset(&logo_lines, opts));
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, wrap_width));
+        
+        // Print logo and info side by side
+        display::print_side_by_side(&logo_lines, &logo_colors, &info_lines, opts);
     }
-} 
\ No newline at end of file
+
+    /// Display CPU information without any logo.
+    ///
+    /// This function displays comprehensive CPU information in a simple list format
+    /// without any vendor logo or side-by-side alignment.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - Display options (compact cache line)
+    fn display_info_no_logo(&self, opts: &DisplayOptions) {
+        // Print CPU information without logo
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, opts.resolve_width(display::DEFAULT_NO_LOGO_WIDTH)));
+        display::print_lines(&info_lines, opts);
+    }
+
+    /// Display CPU information as a self-contained HTML snippet (`--format html`).
+    ///
+    /// # Arguments
+    ///
+    /// * `logo_override` - Optional vendor ID to override the detected vendor logo
+    /// * `opts` - Display options (colors, logo)
+    fn display_info_html(&self, logo_override: Option<&str>, opts: &DisplayOptions) {
+        let logo_lines = if opts.no_logo {
+            Vec::new()
+        } else {
+            get_logo_for_vendor(logo_override.unwrap_or(&self.vendor), opts.use_color(), opts.logo_colors.as_deref(), opts.background)
+                .map(|l| l.lines)
+                .unwrap_or_default()
+        };
+
+        let mut info_lines = self.get_info_lines(opts);
+        info_lines.extend(self.flag_lines(opts, usize::MAX));
+        display::print_html(&logo_lines, &info_lines);
+    }
+}